@@ -5,9 +5,12 @@ use color_eyre::{
 use convert_case::{Case, Casing};
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use source_analyzer::{CyclerInstances, CyclerType, CyclerTypes, Nodes};
+use source_analyzer::{CyclerInstances, CyclerType, CyclerTypes, Nodes, PathSegment};
 
-use super::{node::Node, other_cycler::OtherCycler};
+use super::{
+    accessor::path_to_accessor_token_stream, node::Node, other_cycler::OtherCycler,
+    reference_type::ReferenceType,
+};
 
 pub fn get_cyclers<'a>(
     cycler_instances: &'a CyclerInstances,
@@ -31,6 +34,12 @@ pub fn get_cyclers<'a>(
                     cycler_types,
                     cycler_module_name,
                 },
+                CyclerType::Periodic => Cycler::Periodic {
+                    cycler_instances,
+                    nodes,
+                    cycler_types,
+                    cycler_module_name,
+                },
             }
         })
         .collect()
@@ -66,6 +75,12 @@ pub enum Cycler<'a> {
         cycler_types: &'a CyclerTypes,
         cycler_module_name: &'a str,
     },
+    Periodic {
+        cycler_instances: &'a CyclerInstances,
+        nodes: &'a Nodes,
+        cycler_types: &'a CyclerTypes,
+        cycler_module_name: &'a str,
+    },
 }
 
 impl Cycler<'_> {
@@ -77,6 +92,9 @@ impl Cycler<'_> {
             Cycler::RealTime {
                 cycler_instances, ..
             } => cycler_instances,
+            Cycler::Periodic {
+                cycler_instances, ..
+            } => cycler_instances,
         }
     }
 
@@ -84,6 +102,7 @@ impl Cycler<'_> {
         match self {
             Cycler::Perception { nodes, .. } => nodes,
             Cycler::RealTime { nodes, .. } => nodes,
+            Cycler::Periodic { nodes, .. } => nodes,
         }
     }
 
@@ -91,6 +110,7 @@ impl Cycler<'_> {
         match self {
             Cycler::Perception { cycler_types, .. } => cycler_types,
             Cycler::RealTime { cycler_types, .. } => cycler_types,
+            Cycler::Periodic { cycler_types, .. } => cycler_types,
         }
     }
 
@@ -102,6 +122,9 @@ impl Cycler<'_> {
             Cycler::RealTime {
                 cycler_module_name, ..
             } => cycler_module_name,
+            Cycler::Periodic {
+                cycler_module_name, ..
+            } => cycler_module_name,
         }
     }
 
@@ -113,6 +136,7 @@ impl Cycler<'_> {
         let cycler_module_name_identifier = self.get_cycler_module_name_identifier();
         quote! {
             #[derive(Clone, Default, serde::Deserialize, serde::Serialize, serialize_hierarchy::SerializeHierarchy)]
+            #[serde(default)]
             pub struct Database {
                 pub main_outputs: structs::#cycler_module_name_identifier::MainOutputs,
                 pub additional_outputs: structs::#cycler_module_name_identifier::AdditionalOutputs,
@@ -123,7 +147,7 @@ impl Cycler<'_> {
     pub fn get_own_producer_identifier(&self) -> TokenStream {
         match self {
             Cycler::Perception { .. } => quote! { own_producer, },
-            Cycler::RealTime { .. } => Default::default(),
+            Cycler::RealTime { .. } | Cycler::Periodic { .. } => Default::default(),
         }
     }
 
@@ -140,7 +164,7 @@ impl Cycler<'_> {
         let own_producer_type = self.get_own_producer_type();
         match self {
             Cycler::Perception { .. } => quote! { own_producer: #own_producer_type, },
-            Cycler::RealTime { .. } => Default::default(),
+            Cycler::RealTime { .. } | Cycler::Periodic { .. } => Default::default(),
         }
     }
 
@@ -148,6 +172,9 @@ impl Cycler<'_> {
         match self {
             Cycler::Perception {
                 cycler_instances, ..
+            }
+            | Cycler::Periodic {
+                cycler_instances, ..
             } => self
                 .get_cycler_types()
                 .cycler_modules_to_cycler_types
@@ -162,7 +189,7 @@ impl Cycler<'_> {
                                     cycler_module_name: other_cycler_module_name,
                                 }),
                         ),
-                        CyclerType::Perception => None,
+                        CyclerType::Perception | CyclerType::Periodic => None,
                     },
                 )
                 .flatten()
@@ -183,7 +210,7 @@ impl Cycler<'_> {
                                     cycler_module_name: other_cycler_module_name,
                                 }),
                         ),
-                        CyclerType::RealTime => None,
+                        CyclerType::RealTime | CyclerType::Periodic => None,
                     },
                 )
                 .flatten()
@@ -316,6 +343,20 @@ impl Cycler<'_> {
             .collect()
     }
 
+    pub fn get_node_recreations_identifiers(&self) -> Vec<Ident> {
+        self.get_interpreted_nodes()
+            .into_iter()
+            .map(|node| node.get_recreations_identifier())
+            .collect()
+    }
+
+    pub fn get_node_recreations_fields(&self) -> Vec<TokenStream> {
+        self.get_interpreted_nodes()
+            .into_iter()
+            .map(|node| node.get_recreations_field())
+            .collect()
+    }
+
     pub fn get_node_initializers(&self) -> Result<Vec<TokenStream>> {
         self.get_interpreted_nodes()
             .into_iter()
@@ -330,13 +371,45 @@ impl Cycler<'_> {
             .collect()
     }
 
+    pub fn get_recording_parameter_accessor(&self, name: &str) -> TokenStream {
+        self.get_cycler_instance_parameter_accessor("recording", name)
+    }
+
+    pub fn get_cycle_timings_parameter_accessor(&self, name: &str) -> TokenStream {
+        self.get_cycler_instance_parameter_accessor("cycle_timings", name)
+    }
+
+    pub fn get_cycle_period_accessor(&self) -> TokenStream {
+        self.get_cycler_instance_accessor(
+            &["cycle_periods", "$cycler_instance"].map(PathSegment::from),
+        )
+    }
+
+    fn get_cycler_instance_parameter_accessor(&self, prefix: &str, name: &str) -> TokenStream {
+        self.get_cycler_instance_accessor(
+            &[prefix, "$cycler_instance", name].map(PathSegment::from),
+        )
+    }
+
+    fn get_cycler_instance_accessor(&self, path: &[PathSegment]) -> TokenStream {
+        let cycler_module_name_identifier = self.get_cycler_module_name_identifier();
+        path_to_accessor_token_stream(
+            quote! { configuration },
+            path,
+            ReferenceType::Immutable,
+            quote! { self.instance },
+            quote! { #cycler_module_name_identifier::CyclerInstance:: },
+            &self.get_cycler_instances().modules_to_instances[self.get_cycler_module_name()],
+        )
+    }
+
     pub fn get_struct_definition(&self) -> TokenStream {
         let database_struct = self.get_database_struct();
         let own_producer_field = self.get_own_producer_field();
         let other_cycler_fields = self.get_other_cycler_fields();
         let cycler_module_name_identifier = self.get_cycler_module_name_identifier();
         let real_time_fields = match self {
-            Cycler::Perception { .. } => Default::default(),
+            Cycler::Perception { .. } | Cycler::Periodic { .. } => Default::default(),
             Cycler::RealTime {
                 cycler_module_name, ..
             } => {
@@ -351,6 +424,7 @@ impl Cycler<'_> {
             }
         };
         let node_fields = self.get_node_fields();
+        let node_recreations_fields = self.get_node_recreations_fields();
 
         quote! {
             #database_struct
@@ -359,14 +433,20 @@ impl Cycler<'_> {
                 instance: #cycler_module_name_identifier::CyclerInstance,
                 hardware_interface: std::sync::Arc<Interface>,
                 own_writer: framework::Writer<Database>,
+                own_reader: framework::Reader<Database>,
                 #own_producer_field
                 #(#other_cycler_fields,)*
                 own_changed: std::sync::Arc<tokio::sync::Notify>,
                 own_subscribed_outputs_reader: framework::Reader<std::collections::HashSet<String>>,
                 configuration_reader: framework::Reader<structs::Configuration>,
+                recording_sender: Option<std::sync::mpsc::SyncSender<framework::RecordingFrame>>,
                 #real_time_fields
+                cycle_timings: types::CycleTimings,
+                budget_exceedances: types::BudgetExceedances,
+                cycle_count: usize,
                 persistent_state: structs::#cycler_module_name_identifier::PersistentState,
                 #(#node_fields,)*
+                #(#node_recreations_fields,)*
             }
         }
     }
@@ -381,24 +461,27 @@ impl Cycler<'_> {
         let own_producer_identifier = self.get_own_producer_identifier();
         let other_cycler_identifiers = self.get_other_cycler_identifiers();
         let real_time_initializers = match self {
-            Cycler::Perception { .. } => Default::default(),
+            Cycler::Perception { .. } | Cycler::Periodic { .. } => Default::default(),
             Cycler::RealTime { .. } => quote! {
                 historic_databases: Default::default(),
                 perception_databases: Default::default(),
             },
         };
         let node_identifiers = self.get_node_identifiers();
+        let node_recreations_identifiers = self.get_node_recreations_identifiers();
 
         Ok(quote! {
             pub fn new(
                 instance: #cycler_module_name_identifier::CyclerInstance,
                 hardware_interface: std::sync::Arc<Interface>,
                 own_writer: framework::Writer<Database>,
+                own_reader: framework::Reader<Database>,
                 #own_producer_field
                 #(#other_cycler_fields,)*
                 own_changed: std::sync::Arc<tokio::sync::Notify>,
                 own_subscribed_outputs_reader: framework::Reader<std::collections::HashSet<String>>,
                 configuration_reader: framework::Reader<structs::Configuration>,
+                recording_sender: Option<std::sync::mpsc::SyncSender<framework::RecordingFrame>>,
             ) -> color_eyre::Result<Self> {
                 use color_eyre::eyre::WrapErr;
                 let configuration = configuration_reader.next().clone();
@@ -408,20 +491,59 @@ impl Cycler<'_> {
                     instance,
                     hardware_interface,
                     own_writer,
+                    own_reader,
                     #own_producer_identifier
                     #(#other_cycler_identifiers,)*
                     own_changed,
                     own_subscribed_outputs_reader,
                     configuration_reader,
+                    recording_sender,
                     #real_time_initializers
+                    cycle_timings: Default::default(),
+                    budget_exceedances: Default::default(),
+                    cycle_count: 0,
                     persistent_state,
                     #(#node_identifiers,)*
+                    #(#node_recreations_identifiers,)*
                 })
             }
         })
     }
 
     pub fn get_start_method(&self) -> TokenStream {
+        let (before_first_cycle, after_cycle) = match self {
+            Cycler::Perception { .. } | Cycler::RealTime { .. } => Default::default(),
+            Cycler::Periodic { .. } => {
+                let cycle_period_accessor = self.get_cycle_period_accessor();
+                (
+                    quote! {
+                        let timer_runtime = tokio::runtime::Builder::new_current_thread()
+                            .enable_time()
+                            .build()
+                            .wrap_err("failed to create timer runtime")?;
+                        let mut next_cycle_start = std::time::Instant::now();
+                    },
+                    // overrunning cycles delay the following ones instead of being caught up,
+                    // cancelling wakes the cycler up immediately
+                    quote! {
+                        let period = {
+                            let configuration = self.configuration_reader.next();
+                            *(#cycle_period_accessor)
+                        };
+                        next_cycle_start = std::cmp::max(
+                            next_cycle_start + period,
+                            std::time::Instant::now(),
+                        );
+                        timer_runtime.block_on(async {
+                            tokio::select! {
+                                _ = keep_running.cancelled() => {},
+                                _ = tokio::time::sleep_until(next_cycle_start.into()) => {},
+                            }
+                        });
+                    },
+                )
+            }
+        };
         quote! {
             pub fn start(
                 mut self,
@@ -432,13 +554,19 @@ impl Cycler<'_> {
                 std::thread::Builder::new()
                     .name(instance_name.clone())
                     .spawn(move || {
+                        #before_first_cycle
                         while !keep_running.is_cancelled() {
                             if let Err(error) = self.cycle() {
+                                // hardware interfaces fail pending calls once termination is requested
+                                if keep_running.is_cancelled() {
+                                    break;
+                                }
                                 keep_running.cancel();
                                 return Err(error).wrap_err_with(|| {
                                     format!("failed to execute cycle of cycler `{:?}`", self.instance)
                                 });
                             }
+                            #after_cycle
                         }
                         Ok(())
                     })
@@ -458,46 +586,81 @@ impl Cycler<'_> {
             bail!("expected at least one node");
         }
 
+        // the first node of perception and real-time cyclers blocks until new data arrives, so
+        // their cycle duration is measured from when it returns
+        let cycle_start = quote! {
+            let cycle_start = std::time::Instant::now();
+        };
+        let before_first_node_cycle_start = match self {
+            Cycler::Perception { .. } | Cycler::RealTime { .. } => Default::default(),
+            Cycler::Periodic { .. } => cycle_start.clone(),
+        };
         let before_first_node = quote! {
+            #before_first_node_cycle_start
             let mut own_database = self.own_writer.next();
             let own_database_reference = {
                 use std::ops::DerefMut;
                 own_database.deref_mut()
             };
         };
+        let recording_enabled_accessor = self.get_recording_parameter_accessor("enabled");
+        let recording_additional_outputs_accessor =
+            self.get_recording_parameter_accessor("additional_outputs");
+        let recorded_additional_outputs = quote! {
+            let recording_enabled = self.recording_sender.is_some() && *(#recording_enabled_accessor);
+            let recorded_additional_outputs: &[String] = match recording_enabled {
+                true => (#recording_additional_outputs_accessor).as_slice(),
+                false => &[],
+            };
+        };
+        let other_cycler_databases = self.get_perception_cycler_databases();
         let (first_node, remaining_nodes) = node_executions.split_at(1);
         let first_node = {
             let first_node = &first_node[0];
+            // periodic cyclers are not woken by their first node, so it may read other cyclers too
+            let first_node_other_cycler_databases = match self {
+                Cycler::Perception { .. } | Cycler::RealTime { .. } => Default::default(),
+                Cycler::Periodic { .. } => quote! { #(#other_cycler_databases)* },
+            };
             quote! {
                 {
                     let own_subscribed_outputs = self.own_subscribed_outputs_reader.next();
                     let configuration = self.configuration_reader.next();
+                    #recorded_additional_outputs
+                    #first_node_other_cycler_databases
                     #first_node
                 }
             }
         };
         let after_first_node = match self {
             Cycler::Perception { .. } => quote! {
+                #cycle_start
+                let recording_timestamp = self.hardware_interface.get_now();
                 self.own_producer.announce();
             },
+            Cycler::Periodic { .. } => quote! {
+                let recording_timestamp = self.hardware_interface.get_now();
+            },
             Cycler::RealTime { .. } => {
                 let perception_cycler_updates = self.get_perception_cycler_updates();
 
                 quote! {
+                    #cycle_start
                     let now = self.hardware_interface.get_now();
+                    let recording_timestamp = now;
                     self.perception_databases.update(now, framework::Updates {
                         #(#perception_cycler_updates,)*
                     });
                 }
             }
         };
-        let other_cycler_databases = self.get_perception_cycler_databases();
         let remaining_nodes = match remaining_nodes.is_empty() {
             true => Default::default(),
             false => quote! {
                 {
                     let own_subscribed_outputs = self.own_subscribed_outputs_reader.next();
                     let configuration = self.configuration_reader.next();
+                    #recorded_additional_outputs
                     #(#other_cycler_databases)*
                     #(#remaining_nodes)*
                 }
@@ -507,6 +670,7 @@ impl Cycler<'_> {
             Cycler::Perception { .. } => quote! {
                 self.own_producer.finalize(own_database_reference.main_outputs.clone());
             },
+            Cycler::Periodic { .. } => Default::default(),
             Cycler::RealTime { .. } => quote! {
                 self.historic_databases.update(
                     now,
@@ -516,8 +680,56 @@ impl Cycler<'_> {
                 );
             },
         };
+        let cycle_timings_budget_accessor = self.get_cycle_timings_parameter_accessor("budget");
+        let cycle_timings = quote! {
+            {
+                let cycle_duration = cycle_start.elapsed();
+                self.cycle_timings.record_cycle(cycle_duration);
+                let own_subscribed_outputs = self.own_subscribed_outputs_reader.next();
+                let configuration = self.configuration_reader.next();
+                if let Some(budget) = *(#cycle_timings_budget_accessor) {
+                    // an overloaded robot would otherwise warn in every cycle
+                    if cycle_duration > budget {
+                        if let Some(amount_of_cycles) = self
+                            .budget_exceedances
+                            .count(std::time::Instant::now(), std::time::Duration::from_secs(1))
+                        {
+                            log::warn!(
+                                "{amount_of_cycles} cycle(s) of {instance_name} exceeded the budget of {budget:?} since the last warning, the last one took {cycle_duration:?}"
+                            );
+                        }
+                    }
+                }
+                #recorded_additional_outputs
+                if own_subscribed_outputs
+                    .iter()
+                    .chain(recorded_additional_outputs.iter())
+                    .any(|subscribed_output| framework::should_be_filled(subscribed_output, "additional_outputs.cycle_timings"))
+                {
+                    own_database_reference.additional_outputs.cycle_timings = Some(self.cycle_timings.clone());
+                }
+            }
+        };
+        // frames are dropped instead of blocking the cycler if the recorder lags behind
+        let recording = quote! {
+            if let Some(recording_sender) = &self.recording_sender {
+                let configuration = self.configuration_reader.next();
+                if *(#recording_enabled_accessor) {
+                    let data = framework::encode_database(&*own_database_reference)
+                        .wrap_err("failed to serialize database for recording")?;
+                    let _ = recording_sender.try_send(framework::RecordingFrame {
+                        cycler_instance: instance_name.clone(),
+                        frame: framework::Frame {
+                            timestamp: recording_timestamp,
+                            data,
+                        },
+                    });
+                }
+            }
+        };
         let after_dropping_database_writer_guard = quote! {
             self.own_changed.notify_one();
+            self.cycle_count = self.cycle_count.wrapping_add(1);
         };
 
         Ok(quote! {
@@ -531,6 +743,8 @@ impl Cycler<'_> {
                     #after_first_node
                     #remaining_nodes
                     #after_remaining_nodes
+                    #cycle_timings
+                    #recording
                 }
                 #after_dropping_database_writer_guard
                 Ok(())
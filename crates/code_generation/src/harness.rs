@@ -0,0 +1,503 @@
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use source_analyzer::{Field, PathSegment};
+
+use super::{
+    accessor::path_to_accessor_token_stream, cycler::Cycler, node::Node, other_cycler::OtherCycler,
+    reference_type::ReferenceType,
+};
+
+pub fn generate_harnesses(cyclers: &[Cycler]) -> Result<TokenStream> {
+    let modules: Vec<_> = cyclers
+        .iter()
+        .map(|cycler| {
+            get_module(cycler).wrap_err_with(|| {
+                format!(
+                    "failed to get harness module for cycler `{}`",
+                    cycler.get_cycler_module_name()
+                )
+            })
+        })
+        .collect::<Result<_, _>>()
+        .wrap_err("failed to get harness modules")?;
+
+    Ok(quote! {
+        #[cfg(feature = "harness")]
+        pub mod harness {
+            #(#modules)*
+        }
+    })
+}
+
+fn get_module(cycler: &Cycler) -> Result<TokenStream> {
+    let cycler_module_name_identifier = cycler.get_cycler_module_name_identifier();
+    let fixture = get_fixture(cycler);
+    let harnesses: Vec<_> = cycler
+        .get_interpreted_nodes()
+        .iter()
+        .map(|node| {
+            get_harness(cycler, node)
+                .wrap_err_with(|| format!("failed to get harness for node `{}`", node.node_name))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(quote! {
+        #[allow(dead_code, unused_mut, unused_variables)]
+        pub mod #cycler_module_name_identifier {
+            pub type Values = std::collections::BTreeMap<String, serde_json::Value>;
+
+            fn apply<T>(target: &mut T, values: &Values) -> color_eyre::Result<()>
+            where
+                T: serialize_hierarchy::SerializeHierarchy,
+            {
+                use color_eyre::eyre::WrapErr;
+                for (path, value) in values {
+                    target
+                        .deserialize_path(path, value.clone())
+                        .wrap_err_with(|| format!("failed to apply fixture value to `{path}`"))?;
+                }
+                Ok(())
+            }
+
+            #fixture
+            #(#harnesses)*
+        }
+    })
+}
+
+fn get_fixture(cycler: &Cycler) -> TokenStream {
+    let cycler_module_name_identifier = cycler.get_cycler_module_name_identifier();
+    let other_cyclers = cycler.get_other_cyclers();
+    let reader_identifiers: Vec<_> = other_cyclers
+        .iter()
+        .filter_map(|other_cycler| match other_cycler {
+            OtherCycler::Reader {
+                cycler_instance_name,
+                ..
+            } => Some(format_ident!(
+                "{}",
+                cycler_instance_name.to_case(Case::Snake)
+            )),
+            OtherCycler::Consumer { .. } => None,
+        })
+        .collect();
+    let reader_database_getters = other_cyclers.iter().filter_map(|other_cycler| match other_cycler {
+        OtherCycler::Reader {
+            cycler_instance_name,
+            cycler_module_name,
+        } => {
+            let field_identifier = format_ident!("{}", cycler_instance_name.to_case(Case::Snake));
+            let getter_identifier =
+                format_ident!("{}_database", cycler_instance_name.to_case(Case::Snake));
+            let other_cycler_module_name_identifier = format_ident!("{}", cycler_module_name);
+            let error_message = format!("failed to apply main outputs of `{cycler_instance_name}`");
+            Some(quote! {
+                fn #getter_identifier(&self) -> color_eyre::Result<crate::#other_cycler_module_name_identifier::Database> {
+                    use color_eyre::eyre::WrapErr;
+                    let mut database = crate::#other_cycler_module_name_identifier::Database::default();
+                    apply(&mut database.main_outputs, &self.#field_identifier).wrap_err(#error_message)?;
+                    Ok(database)
+                }
+            })
+        }
+        OtherCycler::Consumer { .. } => None,
+    });
+    let consumer_identifiers: Vec<_> = other_cyclers
+        .iter()
+        .filter_map(|other_cycler| match other_cycler {
+            OtherCycler::Consumer {
+                cycler_instance_name,
+                ..
+            } => Some(format_ident!(
+                "{}",
+                cycler_instance_name.to_case(Case::Snake)
+            )),
+            OtherCycler::Reader { .. } => None,
+        })
+        .collect();
+    let consumer_module_name_identifiers: Vec<_> = other_cyclers
+        .iter()
+        .filter_map(|other_cycler| match other_cycler {
+            OtherCycler::Consumer {
+                cycler_module_name, ..
+            } => Some(format_ident!("{}", cycler_module_name)),
+            OtherCycler::Reader { .. } => None,
+        })
+        .collect();
+
+    let (real_time_fields, real_time_definitions) = match cycler {
+        Cycler::Perception { .. } | Cycler::Periodic { .. } => Default::default(),
+        Cycler::RealTime { .. } => (
+            quote! {
+                pub now: Option<std::time::SystemTime>,
+                pub historic: Vec<HistoricFrame>,
+                pub perception: Vec<PerceptionFrame>,
+            },
+            quote! {
+                #[derive(Clone, Debug, serde::Deserialize)]
+                pub struct HistoricFrame {
+                    pub system_time: std::time::SystemTime,
+                    #[serde(default)]
+                    pub main_outputs: Values,
+                }
+
+                #[derive(Clone, Debug, serde::Deserialize)]
+                pub struct PerceptionFrame {
+                    pub system_time: std::time::SystemTime,
+                    #[serde(default)]
+                    pub temporary: bool,
+                    #(
+                        #[serde(default)]
+                        pub #consumer_identifiers: Vec<Values>,
+                    )*
+                }
+
+                impl Fixture {
+                    fn historic_databases(
+                        &self,
+                    ) -> color_eyre::Result<std::collections::BTreeMap<std::time::SystemTime, structs::#cycler_module_name_identifier::MainOutputs>> {
+                        use color_eyre::eyre::WrapErr;
+                        self.historic
+                            .iter()
+                            .map(|frame| {
+                                let mut main_outputs = structs::#cycler_module_name_identifier::MainOutputs::default();
+                                apply(&mut main_outputs, &frame.main_outputs)
+                                    .wrap_err("failed to apply historic main outputs")?;
+                                Ok((frame.system_time, main_outputs))
+                            })
+                            .collect()
+                    }
+
+                    fn perception_databases(
+                        &self,
+                        temporary: bool,
+                    ) -> color_eyre::Result<std::collections::BTreeMap<std::time::SystemTime, framework::Databases>> {
+                        use color_eyre::eyre::WrapErr;
+                        self.perception
+                            .iter()
+                            .filter(|frame| frame.temporary == temporary)
+                            .map(|frame| {
+                                let databases = framework::Databases {
+                                    #(
+                                        #consumer_identifiers: frame
+                                            .#consumer_identifiers
+                                            .iter()
+                                            .map(|values| {
+                                                let mut main_outputs = structs::#consumer_module_name_identifiers::MainOutputs::default();
+                                                apply(&mut main_outputs, values)
+                                                    .wrap_err("failed to apply perception main outputs")?;
+                                                Ok(main_outputs)
+                                            })
+                                            .collect::<color_eyre::Result<_>>()?,
+                                    )*
+                                };
+                                Ok((frame.system_time, databases))
+                            })
+                            .collect()
+                    }
+                }
+            },
+        ),
+    };
+
+    quote! {
+        #[derive(Clone, Debug, Default, serde::Deserialize)]
+        #[serde(default)]
+        pub struct Fixture {
+            pub main_outputs: Values,
+            pub persistent_state: Values,
+            #real_time_fields
+            #(pub #reader_identifiers: Values,)*
+        }
+
+        impl Fixture {
+            fn database(&self) -> color_eyre::Result<crate::#cycler_module_name_identifier::Database> {
+                use color_eyre::eyre::WrapErr;
+                let mut database = crate::#cycler_module_name_identifier::Database::default();
+                apply(&mut database.main_outputs, &self.main_outputs)
+                    .wrap_err("failed to apply main outputs")?;
+                Ok(database)
+            }
+
+            #(#reader_database_getters)*
+        }
+
+        #real_time_definitions
+    }
+}
+
+fn get_harness(cycler: &Cycler, node: &Node) -> Result<TokenStream> {
+    let cycler_module_name_identifier = cycler.get_cycler_module_name_identifier();
+    let harness_identifier = node.get_identifier();
+    let path_segments = node.get_path_segments();
+    let requires_hardware_interface = node
+        .node
+        .contexts
+        .creation_context
+        .iter()
+        .chain(node.node.contexts.cycle_context.iter())
+        .any(|field| matches!(field, Field::HardwareInterface { .. }));
+    let (
+        generics,
+        where_clause,
+        hardware_interface_field,
+        hardware_interface_parameter,
+        hardware_interface_initializer,
+    ) = match requires_hardware_interface {
+        true => (
+            quote! { <Interface> },
+            quote! { where Interface: types::hardware::Interface },
+            quote! { hardware_interface: std::sync::Arc<Interface>, },
+            quote! { hardware_interface: std::sync::Arc<Interface>, },
+            quote! { hardware_interface, },
+        ),
+        false => Default::default(),
+    };
+    let creation = node
+        .get_creation(
+            quote! { instance },
+            quote! { hardware_interface },
+            quote! { persistent_state },
+        )
+        .wrap_err("failed to generate creation")?;
+    let field_initializers =
+        get_field_initializers(node).wrap_err("failed to generate field initializers")?;
+    let main_output_setters = node.get_main_output_setters_from_cycle_result();
+    let other_cycler_databases =
+        cycler
+            .get_other_cyclers()
+            .into_iter()
+            .filter_map(|other_cycler| match other_cycler {
+                OtherCycler::Reader {
+                    cycler_instance_name,
+                    ..
+                } => {
+                    let database_identifier =
+                        format_ident!("{}_database", cycler_instance_name.to_case(Case::Snake));
+                    Some(quote! {
+                        let #database_identifier = fixture.#database_identifier()?;
+                    })
+                }
+                OtherCycler::Consumer { .. } => None,
+            });
+    let real_time_databases = match cycler {
+        Cycler::Perception { .. } | Cycler::Periodic { .. } => Default::default(),
+        Cycler::RealTime { .. } => quote! {
+            let now = fixture.now.unwrap_or(std::time::UNIX_EPOCH);
+            let historic_databases = fixture.historic_databases()?;
+            let persistent_databases = fixture.perception_databases(false)?;
+            let temporary_databases = fixture.perception_databases(true)?;
+        },
+    };
+    let error_message = format!("failed to execute cycle of node `{}`", node.node_name);
+
+    Ok(quote! {
+        pub struct #harness_identifier #generics {
+            instance: #cycler_module_name_identifier::CyclerInstance,
+            #hardware_interface_field
+            persistent_state: structs::#cycler_module_name_identifier::PersistentState,
+            node: #cycler_module_name_identifier::#(#path_segments::)*#harness_identifier,
+        }
+
+        impl #generics #harness_identifier #generics #where_clause {
+            pub fn new(
+                instance: #cycler_module_name_identifier::CyclerInstance,
+                #hardware_interface_parameter
+                configuration: &structs::Configuration,
+            ) -> color_eyre::Result<Self> {
+                use color_eyre::eyre::WrapErr;
+                let mut persistent_state = structs::#cycler_module_name_identifier::PersistentState::default();
+                let node = #creation;
+                Ok(Self {
+                    instance,
+                    #hardware_interface_initializer
+                    persistent_state,
+                    node,
+                })
+            }
+
+            pub fn cycle(
+                &mut self,
+                configuration: &structs::Configuration,
+                fixture: &Fixture,
+            ) -> color_eyre::Result<crate::#cycler_module_name_identifier::Database> {
+                use color_eyre::eyre::WrapErr;
+                apply(&mut self.persistent_state, &fixture.persistent_state)
+                    .wrap_err("failed to apply persistent state")?;
+                let mut database = fixture.database()?;
+                #(#other_cycler_databases)*
+                #real_time_databases
+                {
+                    let own_database_reference = &mut database;
+                    let main_outputs = self.node.cycle(
+                        #cycler_module_name_identifier::#(#path_segments::)*CycleContext {
+                            #(#field_initializers,)*
+                        },
+                    )
+                    .wrap_err(#error_message)?;
+                    #(#main_output_setters)*
+                }
+                Ok(database)
+            }
+        }
+    })
+}
+
+fn get_field_initializers(node: &Node) -> Result<Vec<TokenStream>> {
+    let cycler_module_name_identifier = format_ident!("{}", node.node.cycler_module);
+    let accessor = |prefix: TokenStream, path: &[PathSegment], reference_type: ReferenceType| {
+        path_to_accessor_token_stream(
+            prefix,
+            path,
+            reference_type,
+            quote! { self.instance },
+            quote! { #cycler_module_name_identifier::CyclerInstance:: },
+            &node.cycler_instances.modules_to_instances[&node.node.cycler_module],
+        )
+    };
+    let database_prefix = |cycler_instance: &Option<String>| match cycler_instance {
+        Some(cycler_instance) => {
+            let identifier = format_ident!("{}_database", cycler_instance.to_case(Case::Snake));
+            quote! { #identifier.main_outputs }
+        }
+        None => quote! { own_database_reference.main_outputs },
+    };
+    node.node
+        .contexts
+        .cycle_context
+        .iter()
+        .map(|field| match field {
+            Field::AdditionalOutput { name, path, .. } => {
+                let accessor = accessor(
+                    quote! { own_database_reference.additional_outputs },
+                    path,
+                    ReferenceType::Mutable,
+                );
+                Ok(quote! {
+                    #name: framework::AdditionalOutput::new(true, #accessor)
+                })
+            }
+            Field::CyclerInstance { name } => Ok(quote! {
+                #name: self.instance
+            }),
+            Field::HardwareInterface { name } => Ok(quote! {
+                #name: &self.hardware_interface
+            }),
+            Field::HistoricInput { name, path, .. } => {
+                let now_accessor = accessor(
+                    quote! { own_database_reference.main_outputs },
+                    path,
+                    ReferenceType::Immutable,
+                );
+                let historic_accessor =
+                    accessor(quote! { database }, path, ReferenceType::Immutable);
+                Ok(quote! {
+                    #name: [(now, #now_accessor)]
+                        .into_iter()
+                        .chain(
+                            historic_databases
+                                .iter()
+                                .map(|(system_time, database)| (
+                                    *system_time,
+                                    #historic_accessor,
+                                ))
+                        )
+                        .collect::<std::collections::BTreeMap<_, _>>()
+                        .into()
+                })
+            }
+            Field::Input {
+                cycler_instance,
+                name,
+                path,
+                ..
+            } => {
+                let accessor = accessor(
+                    database_prefix(cycler_instance),
+                    path,
+                    ReferenceType::Immutable,
+                );
+                Ok(quote! {
+                    #name: #accessor
+                })
+            }
+            Field::MainOutput { name, .. } => {
+                bail!("unexpected main output field `{name}` in cycle context")
+            }
+            Field::Parameter { name, path, .. } => {
+                let accessor = accessor(quote! { configuration }, path, ReferenceType::Immutable);
+                Ok(quote! {
+                    #name: #accessor
+                })
+            }
+            Field::PerceptionInput {
+                cycler_instance,
+                name,
+                path,
+                ..
+            } => {
+                let cycler_instance_identifier =
+                    format_ident!("{}", cycler_instance.to_case(Case::Snake));
+                let accessor = accessor(quote! { database }, path, ReferenceType::Immutable);
+                Ok(quote! {
+                    #name: framework::PerceptionInput {
+                        persistent: persistent_databases
+                            .iter()
+                            .map(|(system_time, databases)| (
+                                *system_time,
+                                databases
+                                    .#cycler_instance_identifier
+                                    .iter()
+                                    .map(|database| #accessor)
+                                    .collect(),
+                            ))
+                            .collect(),
+                        temporary: temporary_databases
+                            .iter()
+                            .map(|(system_time, databases)| (
+                                *system_time,
+                                databases
+                                    .#cycler_instance_identifier
+                                    .iter()
+                                    .map(|database| #accessor)
+                                    .collect(),
+                            ))
+                            .collect(),
+                    }
+                })
+            }
+            Field::PersistentState { name, path, .. } => {
+                let accessor = accessor(
+                    quote! { self.persistent_state },
+                    path,
+                    ReferenceType::Mutable,
+                );
+                Ok(quote! {
+                    #name: #accessor
+                })
+            }
+            Field::RequiredInput {
+                cycler_instance,
+                name,
+                path,
+                ..
+            } => {
+                let accessor = accessor(
+                    database_prefix(cycler_instance),
+                    path,
+                    ReferenceType::Immutable,
+                );
+                let error_message = format!("required input `{name}` is missing in fixture");
+                Ok(quote! {
+                    #name: #accessor
+                        .ok_or_else(|| color_eyre::eyre::eyre!(#error_message))?
+                })
+            }
+        })
+        .collect()
+}
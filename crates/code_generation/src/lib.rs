@@ -1,5 +1,6 @@
 pub mod accessor;
 pub mod cycler;
+pub mod harness;
 pub mod node;
 pub mod other_cycler;
 pub mod path;
@@ -3,9 +3,9 @@ use color_eyre::{
     Result,
 };
 use convert_case::{Case, Casing};
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
-use source_analyzer::{CyclerInstances, Field};
+use source_analyzer::{CyclerInstances, Field, PathSegment};
 
 use crate::path::path_to_path_string_token_stream;
 
@@ -46,7 +46,38 @@ impl Node<'_> {
         }
     }
 
-    pub fn get_initializer_field_initializers(&self) -> Result<Vec<TokenStream>> {
+    pub fn get_recreations_identifier(&self) -> Ident {
+        format_ident!("{}_recreations", self.node_name.to_case(Case::Snake))
+    }
+
+    pub fn get_recreations_field(&self) -> TokenStream {
+        let recreations_identifier = self.get_recreations_identifier();
+        quote! {
+            #recreations_identifier: usize
+        }
+    }
+
+    pub fn get_settings_accessor(&self, instance: TokenStream) -> TokenStream {
+        let cycler_module_name_identifier = format_ident!("{}", self.node.cycler_module);
+        let node_name_snake_case = self.node_name.to_case(Case::Snake);
+        let path =
+            ["nodes", "$cycler_instance", node_name_snake_case.as_str()].map(PathSegment::from);
+        path_to_accessor_token_stream(
+            quote! { configuration },
+            &path,
+            ReferenceType::Immutable,
+            instance,
+            quote! { #cycler_module_name_identifier::CyclerInstance:: },
+            &self.cycler_instances.modules_to_instances[&self.node.cycler_module],
+        )
+    }
+
+    pub fn get_initializer_field_initializers(
+        &self,
+        instance: &TokenStream,
+        hardware_interface: &TokenStream,
+        persistent_state: &TokenStream,
+    ) -> Result<Vec<TokenStream>> {
         let cycler_module_name_identifier = format_ident!("{}", self.node.cycler_module);
         self.node
             .contexts
@@ -57,10 +88,10 @@ impl Node<'_> {
                     bail!("unexpected additional output field `{name}` in new context")
                 }
                 Field::CyclerInstance { name } => Ok(quote! {
-                    #name: instance
+                    #name: #instance
                 }),
                 Field::HardwareInterface { name } => Ok(quote! {
-                    #name: &hardware_interface
+                    #name: &#hardware_interface
                 }),
                 Field::HistoricInput { name, .. } => {
                     bail!("unexpected historic input field `{name}` in new context")
@@ -76,7 +107,7 @@ impl Node<'_> {
                         quote! { configuration },
                         path,
                         ReferenceType::Immutable,
-                        quote! { instance },
+                        instance.clone(),
                         quote! { #cycler_module_name_identifier::CyclerInstance:: },
                         &self.cycler_instances.modules_to_instances[&self.node.cycler_module],
                     );
@@ -89,10 +120,10 @@ impl Node<'_> {
                 }
                 Field::PersistentState { name, path, .. } => {
                     let accessor = path_to_accessor_token_stream(
-                        quote! { persistent_state },
+                        persistent_state.clone(),
                         path,
                         ReferenceType::Mutable,
-                        quote! { instance },
+                        instance.clone(),
                         quote! { #cycler_module_name_identifier::CyclerInstance:: },
                         &self.cycler_instances.modules_to_instances[&self.node.cycler_module],
                     );
@@ -107,23 +138,62 @@ impl Node<'_> {
             .collect()
     }
 
-    pub fn get_initializer(&self) -> Result<TokenStream> {
-        let node_name_identifier_snake_case = self.get_identifier_snake_case();
+    pub fn get_creation(
+        &self,
+        instance: TokenStream,
+        hardware_interface: TokenStream,
+        persistent_state: TokenStream,
+    ) -> Result<TokenStream> {
         let node_name_identifier = self.get_identifier();
         let path_segments = self.get_path_segments();
         let cycler_module_name_identifier = format_ident!("{}", self.node.cycler_module);
         let field_initializers = self
-            .get_initializer_field_initializers()
+            .get_initializer_field_initializers(&instance, &hardware_interface, &persistent_state)
             .wrap_err("failed to generate field initializers")?;
         let error_message = format!("failed to create node `{}`", self.node_name);
 
         Ok(quote! {
-            let #node_name_identifier_snake_case = #cycler_module_name_identifier::#(#path_segments::)*#node_name_identifier::new(
+            #cycler_module_name_identifier::#(#path_segments::)*#node_name_identifier::new(
                 #cycler_module_name_identifier::#(#path_segments::)*CreationContext {
                     #(#field_initializers,)*
                 },
             )
-            .wrap_err(#error_message)?;
+            .wrap_err(#error_message)?
+        })
+    }
+
+    pub fn get_initializer(&self) -> Result<TokenStream> {
+        let node_name_identifier_snake_case = self.get_identifier_snake_case();
+        let recreations_identifier = self.get_recreations_identifier();
+        let creation = self.get_creation(
+            quote! { instance },
+            quote! { hardware_interface },
+            quote! { persistent_state },
+        )?;
+        let settings_accessor = self.get_settings_accessor(quote! { instance });
+
+        Ok(quote! {
+            let #node_name_identifier_snake_case = #creation;
+            let #recreations_identifier = (#settings_accessor).recreations;
+        })
+    }
+
+    pub fn get_recreation(&self) -> Result<TokenStream> {
+        let node_name_identifier_snake_case = self.get_identifier_snake_case();
+        let recreations_identifier = self.get_recreations_identifier();
+        let creation = self.get_creation(
+            quote! { self.instance },
+            quote! { self.hardware_interface },
+            quote! { self.persistent_state },
+        )?;
+        let message = format!("re-creating node `{}`", self.node_name);
+
+        Ok(quote! {
+            if node_settings.recreations != self.#recreations_identifier {
+                log::info!(#message);
+                self.#node_name_identifier_snake_case = #creation;
+                self.#recreations_identifier = node_settings.recreations;
+            }
         })
     }
 
@@ -200,6 +270,7 @@ impl Node<'_> {
                         #name: framework::AdditionalOutput::new(
                             own_subscribed_outputs
                                 .iter()
+                                .chain(recorded_additional_outputs.iter())
                                 .any(|subscribed_output| framework::should_be_filled(subscribed_output, #path_string)),
                             #accessor,
                         )
@@ -409,6 +480,20 @@ impl Node<'_> {
             .collect()
     }
 
+    pub fn get_main_output_setters_from_last_database(&self) -> Vec<TokenStream> {
+        self.node
+            .contexts
+            .main_outputs
+            .iter()
+            .filter_map(|field| match field {
+                Field::MainOutput { name, .. } => Some(quote! {
+                    own_database_reference.main_outputs.#name = last_database.main_outputs.#name.clone();
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn get_execution(&self) -> Result<TokenStream> {
         let node_name_identifier_snake_case = self.get_identifier_snake_case();
         let path_segments = self.get_path_segments();
@@ -420,34 +505,96 @@ impl Node<'_> {
         let main_output_setters_from_cycle_result =
             self.get_main_output_setters_from_cycle_result();
         let main_output_setters_from_default = self.get_main_output_setters_from_default();
+        let main_output_setters_from_last_database =
+            self.get_main_output_setters_from_last_database();
+        let settings_accessor = self.get_settings_accessor(quote! { self.instance });
+        let recreation = self
+            .get_recreation()
+            .wrap_err("failed to generate recreation")?;
         let error_message = format!("failed to execute cycle of node `{}`", self.node_name);
         let node_name = self.node_name;
-        let node_execution = quote! {
-            let main_outputs = {
-                let _task = ittapi::Task::begin(&itt_domain, #node_name);
-                self.#node_name_identifier_snake_case.cycle(
-                    #cycler_module_name_identifier::#(#path_segments::)*CycleContext {
-                        #(#field_initializers,)*
-                    },
-                )
-                .wrap_err(#error_message)?
-            };
-            #(#main_output_setters_from_cycle_result)*
+        let node_name_snake_case = self.node_name.to_case(Case::Snake);
+        let context = quote! {
+            #cycler_module_name_identifier::#(#path_segments::)*CycleContext {
+                #(#field_initializers,)*
+            }
+        };
+        let cycle = |context: TokenStream| {
+            quote! {
+                let main_outputs = {
+                    let _task = ittapi::Task::begin(&itt_domain, #node_name);
+                    let node_start = std::time::Instant::now();
+                    let main_outputs = self.#node_name_identifier_snake_case.cycle(#context)
+                        .wrap_err(#error_message)?;
+                    self.cycle_timings.record_node(#node_name_snake_case, node_start.elapsed());
+                    main_outputs
+                };
+                #(#main_output_setters_from_cycle_result)*
+            }
+        };
+        let keep_last_main_outputs = quote! {
+            let last_database = self.own_reader.next();
+            #(#main_output_setters_from_last_database)*
+        };
+        let schedule = &self.node.contexts.schedule;
+
+        let node_execution = match &schedule.run_if {
+            Some(run_if) => {
+                let cycle = cycle(quote! { context });
+                quote! {
+                    let context = #context;
+                    if #cycler_module_name_identifier::#(#path_segments::)*#run_if(&context) {
+                        #cycle
+                    } else {
+                        drop(context);
+                        #keep_last_main_outputs
+                    }
+                }
+            }
+            None => cycle(context),
         };
 
-        match required_inputs_are_some {
-            Some(required_inputs_are_some) => Ok(quote! {
+        let enabled_node_execution = match required_inputs_are_some {
+            Some(required_inputs_are_some) => quote! {
                 if #required_inputs_are_some {
                     #node_execution
                 } else {
                     #(#main_output_setters_from_default)*
                 }
-            }),
-            None => Ok(quote! {
+            },
+            None => quote! {
                 {
                     #node_execution
                 }
-            }),
-        }
+            },
+        };
+
+        let scheduled_node_execution = match schedule.every {
+            Some(every) => {
+                let every = Literal::usize_unsuffixed(every);
+                quote! {
+                    if self.cycle_count % #every == 0 {
+                        #enabled_node_execution
+                    } else {
+                        #keep_last_main_outputs
+                    }
+                }
+            }
+            None => enabled_node_execution,
+        };
+
+        Ok(quote! {
+            {
+                let node_settings = *(#settings_accessor);
+                #recreation
+                if node_settings.enabled {
+                    #scheduled_node_execution
+                } else if node_settings.outputs_when_disabled == types::DisabledNodeOutputs::Default {
+                    #(#main_output_setters_from_default)*
+                } else {
+                    #keep_last_main_outputs
+                }
+            }
+        })
     }
 }
@@ -27,7 +27,7 @@ pub fn generate_run(cyclers: &[Cycler]) -> TokenStream {
                             let own_producer_identifier = format_ident!("{}_producer", cycler_instance_snake_case);
                             quote! { #own_producer_identifier, }
                         },
-                        Cycler::RealTime { .. } => Default::default(),
+                        Cycler::RealTime { .. } | Cycler::Periodic { .. } => Default::default(),
                     };
                     let other_cycler_identifiers: Vec<_> = cycler
                         .get_other_cyclers()
@@ -61,11 +61,13 @@ pub fn generate_run(cyclers: &[Cycler]) -> TokenStream {
                             ::#cycler_module_name_identifier::CyclerInstance::#cycler_instance_identifier,
                             hardware_interface.clone(),
                             #own_writer_identifier,
+                            #own_reader_identifier.clone(),
                             #own_producer_identifier
                             #(#other_cycler_identifiers,)*
                             #cycler_database_changed_identifier.clone(),
                             #own_subscribed_outputs_reader_identifier,
                             communication_server.get_parameters_reader(),
+                            recording_sender.clone(),
                         )
                         .wrap_err(#error_message)?;
                         communication_server.register_cycler_instance(
@@ -81,11 +83,11 @@ pub fn generate_run(cyclers: &[Cycler]) -> TokenStream {
         .collect();
     let amount_of_parameters_slots = 2 + cycler_initializations.len() /* 2 communication writer slots + n reader slots for other cyclers */;
     let default_slot_initializers_for_all_cyclers: Vec<_> = repeat(quote! { Default::default() })
-        .take(2 + cycler_initializations.len() /* 2 writer slots + n-1 reader slots for other cyclers + 1 reader slot for communication */)
+        .take(3 + cycler_initializations.len() /* 2 writer slots + n-1 reader slots for other cyclers + 1 reader slot for communication + 1 reader slot for the cycler itself */)
         .collect();
     let default_slot_initializers_for_communication: Vec<_> = repeat(quote! { Default::default() })
         .take(
-            2 + 1, /* 2 writer slots + 1 reader slot for communication */
+            2 + 1 + 1, /* 2 writer slots + 1 reader slot for communication + 1 reader slot for the cycler itself */
         )
         .collect();
     let multiple_buffer_initializers: Vec<_> = cyclers
@@ -98,7 +100,7 @@ pub fn generate_run(cyclers: &[Cycler]) -> TokenStream {
                     let writer_identifier = format_ident!("{}_writer", cycler_instance_snake_case);
                     let reader_identifier = format_ident!("{}_reader", cycler_instance_snake_case);
                     let slot_initializers = match cycler {
-                        Cycler::Perception { .. } => &default_slot_initializers_for_communication,
+                        Cycler::Perception { .. } | Cycler::Periodic { .. } => &default_slot_initializers_for_communication,
                         Cycler::RealTime { .. } => &default_slot_initializers_for_all_cyclers,
                     };
                     quote! {
@@ -179,7 +181,135 @@ pub fn generate_run(cyclers: &[Cycler]) -> TokenStream {
                 .collect::<Vec<_>>()
         })
         .collect();
+    let cycler_instance_identifiers: Vec<_> = cyclers
+        .iter()
+        .flat_map(|cycler| {
+            cycler.get_cycler_instances().modules_to_instances[cycler.get_cycler_module_name()]
+                .iter()
+                .map(|cycler_instance| format_ident!("{}", cycler_instance))
+        })
+        .collect();
+    let cycler_variable_identifiers: Vec<_> = cyclers
+        .iter()
+        .flat_map(|cycler| {
+            cycler.get_cycler_instances().modules_to_instances[cycler.get_cycler_module_name()]
+                .iter()
+                .map(|cycler_instance| {
+                    format_ident!("{}_cycler", cycler_instance.to_case(Case::Snake))
+                })
+        })
+        .collect();
+    let cycler_types: Vec<_> = cyclers
+        .iter()
+        .flat_map(|cycler| {
+            let cycler_module_name_identifier = cycler.get_cycler_module_name_identifier();
+            cycler.get_cycler_instances().modules_to_instances[cycler.get_cycler_module_name()]
+                .iter()
+                .map(move |_| quote! { #cycler_module_name_identifier::Cycler<Interface> })
+        })
+        .collect();
+    let lockstep_cycle_arms: Vec<_> = cycler_instance_identifiers
+        .iter()
+        .zip(cycler_variable_identifiers.iter())
+        .map(|(cycler_instance_identifier, cycler_variable_identifier)| {
+            let error_message =
+                format!("failed to execute cycle of cycler `{cycler_instance_identifier}`");
+            quote! {
+                CyclerInstance::#cycler_instance_identifier => self
+                    .#cycler_variable_identifier
+                    .cycle()
+                    .wrap_err(#error_message),
+            }
+        })
+        .collect();
     quote! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum CyclerInstance {
+            #(#cycler_instance_identifiers,)*
+        }
+
+        pub struct Lockstep<Interface> {
+            communication_server: communication::server::Runtime<structs::Configuration>,
+            #(#cycler_variable_identifiers: #cycler_types,)*
+        }
+
+        #[allow(unused_imports, unused_variables)]
+        impl<Interface> Lockstep<Interface>
+        where
+            Interface: types::hardware::Interface + std::marker::Send + std::marker::Sync + 'static,
+        {
+            pub fn new(
+                hardware_interface: std::sync::Arc<Interface>,
+                addresses: Option<impl tokio::net::ToSocketAddrs + std::marker::Send + std::marker::Sync + 'static>,
+                parameters_directory: impl std::convert::AsRef<std::path::Path> + std::marker::Send + std::marker::Sync + 'static,
+                body_id: String,
+                head_id: String,
+                recording_sender: Option<std::sync::mpsc::SyncSender<framework::RecordingFrame>>,
+                keep_running: tokio_util::sync::CancellationToken,
+            ) -> color_eyre::Result<Self> {
+                use color_eyre::eyre::WrapErr;
+
+                #(#multiple_buffer_initializers)*
+                #(#future_queue_initializers)*
+
+                let communication_server = communication::server::Runtime::start(
+                    addresses, parameters_directory, body_id, head_id, #amount_of_parameters_slots, keep_running.clone())
+                    .wrap_err("failed to start communication server")?;
+
+                #(#cycler_initializations)*
+
+                Ok(Self {
+                    communication_server,
+                    #(#cycler_variable_identifiers,)*
+                })
+            }
+
+            pub fn cycle(&mut self, cycler_instance: CyclerInstance) -> color_eyre::Result<()> {
+                use color_eyre::eyre::WrapErr;
+
+                match cycler_instance {
+                    #(#lockstep_cycle_arms)*
+                }
+            }
+
+            /// `before_cycle` is called with each scheduled cycler instance right before it cycles
+            pub fn run(
+                mut self,
+                mut schedule: framework::LockstepSchedule<CyclerInstance>,
+                keep_running: tokio_util::sync::CancellationToken,
+                mut before_cycle: impl FnMut(CyclerInstance),
+            ) -> color_eyre::Result<()> {
+                let mut result = Ok(());
+                'rounds: while !keep_running.is_cancelled() {
+                    for cycler_instance in schedule.next_round() {
+                        before_cycle(cycler_instance);
+                        if let Err(error) = self.cycle(cycler_instance) {
+                            // hardware interfaces fail pending calls once termination is requested
+                            if keep_running.is_cancelled() {
+                                break 'rounds;
+                            }
+                            keep_running.cancel();
+                            result = Err(error);
+                            break 'rounds;
+                        }
+                    }
+                }
+                keep_running.cancel();
+                match self.communication_server.join() {
+                    Ok(Err(error)) => {
+                        println!("{error:?}");
+                        color_eyre::eyre::bail!("communication server exited with error");
+                    },
+                    Err(error) => {
+                        println!("{error:?}");
+                        color_eyre::eyre::bail!("communication server exited with error");
+                    },
+                    _ => {},
+                }
+                result
+            }
+        }
+
         #[allow(unused_imports, unused_variables)]
         pub fn run<Interface>(
             hardware_interface: std::sync::Arc<Interface>,
@@ -187,6 +317,7 @@ pub fn generate_run(cyclers: &[Cycler]) -> TokenStream {
             parameters_directory: impl std::convert::AsRef<std::path::Path> + std::marker::Send + std::marker::Sync + 'static,
             body_id: String,
             head_id: String,
+            recording_sender: Option<std::sync::mpsc::SyncSender<framework::RecordingFrame>>,
             keep_running: tokio_util::sync::CancellationToken,
         ) -> color_eyre::Result<()>
         where
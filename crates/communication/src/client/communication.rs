@@ -1,6 +1,7 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use serde_json::Value;
+use serialize_hierarchy::{Constraints, TypeDescription};
 use tokio::{
     spawn,
     sync::{
@@ -16,7 +17,7 @@ use crate::{
         parameter_subscription_manager::{self, parameter_subscription_manager},
         SubscriberMessage,
     },
-    messages::{Fields, Format, Path},
+    messages::{Fields, Format, Layer, ParameterChange, Path, Rate, Reason, Types},
 };
 
 use super::{
@@ -29,6 +30,7 @@ use super::{
 #[derive(Clone)]
 pub struct Communication {
     connector: mpsc::Sender<connector::Message>,
+    token: mpsc::Sender<Option<String>>,
     output_subscription_manager: mpsc::Sender<output_subscription_manager::Message>,
     parameter_subscription_manager: mpsc::Sender<parameter_subscription_manager::Message>,
 }
@@ -36,6 +38,7 @@ pub struct Communication {
 impl Communication {
     pub fn new(address: Option<String>, connect: bool) -> Self {
         let (connector_sender, connector_receiver) = mpsc::channel(10);
+        let (token_sender, token_receiver) = mpsc::channel(10);
         let (output_subscription_manager_sender, output_subscription_manager_receiver) =
             mpsc::channel(10);
         let (parameter_subscription_manager_sender, parameter_subscription_manager_receiver) =
@@ -45,10 +48,12 @@ impl Communication {
 
         spawn(connector(
             connector_receiver,
+            token_receiver,
             connector_sender.clone(),
             output_subscription_manager_sender.clone(),
             parameter_subscription_manager_sender.clone(),
             responder_sender.clone(),
+            id_tracker_sender.clone(),
             address,
             connect,
         ));
@@ -69,6 +74,7 @@ impl Communication {
 
         Self {
             connector: connector_sender,
+            token: token_sender,
             output_subscription_manager: output_subscription_manager_sender,
             parameter_subscription_manager: parameter_subscription_manager_sender,
         }
@@ -88,6 +94,11 @@ impl Communication {
             .unwrap();
     }
 
+    /// Sets the token to authenticate with, it is sent on every (re-)connect
+    pub async fn set_token(&self, token: Option<String>) {
+        self.token.send(token).await.unwrap();
+    }
+
     pub async fn subscribe_connection_updates(&self) -> Receiver<ConnectionStatus> {
         let (subscriber_sender, subscriber_receiver) = mpsc::channel(10);
         self.connector
@@ -101,6 +112,7 @@ impl Communication {
         &self,
         output: CyclerOutput,
         format: Format,
+        rate: Rate,
     ) -> (Uuid, Receiver<SubscriberMessage>) {
         let (subscriber_sender, subscriber_receiver) = mpsc::channel(10);
         let (response_sender, response_receiver) = oneshot::channel();
@@ -108,6 +120,7 @@ impl Communication {
             .send(output_subscription_manager::Message::Subscribe {
                 output,
                 format,
+                rate,
                 subscriber: subscriber_sender,
                 response_sender,
             })
@@ -155,6 +168,15 @@ impl Communication {
         response_receiver.await.unwrap()
     }
 
+    pub async fn get_output_types(&self) -> Option<Types> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        self.output_subscription_manager
+            .send(output_subscription_manager::Message::GetOutputTypes { response_sender })
+            .await
+            .unwrap();
+        response_receiver.await.unwrap()
+    }
+
     pub async fn get_parameter_fields(&self) -> Option<BTreeSet<Path>> {
         let (response_sender, response_receiver) = oneshot::channel();
         self.parameter_subscription_manager
@@ -164,6 +186,24 @@ impl Communication {
         response_receiver.await.unwrap()
     }
 
+    pub async fn get_parameter_constraints(&self) -> Option<BTreeMap<Path, Constraints>> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        self.parameter_subscription_manager
+            .send(parameter_subscription_manager::Message::GetConstraints { response_sender })
+            .await
+            .unwrap();
+        response_receiver.await.unwrap()
+    }
+
+    pub async fn get_parameter_types(&self) -> Option<BTreeMap<Path, TypeDescription>> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        self.parameter_subscription_manager
+            .send(parameter_subscription_manager::Message::GetTypes { response_sender })
+            .await
+            .unwrap();
+        response_receiver.await.unwrap()
+    }
+
     pub async fn update_parameter_value(&self, path: &str, value: Value) {
         self.parameter_subscription_manager
             .send(
@@ -175,4 +215,43 @@ impl Communication {
             .await
             .expect("Failed to update Parameter from communication");
     }
+
+    /// Returns the recent parameter changes of the connected server, oldest first
+    pub async fn get_parameter_history(&self) -> Option<Vec<ParameterChange>> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        self.parameter_subscription_manager
+            .send(parameter_subscription_manager::Message::GetHistory { response_sender })
+            .await
+            .unwrap();
+        response_receiver.await.unwrap()
+    }
+
+    /// Stores the modified values of the given paths into the file of the layer, returns the diff
+    /// that was (or would have been in a dry run) written
+    pub async fn store_parameters_to_layer(
+        &self,
+        paths: Vec<Path>,
+        layer: Layer,
+        dry_run: bool,
+    ) -> Result<Value, Reason> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        self.parameter_subscription_manager
+            .send(parameter_subscription_manager::Message::StoreToLayer {
+                paths,
+                layer,
+                dry_run,
+                response_sender,
+            })
+            .await
+            .unwrap();
+        response_receiver.await.unwrap()
+    }
+
+    /// Undoes the given parameter change and all changes after it
+    pub async fn revert_parameters_to(&self, change_id: usize) {
+        self.parameter_subscription_manager
+            .send(parameter_subscription_manager::Message::RevertTo { change_id })
+            .await
+            .unwrap();
+    }
 }
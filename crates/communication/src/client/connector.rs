@@ -5,16 +5,22 @@ use futures_util::StreamExt;
 use log::{error, info, warn};
 use tokio::{
     net::TcpStream,
-    spawn,
+    select, spawn,
     sync::mpsc::{channel, Receiver, Sender},
     task::JoinHandle,
     time::sleep,
 };
 use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 
-use crate::client::{
-    output_subscription_manager, parameter_subscription_manager,
-    receiver::receiver as receiver_task, requester::requester, responder,
+use crate::{
+    client::{
+        id_tracker::{self, get_message_id},
+        output_subscription_manager, parameter_subscription_manager,
+        receiver::receiver as receiver_task,
+        requester::requester,
+        responder,
+    },
+    messages::{AuthenticationRequest, Request},
 };
 
 #[derive(Debug)]
@@ -56,12 +62,15 @@ pub enum ConnectionStatus {
     },
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn connector(
     mut receiver: Receiver<Message>,
+    mut token_receiver: Receiver<Option<String>>,
     sender: Sender<Message>,
     output_subscription_manager: Sender<output_subscription_manager::Message>,
     parameter_subscription_manager: Sender<parameter_subscription_manager::Message>,
     responder: Sender<responder::Message>,
+    id_tracker: Sender<id_tracker::Message>,
     initial_address: Option<String>,
     initial_connect: bool,
 ) {
@@ -77,8 +86,27 @@ pub async fn connector(
     };
 
     let mut subscribers = Vec::new();
+    let mut token = None;
+    let mut connected_requester = None;
 
-    while let Some(message) = receiver.recv().await {
+    loop {
+        let message = select! {
+            // tokens set before connecting are known once the connection is established
+            biased;
+            Some(new_token) = token_receiver.recv() => {
+                token = new_token;
+                if let (ConnectionState::Connected { .. }, Some(requester), Some(token)) =
+                    (&status, &connected_requester, &token)
+                {
+                    authenticate(token, requester, &id_tracker).await;
+                }
+                continue;
+            }
+            message = receiver.recv() => match message {
+                Some(message) => message,
+                None => break,
+            },
+        };
         status = match status {
             ConnectionState::Disconnected {
                 connect: false,
@@ -235,6 +263,11 @@ pub async fn connector(
                 Message::Connected(ws_stream) => {
                     let (writer, reader) = (*ws_stream).split();
                     let (requester_sender, requester_receiver) = channel(10);
+                    // authenticate first to have the permission for the following requests
+                    if let Some(token) = &token {
+                        authenticate(token, &requester_sender, &id_tracker).await;
+                    }
+                    connected_requester = Some(requester_sender.clone());
                     output_subscription_manager
                         .send(output_subscription_manager::Message::Connect {
                             requester: requester_sender.clone(),
@@ -285,6 +318,10 @@ pub async fn connector(
                         .send(parameter_subscription_manager::Message::Disconnect)
                         .await
                         .unwrap();
+                    responder
+                        .send(responder::Message::Disconnect)
+                        .await
+                        .unwrap();
                     ConnectionState::Disconnected {
                         connect: false,
                         address: Some(address),
@@ -303,6 +340,10 @@ pub async fn connector(
                             .send(parameter_subscription_manager::Message::Disconnect)
                             .await
                             .unwrap();
+                        responder
+                            .send(responder::Message::Disconnect)
+                            .await
+                            .unwrap();
                         let ongoing_connection = spawn_connect(new_address.clone(), sender.clone());
                         ConnectionState::Connecting {
                             address: new_address,
@@ -322,6 +363,10 @@ pub async fn connector(
                         .send(parameter_subscription_manager::Message::Disconnect)
                         .await
                         .unwrap();
+                    responder
+                        .send(responder::Message::Disconnect)
+                        .await
+                        .unwrap();
                     ConnectionState::Disconnected {
                         connect: true,
                         address: Some(address),
@@ -351,6 +396,21 @@ pub async fn connector(
     }
 }
 
+async fn authenticate(
+    token: &str,
+    requester: &Sender<Request>,
+    id_tracker: &Sender<id_tracker::Message>,
+) {
+    let id = get_message_id(id_tracker).await;
+    let request = Request::Authentication(AuthenticationRequest::Authenticate {
+        id,
+        token: token.to_string(),
+    });
+    if let Err(error) = requester.send(request).await {
+        error!("{error}");
+    }
+}
+
 fn spawn_reconnect_timer(sender: Sender<Message>) {
     spawn(async move {
         sleep(Duration::from_secs(1)).await;
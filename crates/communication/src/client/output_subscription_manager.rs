@@ -14,8 +14,9 @@ use crate::{
         responder, Output, SubscriberMessage,
     },
     messages::{
-        Fields, Format, OutputsRequest, Request,
+        Fields, Format, OutputsRequest, Rate, Request,
         TextualDataOrBinaryReference::{self, BinaryReference, TextualData},
+        Types,
     },
 };
 
@@ -30,6 +31,7 @@ pub enum Message {
     Subscribe {
         output: CyclerOutput,
         format: Format,
+        rate: Rate,
         subscriber: mpsc::Sender<SubscriberMessage>,
         response_sender: oneshot::Sender<Uuid>,
     },
@@ -44,17 +46,21 @@ pub enum Message {
     },
     UpdateFields {
         fields: Fields,
+        types: Types,
     },
     GetOutputFields {
         response_sender: oneshot::Sender<Option<Fields>>,
     },
+    GetOutputTypes {
+        response_sender: oneshot::Sender<Option<Types>>,
+    },
 }
 
 #[derive(Default)]
 struct SubscriptionManager {
-    ids_to_outputs: HashMap<usize, (CyclerOutput, Format)>,
+    ids_to_outputs: HashMap<usize, (CyclerOutput, Format, Rate)>,
     outputs_to_subscribers:
-        HashMap<(CyclerOutput, Format), HashMap<Uuid, mpsc::Sender<SubscriberMessage>>>,
+        HashMap<(CyclerOutput, Format, Rate), HashMap<Uuid, mpsc::Sender<SubscriberMessage>>>,
 }
 
 pub async fn output_subscription_manager(
@@ -66,8 +72,10 @@ pub async fn output_subscription_manager(
     let mut manager = SubscriptionManager::default();
     let mut requester = None;
     let mut fields = None;
+    let mut types = None;
     let mut binary_data_waiting_for_references: HashMap<usize, Vec<u8>> = HashMap::new();
-    let mut binary_references_waiting_for_data: HashMap<usize, CyclerOutput> = HashMap::new();
+    let mut binary_references_waiting_for_data: HashMap<usize, (CyclerOutput, Format, Rate)> =
+        HashMap::new();
 
     while let Some(message) = receiver.recv().await {
         match message {
@@ -75,11 +83,12 @@ pub async fn output_subscription_manager(
                 requester: new_requester,
             } => {
                 assert!(manager.ids_to_outputs.is_empty());
-                for ((output, format), subscribers) in &manager.outputs_to_subscribers {
+                for ((output, format, rate), subscribers) in &manager.outputs_to_subscribers {
                     let subscribers = subscribers.values().cloned().collect();
                     if let Some(subscription_id) = subscribe(
                         output.clone(),
                         *format,
+                        *rate,
                         subscribers,
                         &id_tracker,
                         &responder,
@@ -89,7 +98,7 @@ pub async fn output_subscription_manager(
                     {
                         manager
                             .ids_to_outputs
-                            .insert(subscription_id, (output.clone(), *format));
+                            .insert(subscription_id, (output.clone(), *format, *rate));
                     }
                 }
                 match query_output_fields(sender.clone(), &id_tracker, &responder, &new_requester)
@@ -108,6 +117,7 @@ pub async fn output_subscription_manager(
             Message::Subscribe {
                 output,
                 format,
+                rate,
                 subscriber: output_sender,
                 response_sender,
             } => {
@@ -119,6 +129,7 @@ pub async fn output_subscription_manager(
                             uuid,
                             output,
                             format,
+                            rate,
                             output_sender,
                             &id_tracker,
                             &responder,
@@ -195,27 +206,34 @@ pub async fn output_subscription_manager(
                                     }
                                 } else {
                                     binary_references_waiting_for_data
-                                        .insert(reference_id, output.0.clone());
+                                        .insert(reference_id, output.clone());
                                 }
                             }
                         }
                     }
                 }
             }
-            Message::UpdateFields { fields: new_fields } => {
+            Message::UpdateFields {
+                fields: new_fields,
+                types: new_types,
+            } => {
                 fields = Some(new_fields);
+                types = Some(new_types);
             }
             Message::GetOutputFields { response_sender } => {
                 if let Err(error) = response_sender.send(fields.clone()) {
                     error!("{error:?}");
                 }
             }
+            Message::GetOutputTypes { response_sender } => {
+                if let Err(error) = response_sender.send(types.clone()) {
+                    error!("{error:?}");
+                }
+            }
             Message::UpdateBinary { referenced_items } => {
                 for (reference_id, data) in referenced_items {
                     if let Some(output) = binary_references_waiting_for_data.get(&reference_id) {
-                        let subscribers = manager
-                            .outputs_to_subscribers
-                            .get(&(output.clone(), Format::Binary));
+                        let subscribers = manager.outputs_to_subscribers.get(output);
                         if let Some(senders) = subscribers {
                             for sender in senders.values() {
                                 if let Err(error) = sender
@@ -255,8 +273,8 @@ async fn query_output_fields(
     spawn(async move {
         let response = response_receiver.await.unwrap();
         match response {
-            Response::Fields(fields) => {
-                if let Err(error) = manager.send(Message::UpdateFields { fields }).await {
+            Response::Fields { fields, types } => {
+                if let Err(error) = manager.send(Message::UpdateFields { fields, types }).await {
                     error!("{error}");
                 };
             }
@@ -272,6 +290,7 @@ async fn add_subscription(
     uuid: Uuid,
     output: CyclerOutput,
     format: Format,
+    rate: Rate,
     output_sender: mpsc::Sender<SubscriberMessage>,
     id_tracker: &mpsc::Sender<id_tracker::Message>,
     responder: &mpsc::Sender<responder::Message>,
@@ -279,7 +298,7 @@ async fn add_subscription(
 ) {
     match manager
         .outputs_to_subscribers
-        .entry((output.clone(), format))
+        .entry((output.clone(), format, rate))
     {
         Entry::Occupied(mut entry) => {
             entry.get_mut().insert(uuid, output_sender);
@@ -289,6 +308,7 @@ async fn add_subscription(
                 if let Some(subscription_id) = subscribe(
                     output.clone(),
                     format,
+                    rate,
                     vec![output_sender.clone()],
                     id_tracker,
                     responder,
@@ -298,7 +318,7 @@ async fn add_subscription(
                 {
                     manager
                         .ids_to_outputs
-                        .insert(subscription_id, (output, format));
+                        .insert(subscription_id, (output, format, rate));
                 }
             };
             entry.insert(HashMap::new()).insert(uuid, output_sender);
@@ -309,6 +329,7 @@ async fn add_subscription(
 async fn subscribe(
     output: CyclerOutput,
     format: Format,
+    rate: Rate,
     subscribers: Vec<mpsc::Sender<SubscriberMessage>>,
     id_tracker: &mpsc::Sender<id_tracker::Message>,
     responder: &mpsc::Sender<responder::Message>,
@@ -335,6 +356,7 @@ async fn subscribe(
         cycler_instance: output.cycler.to_string(),
         path,
         format,
+        rate,
     });
     if let Err(error) = requester.send(request).await {
         error!("{error}");
@@ -348,7 +370,9 @@ async fn subscribe(
         };
         let message = match result {
             Ok(()) => SubscriberMessage::SubscriptionSuccess,
-            Err(error) => SubscriberMessage::SubscriptionFailure { info: error },
+            Err(error) => SubscriberMessage::SubscriptionFailure {
+                info: error.to_string(),
+            },
         };
         for sender in subscribers {
             if let Err(error) = sender.send(message.clone()).await {
@@ -1,8 +1,9 @@
-use std::collections::{hash_map::Entry, BTreeSet, HashMap};
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap};
 
 use color_eyre::eyre::Result;
 use log::{error, info, warn};
 use serde_json::Value;
+use serialize_hierarchy::{Constraints, TypeDescription};
 use tokio::{
     spawn,
     sync::{mpsc, oneshot},
@@ -14,7 +15,7 @@ use crate::{
         id_tracker::{self, get_message_id},
         responder, SubscriberMessage,
     },
-    messages::{ParametersRequest, Path, Request},
+    messages::{Layer, ParameterChange, ParametersRequest, Path, Reason, Request},
 };
 
 use super::responder::Response;
@@ -39,14 +40,34 @@ pub enum Message {
     },
     UpdateFields {
         fields: BTreeSet<Path>,
+        constraints: BTreeMap<Path, Constraints>,
+        types: BTreeMap<Path, TypeDescription>,
     },
     GetFields {
         response_sender: oneshot::Sender<Option<BTreeSet<Path>>>,
     },
+    GetConstraints {
+        response_sender: oneshot::Sender<Option<BTreeMap<Path, Constraints>>>,
+    },
+    GetTypes {
+        response_sender: oneshot::Sender<Option<BTreeMap<Path, TypeDescription>>>,
+    },
     UpdateParameterValue {
         path: String,
         value: Value,
     },
+    GetHistory {
+        response_sender: oneshot::Sender<Option<Vec<ParameterChange>>>,
+    },
+    RevertTo {
+        change_id: usize,
+    },
+    StoreToLayer {
+        paths: Vec<Path>,
+        layer: Layer,
+        dry_run: bool,
+        response_sender: oneshot::Sender<Result<Value, Reason>>,
+    },
 }
 
 #[derive(Default)]
@@ -64,6 +85,8 @@ pub async fn parameter_subscription_manager(
     let mut manager = SubscriptionManager::default();
     let mut requester = None;
     let mut fields = None;
+    let mut constraints = None;
+    let mut types = None;
     while let Some(message) = receiver.recv().await {
         match message {
             Message::Connect {
@@ -160,14 +183,30 @@ pub async fn parameter_subscription_manager(
                     }
                 }
             }
-            Message::UpdateFields { fields: new_fields } => {
+            Message::UpdateFields {
+                fields: new_fields,
+                constraints: new_constraints,
+                types: new_types,
+            } => {
                 fields = Some(new_fields);
+                constraints = Some(new_constraints);
+                types = Some(new_types);
             }
             Message::GetFields { response_sender } => {
                 if let Err(error) = response_sender.send(fields.clone()) {
                     error!("{error:?}");
                 }
             }
+            Message::GetConstraints { response_sender } => {
+                if let Err(error) = response_sender.send(constraints.clone()) {
+                    error!("{error:?}");
+                }
+            }
+            Message::GetTypes { response_sender } => {
+                if let Err(error) = response_sender.send(types.clone()) {
+                    error!("{error:?}");
+                }
+            }
             Message::UpdateParameterValue { path, value } => {
                 if let Some(some_requester) = requester {
                     match update_parameter_value(
@@ -187,6 +226,47 @@ pub async fn parameter_subscription_manager(
                     }
                 }
             }
+            Message::GetHistory { response_sender } => match &requester {
+                Some(requester) => {
+                    query_history(response_sender, &id_tracker, &responder, requester).await
+                }
+                None => {
+                    if let Err(error) = response_sender.send(None) {
+                        error!("{error:?}");
+                    }
+                }
+            },
+            Message::RevertTo { change_id } => {
+                if let Some(requester) = &requester {
+                    revert_to(change_id, &id_tracker, &responder, requester).await;
+                }
+            }
+            Message::StoreToLayer {
+                paths,
+                layer,
+                dry_run,
+                response_sender,
+            } => match &requester {
+                Some(requester) => {
+                    store_to_layer(
+                        paths,
+                        layer,
+                        dry_run,
+                        response_sender,
+                        &id_tracker,
+                        &responder,
+                        requester,
+                    )
+                    .await
+                }
+                None => {
+                    if let Err(error) =
+                        response_sender.send(Err(Reason::Failed("not connected".to_string())))
+                    {
+                        error!("{error:?}");
+                    }
+                }
+            },
         }
     }
     info!("Finished manager");
@@ -216,8 +296,16 @@ async fn query_parameter_hierarchy(
     spawn(async move {
         let response = response_receiver.await.unwrap();
         match response {
-            Response::ParameterFields(fields) => manager
-                .send(Message::UpdateFields { fields })
+            Response::ParameterFields {
+                fields,
+                constraints,
+                types,
+            } => manager
+                .send(Message::UpdateFields {
+                    fields,
+                    constraints,
+                    types,
+                })
                 .await
                 .unwrap(),
             response => error!("unexpected response: {response:?}"),
@@ -262,6 +350,115 @@ async fn update_parameter_value(
     Ok(())
 }
 
+async fn query_history(
+    response_sender: oneshot::Sender<Option<Vec<ParameterChange>>>,
+    id_tracker: &mpsc::Sender<id_tracker::Message>,
+    responder: &mpsc::Sender<responder::Message>,
+    requester: &mpsc::Sender<Request>,
+) {
+    let message_id = get_message_id(id_tracker).await;
+    let (awaited_response_sender, response_receiver) = oneshot::channel();
+    responder
+        .send(responder::Message::Await {
+            id: message_id,
+            response_sender: awaited_response_sender,
+        })
+        .await
+        .unwrap();
+    requester
+        .send(Request::Parameters(ParametersRequest::GetHistory {
+            id: message_id,
+        }))
+        .await
+        .unwrap();
+    spawn(async move {
+        let response = response_receiver.await.unwrap();
+        let history = match response {
+            Response::History(history) => Some(history),
+            response => {
+                error!("unexpected response: {response:?}");
+                None
+            }
+        };
+        if let Err(error) = response_sender.send(history) {
+            error!("{error:?}");
+        }
+    });
+}
+
+async fn revert_to(
+    change_id: usize,
+    id_tracker: &mpsc::Sender<id_tracker::Message>,
+    responder: &mpsc::Sender<responder::Message>,
+    requester: &mpsc::Sender<Request>,
+) {
+    let message_id = get_message_id(id_tracker).await;
+    let (response_sender, response_receiver) = oneshot::channel();
+    responder
+        .send(responder::Message::Await {
+            id: message_id,
+            response_sender,
+        })
+        .await
+        .unwrap();
+    requester
+        .send(Request::Parameters(ParametersRequest::RevertTo {
+            id: message_id,
+            change_id,
+        }))
+        .await
+        .unwrap();
+    spawn(async move {
+        let response = response_receiver.await.unwrap();
+        match response {
+            Response::RevertTo(Ok(_)) => {}
+            Response::RevertTo(Err(error)) => {
+                error!("Failed to revert to change {change_id}: {error}")
+            }
+            response => error!("unexpected response: {response:?}"),
+        };
+    });
+}
+
+async fn store_to_layer(
+    paths: Vec<Path>,
+    layer: Layer,
+    dry_run: bool,
+    response_sender: oneshot::Sender<Result<Value, Reason>>,
+    id_tracker: &mpsc::Sender<id_tracker::Message>,
+    responder: &mpsc::Sender<responder::Message>,
+    requester: &mpsc::Sender<Request>,
+) {
+    let message_id = get_message_id(id_tracker).await;
+    let (awaited_response_sender, response_receiver) = oneshot::channel();
+    responder
+        .send(responder::Message::Await {
+            id: message_id,
+            response_sender: awaited_response_sender,
+        })
+        .await
+        .unwrap();
+    requester
+        .send(Request::Parameters(ParametersRequest::StoreToLayer {
+            id: message_id,
+            paths,
+            layer,
+            dry_run,
+        }))
+        .await
+        .unwrap();
+    spawn(async move {
+        let response = response_receiver.await.unwrap();
+        let result = match response {
+            Response::StoreToLayer(result) => result,
+            response => Err(Reason::Failed(format!("unexpected response: {response:?}"))),
+        };
+        if let Err(error) = response_sender.send(result) {
+            error!("{error:?}");
+        }
+    });
+}
+
 async fn add_subscription(
     manager: &mut SubscriptionManager,
     uuid: Uuid,
@@ -322,9 +519,9 @@ async fn subscribe(
         let response = response_receiver.await.unwrap();
         let message = match response {
             Response::Subscribe(Ok(_)) => SubscriberMessage::SubscriptionSuccess,
-            Response::Subscribe(Err(error)) => {
-                SubscriberMessage::SubscriptionFailure { info: error }
-            }
+            Response::Subscribe(Err(error)) => SubscriberMessage::SubscriptionFailure {
+                info: error.to_string(),
+            },
             response => return error!("unexpected response: {response:?}"),
         };
         for sender in subscribers {
@@ -11,8 +11,8 @@ use crate::{
         responder::{Message, Response},
     },
     messages::{
-        BinaryOutputsResponse, BinaryResponse, ParametersResponse, TextualOutputsResponse,
-        TextualResponse,
+        AuthenticationResponse, BinaryOutputsResponse, BinaryResponse, ParametersResponse,
+        TextualOutputsResponse, TextualResponse,
     },
 };
 
@@ -38,9 +38,16 @@ pub async fn receiver(
                         }
                     };
                     match message {
+                        TextualResponse::Authentication(AuthenticationResponse::Authenticate {
+                            id: _,
+                            result,
+                        }) => match result {
+                            Ok(permission) => info!("Authenticated with permission {permission:?}"),
+                            Err(reason) => error!("Failed to authenticate: {reason}"),
+                        },
                         TextualResponse::Outputs(outputs_message) => match outputs_message {
-                            TextualOutputsResponse::GetFields { id, fields } => {
-                                respond(&responder, id, Response::Fields(fields)).await
+                            TextualOutputsResponse::GetFields { id, fields, types } => {
+                                respond(&responder, id, Response::Fields { fields, types }).await
                             }
                             TextualOutputsResponse::GetNext { id: _, result: _ } => todo!(),
                             TextualOutputsResponse::Subscribe { id, result } => {
@@ -60,8 +67,22 @@ pub async fn receiver(
                         },
                         TextualResponse::Parameters(parameters_message) => match parameters_message
                         {
-                            ParametersResponse::GetFields { id, fields } => {
-                                respond(&responder, id, Response::ParameterFields(fields)).await
+                            ParametersResponse::GetFields {
+                                id,
+                                fields,
+                                constraints,
+                                types,
+                            } => {
+                                respond(
+                                    &responder,
+                                    id,
+                                    Response::ParameterFields {
+                                        fields,
+                                        constraints,
+                                        types,
+                                    },
+                                )
+                                .await
                             }
                             ParametersResponse::Subscribe { id, result } => {
                                 respond(&responder, id, Response::Subscribe(result)).await
@@ -89,6 +110,15 @@ pub async fn receiver(
                             ParametersResponse::GetCurrent { id: _, result: _ } => todo!(),
                             ParametersResponse::LoadFromDisk { id: _, result: _ } => todo!(),
                             ParametersResponse::StoreToDisk { id: _, result: _ } => todo!(),
+                            ParametersResponse::GetHistory { id, history } => {
+                                respond(&responder, id, Response::History(history)).await
+                            }
+                            ParametersResponse::RevertTo { id, result } => {
+                                respond(&responder, id, Response::RevertTo(result)).await
+                            }
+                            ParametersResponse::StoreToLayer { id, result } => {
+                                respond(&responder, id, Response::StoreToLayer(result)).await
+                            }
                         },
                         message => todo!("unimplemented message {message:?}"),
                     }
@@ -1,9 +1,11 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use log::{debug, error};
+use serde_json::Value;
+use serialize_hierarchy::{Constraints, TypeDescription};
 use tokio::sync::{mpsc, oneshot};
 
-use crate::messages::{Fields, Path, Reason};
+use crate::messages::{Fields, ParameterChange, Path, Reason, Types};
 
 #[derive(Debug)]
 pub enum Message {
@@ -15,15 +17,28 @@ pub enum Message {
         id: usize,
         response: Response,
     },
+    /// Answers all awaited responses with `Response::Disconnected`
+    Disconnect,
 }
 
 #[derive(Debug)]
 pub enum Response {
-    Fields(Fields),
-    ParameterFields(BTreeSet<Path>),
+    Fields {
+        fields: Fields,
+        types: Types,
+    },
+    ParameterFields {
+        fields: BTreeSet<Path>,
+        constraints: BTreeMap<Path, Constraints>,
+        types: BTreeMap<Path, TypeDescription>,
+    },
     Subscribe(Result<(), Reason>),
     Unsubscribe(Result<(), Reason>),
     Update(Result<(), Reason>),
+    History(Vec<ParameterChange>),
+    RevertTo(Result<(), Reason>),
+    StoreToLayer(Result<Value, Reason>),
+    Disconnected,
 }
 
 pub async fn responder(mut receiver: mpsc::Receiver<Message>) {
@@ -45,6 +60,12 @@ pub async fn responder(mut receiver: mpsc::Receiver<Message>) {
                 }
                 None => error!("Cannot find sender waiting for a response with id '{id}'"),
             },
+            Message::Disconnect => {
+                for (_, sender) in awaiting_response.drain() {
+                    // the requesting task may have been dropped already
+                    let _ = sender.send(Response::Disconnected);
+                }
+            }
         }
     }
 }
@@ -0,0 +1,65 @@
+use std::io;
+
+use serde_json::Value;
+
+const COMPRESSION_LEVEL: i32 = 3;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to compress data")]
+    CompressionFailed(io::Error),
+    #[error("failed to decompress data")]
+    DecompressionFailed(io::Error),
+    #[error("failed to decode MessagePack data")]
+    DecodingFailed(rmp_serde::decode::Error),
+}
+
+pub fn compress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    zstd::encode_all(data, COMPRESSION_LEVEL).map_err(Error::CompressionFailed)
+}
+
+pub fn decompress_value(data: &[u8]) -> Result<Value, Error> {
+    let data = zstd::decode_all(data).map_err(Error::DecompressionFailed)?;
+    rmp_serde::from_slice(&data).map_err(Error::DecodingFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Candidate {
+        position: [f32; 2],
+        score: Option<f32>,
+    }
+
+    #[test]
+    fn compressed_message_pack_decodes_into_json_value() {
+        let candidates = vec![
+            Candidate {
+                position: [1.0, 2.0],
+                score: Some(0.5),
+            },
+            Candidate {
+                position: [3.0, 4.0],
+                score: None,
+            },
+        ];
+        let mut data = Vec::new();
+        let mut serializer = rmp_serde::Serializer::new(&mut data).with_struct_map();
+        candidates.serialize(&mut serializer).unwrap();
+
+        let value = decompress_value(&compress(&data).unwrap()).unwrap();
+
+        assert_eq!(
+            value,
+            json!([
+                {"position": [1.0, 2.0], "score": 0.5},
+                {"position": [3.0, 4.0], "score": null},
+            ])
+        );
+    }
+}
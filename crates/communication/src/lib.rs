@@ -1,6 +1,7 @@
 use serde_json::Value;
 
 pub mod client;
+pub mod compression;
 pub mod messages;
 #[cfg(feature = "server")]
 pub mod server;
@@ -1,31 +1,55 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    time::{Duration, SystemTime},
+};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use serialize_hierarchy::{Constraints, TypeDescription};
 use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 
 pub type CyclerInstance = String;
 pub type Path = String;
-pub type Reason = String;
 pub type Type = String;
 pub type Fields = BTreeMap<CyclerInstance, BTreeSet<Path>>;
+pub type Types = BTreeMap<CyclerInstance, BTreeMap<Path, TypeDescription>>;
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Request {
+    Authentication(AuthenticationRequest),
     Injections(InjectionsRequest),
     Outputs(OutputsRequest),
     Parameters(ParametersRequest),
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+impl Request {
+    pub fn required_permission(&self) -> Option<Permission> {
+        match self {
+            Request::Authentication(_) => None,
+            Request::Injections(_) => Some(Permission::Injection),
+            Request::Outputs(_) => Some(Permission::ReadOnly),
+            Request::Parameters(
+                ParametersRequest::Update { .. }
+                | ParametersRequest::LoadFromDisk { .. }
+                | ParametersRequest::StoreToDisk { .. }
+                | ParametersRequest::RevertTo { .. }
+                | ParametersRequest::StoreToLayer { dry_run: false, .. },
+            ) => Some(Permission::ParameterWrite),
+            Request::Parameters(_) => Some(Permission::ReadOnly),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum Response {
     Textual(TextualResponse),
     Binary(BinaryResponse),
-    Close { code: CloseCode, reason: Reason },
+    Close { code: CloseCode, reason: String },
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub enum TextualResponse {
+    Authentication(AuthenticationResponse),
     Injections(InjectionsResponse),
     Outputs(TextualOutputsResponse),
     Parameters(ParametersResponse),
@@ -36,6 +60,43 @@ pub enum BinaryResponse {
     Outputs(BinaryOutputsResponse),
 }
 
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, thiserror::Error)]
+pub enum Reason {
+    #[error("permission {required:?} required but only {granted:?} granted")]
+    PermissionDenied {
+        required: Permission,
+        granted: Permission,
+    },
+    #[error("invalid token")]
+    InvalidToken,
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// Permission levels of a connection, each level includes the permissions of the previous ones
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Permission {
+    /// Read outputs and parameters
+    ReadOnly,
+    /// Update, load and store parameters
+    ParameterWrite,
+    /// Set and unset injections
+    Injection,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AuthenticationRequest {
+    Authenticate { id: usize, token: String },
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AuthenticationResponse {
+    Authenticate {
+        id: usize,
+        result: Result<Permission, Reason>,
+    },
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum InjectionsRequest {
     Set {
@@ -74,12 +135,16 @@ pub enum OutputsRequest {
         cycler_instance: CyclerInstance,
         path: Path,
         format: Format,
+        #[serde(default)]
+        rate: Rate,
     },
     Subscribe {
         id: usize,
         cycler_instance: CyclerInstance,
         path: Path,
         format: Format,
+        #[serde(default)]
+        rate: Rate,
     },
     Unsubscribe {
         id: usize,
@@ -93,6 +158,7 @@ pub enum TextualOutputsResponse {
     GetFields {
         id: usize,
         fields: Fields,
+        types: Types,
     },
     GetNext {
         id: usize,
@@ -130,21 +196,55 @@ pub enum BinaryOutputsResponse {
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum ParametersRequest {
-    GetFields { id: usize },
-    GetCurrent { id: usize, path: Path },
-    Subscribe { id: usize, path: Path },
-    Unsubscribe { id: usize, subscription_id: usize },
+    GetFields {
+        id: usize,
+    },
+    GetCurrent {
+        id: usize,
+        path: Path,
+    },
+    Subscribe {
+        id: usize,
+        path: Path,
+    },
+    Unsubscribe {
+        id: usize,
+        subscription_id: usize,
+    },
     UnsubscribeEverything,
-    Update { id: usize, path: Path, data: Value },
-    LoadFromDisk { id: usize },
-    StoreToDisk { id: usize },
+    Update {
+        id: usize,
+        path: Path,
+        data: Value,
+    },
+    LoadFromDisk {
+        id: usize,
+    },
+    StoreToDisk {
+        id: usize,
+    },
+    GetHistory {
+        id: usize,
+    },
+    RevertTo {
+        id: usize,
+        change_id: usize,
+    },
+    StoreToLayer {
+        id: usize,
+        paths: Vec<Path>,
+        layer: Layer,
+        dry_run: bool,
+    },
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub enum ParametersResponse {
     GetFields {
         id: usize,
         fields: BTreeSet<Path>,
+        constraints: BTreeMap<Path, Constraints>,
+        types: BTreeMap<Path, TypeDescription>,
     },
     GetCurrent {
         id: usize,
@@ -174,10 +274,61 @@ pub enum ParametersResponse {
         id: usize,
         result: Result<(), Reason>,
     },
+    GetHistory {
+        id: usize,
+        history: Vec<ParameterChange>,
+    },
+    RevertTo {
+        id: usize,
+        result: Result<(), Reason>,
+    },
+    /// Contains the diff that was (or would have been in a dry run) merged into the layer's file
+    StoreToLayer {
+        id: usize,
+        result: Result<Value, Reason>,
+    },
+}
+
+/// Parameter files in the order they are merged when loading, later layers override earlier ones
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Layer {
+    Default,
+    LocationDefault,
+    Body,
+    Head,
+    LocationBody,
+    LocationHead,
+}
+
+/// A change of a single parameter applied by a client, oldest changes have the smallest `id`
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ParameterChange {
+    pub id: usize,
+    pub path: Path,
+    pub old_value: Value,
+    pub new_value: Value,
+    pub timestamp: SystemTime,
+    pub client_id: usize,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum Format {
     Textual,
     Binary,
+    /// zstd-compressed MessagePack, self-describing and therefore decodable without knowing the type
+    Compressed,
+}
+
+/// Limits how often the data of an output subscription is sent. Cycles and intervals are counted
+/// from the request on, i.e. a `GetNext` with a rate skips the cycles in between.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Rate {
+    #[default]
+    EveryCycle,
+    EveryNthCycle {
+        n: usize,
+    },
+    MinimumInterval {
+        interval: Duration,
+    },
 }
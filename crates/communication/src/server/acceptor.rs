@@ -1,6 +1,9 @@
 use std::{
     io,
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 use log::error;
@@ -15,6 +18,7 @@ use tokio_util::sync::CancellationToken;
 use crate::messages::ParametersRequest;
 
 use super::{
+    authentication::Authentication,
     client_request::ClientRequest,
     connection::{connection, ConnectionError},
     outputs,
@@ -33,6 +37,7 @@ pub enum AcceptError {
 pub fn acceptor(
     addresses: impl ToSocketAddrs + Send + Sync + 'static,
     keep_running: CancellationToken,
+    authentication: Arc<Authentication>,
     outputs_sender: Sender<outputs::Request>,
     parameters_sender: Sender<ClientRequest<ParametersRequest>>,
 ) -> JoinHandle<Result<(), AcceptError>> {
@@ -55,6 +60,7 @@ pub fn acceptor(
                 stream,
                 keep_running.clone(),
                 error_sender.clone(),
+                authentication.clone(),
                 outputs_sender.clone(),
                 parameters_sender.clone(),
                 client_id,
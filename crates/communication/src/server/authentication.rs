@@ -0,0 +1,125 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::Deserialize;
+use serde_json::from_str;
+use tokio::fs::read_to_string;
+
+use crate::messages::{Permission, Reason};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthenticationError {
+    #[error("failed to read {path:?}")]
+    FileNotRead { source: io::Error, path: PathBuf },
+    #[error("failed to parse {path:?}")]
+    FileNotParsed {
+        source: serde_json::Error,
+        path: PathBuf,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct Authentication {
+    /// Permission of connections that did not authenticate (yet)
+    pub unauthenticated_permission: Permission,
+    /// Pre-shared tokens and the permission they grant
+    pub tokens: HashMap<String, Permission>,
+}
+
+impl Default for Authentication {
+    fn default() -> Self {
+        Self {
+            unauthenticated_permission: Permission::Injection,
+            tokens: HashMap::new(),
+        }
+    }
+}
+
+impl Authentication {
+    /// Reads `authentication.json` from the parameters directory, everything is allowed if it does not exist
+    pub async fn from_directory(root_path: impl AsRef<Path>) -> Result<Self, AuthenticationError> {
+        let path = root_path.as_ref().join("authentication.json");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file_contents =
+            read_to_string(&path)
+                .await
+                .map_err(|source| AuthenticationError::FileNotRead {
+                    source,
+                    path: path.clone(),
+                })?;
+        from_str(&file_contents)
+            .map_err(|source| AuthenticationError::FileNotParsed { source, path })
+    }
+}
+
+pub struct Session {
+    authentication: Arc<Authentication>,
+    granted: Permission,
+}
+
+impl Session {
+    pub fn new(authentication: Arc<Authentication>) -> Self {
+        let granted = authentication.unauthenticated_permission;
+        Self {
+            authentication,
+            granted,
+        }
+    }
+
+    pub fn authenticate(&mut self, token: &str) -> Result<Permission, Reason> {
+        let permission = *self
+            .authentication
+            .tokens
+            .get(token)
+            .ok_or(Reason::InvalidToken)?;
+        self.granted = self.granted.max(permission);
+        Ok(self.granted)
+    }
+
+    pub fn check(&self, required: Permission) -> Result<(), Reason> {
+        if self.granted >= required {
+            Ok(())
+        } else {
+            Err(Reason::PermissionDenied {
+                required,
+                granted: self.granted,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_elevate_permission_of_session() {
+        let authentication = Arc::new(Authentication {
+            unauthenticated_permission: Permission::ReadOnly,
+            tokens: [("secret".to_string(), Permission::ParameterWrite)].into(),
+        });
+        let mut session = Session::new(authentication);
+
+        assert_eq!(session.check(Permission::ReadOnly), Ok(()));
+        assert_eq!(
+            session.check(Permission::ParameterWrite),
+            Err(Reason::PermissionDenied {
+                required: Permission::ParameterWrite,
+                granted: Permission::ReadOnly,
+            })
+        );
+        assert_eq!(session.authenticate("guess"), Err(Reason::InvalidToken));
+        assert_eq!(
+            session.authenticate("secret"),
+            Ok(Permission::ParameterWrite)
+        );
+        assert_eq!(session.check(Permission::ParameterWrite), Ok(()));
+        assert!(session.check(Permission::Injection).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use std::{io, net::SocketAddr};
+use std::{io, net::SocketAddr, sync::Arc};
 
 use futures_util::StreamExt;
 use log::error;
@@ -12,7 +12,13 @@ use tokio_util::sync::CancellationToken;
 
 use crate::messages::ParametersRequest;
 
-use super::{client_request::ClientRequest, outputs, receiver::receiver, sender::sender};
+use super::{
+    authentication::{Authentication, Session},
+    client_request::ClientRequest,
+    outputs,
+    receiver::receiver,
+    sender::sender,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ConnectionError {
@@ -50,6 +56,7 @@ pub fn connection(
     stream: TcpStream,
     keep_running: CancellationToken,
     connection_error_sender: UnboundedSender<ConnectionError>,
+    authentication: Arc<Authentication>,
     outputs_sender: Sender<outputs::Request>,
     parameters_sender: Sender<ClientRequest<ParametersRequest>>,
     client_id: usize,
@@ -90,6 +97,7 @@ pub fn connection(
             keep_running,
             keep_only_self_running.clone(),
             client_id,
+            Session::new(authentication),
             response_sender,
             outputs_sender,
             parameters_sender,
@@ -1,4 +1,5 @@
 mod acceptor;
+mod authentication;
 mod client;
 mod client_request;
 mod connection;
@@ -1,8 +1,12 @@
-use std::collections::BTreeSet;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Instant,
+};
 
+use serialize_hierarchy::TypeDescription;
 use tokio::sync::mpsc::Sender;
 
-use crate::messages::{Format, OutputsRequest, Path};
+use crate::messages::{Format, OutputsRequest, Path, Rate};
 
 use super::client_request::ClientRequest;
 
@@ -15,6 +19,7 @@ pub enum Request {
     RegisterCycler {
         cycler_instance: String,
         fields: BTreeSet<Path>,
+        types: BTreeMap<Path, TypeDescription>,
         request_sender: Sender<ClientRequest<OutputsRequest>>,
     },
 }
@@ -24,4 +29,75 @@ struct Subscription {
     pub path: Path,
     pub format: Format,
     pub once: bool,
+    pub rate: Rate,
+    pub cycles_since_last_sent: usize,
+    pub last_sent: Instant,
+}
+
+impl Subscription {
+    fn new(path: Path, format: Format, once: bool, rate: Rate) -> Self {
+        Self {
+            path,
+            format,
+            once,
+            rate,
+            cycles_since_last_sent: 0,
+            last_sent: Instant::now(),
+        }
+    }
+
+    /// Counts the cycle and returns whether its data should be sent
+    fn is_due(&mut self, now: Instant) -> bool {
+        self.cycles_since_last_sent += 1;
+        let is_due = match self.rate {
+            Rate::EveryCycle => true,
+            Rate::EveryNthCycle { n } => self.cycles_since_last_sent >= n,
+            Rate::MinimumInterval { interval } => now.duration_since(self.last_sent) >= interval,
+        };
+        if is_due {
+            self.cycles_since_last_sent = 0;
+            self.last_sent = now;
+        }
+        is_due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn every_nth_cycle_is_due_every_nth_cycle() {
+        let mut subscription = Subscription::new(
+            "a.b.c".to_string(),
+            Format::Textual,
+            false,
+            Rate::EveryNthCycle { n: 3 },
+        );
+        let now = Instant::now();
+
+        let due_cycles: Vec<_> = (0..6).map(|_| subscription.is_due(now)).collect();
+        assert_eq!(due_cycles, [false, false, true, false, false, true]);
+    }
+
+    #[test]
+    fn minimum_interval_is_due_after_interval() {
+        let start = Instant::now();
+        let mut subscription = Subscription::new(
+            "a.b.c".to_string(),
+            Format::Binary,
+            false,
+            Rate::MinimumInterval {
+                interval: Duration::from_millis(100),
+            },
+        );
+        subscription.last_sent = start;
+
+        assert!(!subscription.is_due(start + Duration::from_millis(50)));
+        assert!(subscription.is_due(start + Duration::from_millis(100)));
+        assert!(!subscription.is_due(start + Duration::from_millis(150)));
+        assert!(subscription.is_due(start + Duration::from_millis(200)));
+    }
 }
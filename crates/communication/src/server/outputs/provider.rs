@@ -2,6 +2,7 @@ use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     num::Wrapping,
     sync::Arc,
+    time::Instant,
 };
 
 use bincode::{DefaultOptions, Options};
@@ -19,8 +20,9 @@ use tokio::{
 };
 
 use crate::{
+    compression::compress,
     messages::{
-        BinaryOutputsResponse, BinaryResponse, Format, OutputsRequest, Response,
+        BinaryOutputsResponse, BinaryResponse, Format, OutputsRequest, Reason, Response,
         TextualDataOrBinaryReference, TextualOutputsResponse, TextualResponse,
     },
     server::{client::Client, client_request::ClientRequest},
@@ -45,6 +47,7 @@ where
             .send(Request::RegisterCycler {
                 cycler_instance: cycler_instance.to_string(),
                 fields: Outputs::get_fields(),
+                types: Outputs::get_types(),
                 request_sender,
             })
             .await
@@ -105,18 +108,20 @@ where
             cycler_instance: received_cycler_instance,
             path,
             format,
+            rate,
         }
         | OutputsRequest::Subscribe {
             id,
             cycler_instance: received_cycler_instance,
             path,
             format,
+            rate,
         } => {
             assert_eq!(cycler_instance, received_cycler_instance);
             if Outputs::exists(&path) {
                 match subscriptions.entry((request.client.clone(), id)) {
                     Entry::Occupied(_) => {
-                        let error_message = format!("already subscribed with id {id}");
+                        let reason = Reason::Failed(format!("already subscribed with id {id}"));
                         request
                             .client
                             .response_sender
@@ -124,12 +129,12 @@ where
                                 if is_get_next {
                                     TextualOutputsResponse::GetNext {
                                         id,
-                                        result: Err(error_message),
+                                        result: Err(reason),
                                     }
                                 } else {
                                     TextualOutputsResponse::Subscribe {
                                         id,
-                                        result: Err(error_message),
+                                        result: Err(reason),
                                     }
                                 },
                             )))
@@ -138,11 +143,7 @@ where
                         SubscriptionsState::Unchanged
                     }
                     Entry::Vacant(entry) => {
-                        entry.insert(Subscription {
-                            path,
-                            format,
-                            once: is_get_next,
-                        });
+                        entry.insert(Subscription::new(path, format, is_get_next, rate));
                         if !is_get_next {
                             request
                                 .client
@@ -163,7 +164,7 @@ where
                     .send(Response::Textual(TextualResponse::Outputs(
                         TextualOutputsResponse::Subscribe {
                             id,
-                            result: Err(format!("path {path:?} does not exist")),
+                            result: Err(Reason::Failed(format!("path {path:?} does not exist"))),
                         },
                     )))
                     .await
@@ -185,9 +186,9 @@ where
                     .send(Response::Textual(TextualResponse::Outputs(
                         TextualOutputsResponse::Unsubscribe {
                             id,
-                            result: Err(format!(
+                            result: Err(Reason::Failed(format!(
                                 "never subscribed with subscription id {subscription_id}"
-                            )),
+                            ))),
                         },
                     )))
                     .await
@@ -245,9 +246,13 @@ async fn handle_notified_output(
     let mut subscriptions_state = SubscriptionsState::Unchanged;
     {
         let output = outputs_reader.next();
+        let now = Instant::now();
         subscriptions.retain(|(client, subscription_id), subscription| {
-            let data = match subscription.format {
-                Format::Textual => {
+            if !subscription.is_due(now) {
+                return true;
+            }
+            let data = match BinaryFormat::of(subscription.format) {
+                None => {
                     let data = match output
                         .serialize_path(&subscription.path, serde_json::value::Serializer)
                     {
@@ -259,16 +264,14 @@ async fn handle_notified_output(
                     };
                     TextualDataOrBinaryReference::TextualData { data }
                 }
-                Format::Binary => {
-                    let mut data = Vec::new();
-                    let options = DefaultOptions::new()
-                        .with_fixint_encoding()
-                        .allow_trailing_bytes();
-                    let mut serializer = bincode::Serializer::new(&mut data, options);
-                    if let Err(error) = output.serialize_path(&subscription.path, &mut serializer) {
-                        error!("failed to serialize {:?}: {error:?}", subscription.path);
-                        return true;
-                    }
+                Some(binary_format) => {
+                    let data = match serialize_binary(&*output, &subscription.path, binary_format) {
+                        Ok(data) => data,
+                        Err(error) => {
+                            error!("failed to serialize {:?}: {error}", subscription.path);
+                            return true;
+                        }
+                    };
                     let reference_id = next_binary_reference_id.0;
                     *next_binary_reference_id += 1;
                     if subscription.once {
@@ -353,6 +356,49 @@ async fn handle_notified_output(
     subscriptions_state
 }
 
+#[derive(Clone, Copy)]
+enum BinaryFormat {
+    Bincode,
+    CompressedMessagePack,
+}
+
+impl BinaryFormat {
+    fn of(format: Format) -> Option<Self> {
+        match format {
+            Format::Textual => None,
+            Format::Binary => Some(Self::Bincode),
+            Format::Compressed => Some(Self::CompressedMessagePack),
+        }
+    }
+}
+
+fn serialize_binary(
+    output: &impl SerializeHierarchy,
+    path: &str,
+    format: BinaryFormat,
+) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    match format {
+        BinaryFormat::Bincode => {
+            let options = DefaultOptions::new()
+                .with_fixint_encoding()
+                .allow_trailing_bytes();
+            let mut serializer = bincode::Serializer::new(&mut data, options);
+            output
+                .serialize_path(path, &mut serializer)
+                .map_err(|error| format!("{error:?}"))?;
+        }
+        BinaryFormat::CompressedMessagePack => {
+            let mut serializer = rmp_serde::Serializer::new(&mut data).with_struct_map();
+            output
+                .serialize_path(path, &mut serializer)
+                .map_err(|error| format!("{error:?}"))?;
+            data = compress(&data).map_err(|error| format!("{error:?}"))?;
+        }
+    }
+    Ok(data)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::BTreeSet, time::Duration};
@@ -364,7 +410,7 @@ mod tests {
     use serialize_hierarchy::Error;
     use tokio::{sync::mpsc::error::TryRecvError, task::yield_now, time::timeout};
 
-    use crate::messages::Format;
+    use crate::messages::{Format, Rate};
 
     use super::*;
 
@@ -440,7 +486,7 @@ mod tests {
             let Some(request) = outputs_receiver.recv().await else {
                 panic!("expected request");
             };
-            let Request::RegisterCycler { cycler_instance: cycler_instance_to_register, fields, request_sender } = request else {
+            let Request::RegisterCycler { cycler_instance: cycler_instance_to_register, fields, request_sender, .. } = request else {
                 panic!("expected Request::RegisterCycler");
             };
             assert_eq!(cycler_instance, cycler_instance_to_register);
@@ -534,6 +580,7 @@ mod tests {
                     cycler_instance: cycler_instance.clone(),
                     path: path.clone(),
                     format,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -571,6 +618,7 @@ mod tests {
                     cycler_instance,
                     path: path.clone(),
                     format,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -635,6 +683,7 @@ mod tests {
                     cycler_instance: cycler_instance.clone(),
                     path: path.clone(),
                     format,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: 1337,
@@ -672,6 +721,7 @@ mod tests {
                     cycler_instance,
                     path: path.clone(),
                     format,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: 7331,
@@ -736,6 +786,7 @@ mod tests {
                     cycler_instance: cycler_instance.clone(),
                     path: path.clone(),
                     format,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -773,6 +824,7 @@ mod tests {
                     cycler_instance,
                     path: path.clone(),
                     format,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -890,6 +942,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1014,6 +1067,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: 1337,
@@ -1130,6 +1184,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1257,6 +1312,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Binary,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1389,6 +1445,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1427,6 +1484,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1611,6 +1669,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Textual,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1689,6 +1748,7 @@ mod tests {
                     cycler_instance: cycler_instance.to_string(),
                     path: path.clone(),
                     format: Format::Binary,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: client_id,
@@ -1,5 +1,6 @@
-use std::collections::{hash_map::Entry, BTreeSet, HashMap};
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap};
 
+use serialize_hierarchy::TypeDescription;
 use tokio::{
     spawn,
     sync::mpsc::{Receiver, Sender},
@@ -7,12 +8,18 @@ use tokio::{
 };
 
 use crate::{
-    messages::{OutputsRequest, Path, Response, TextualOutputsResponse, TextualResponse},
+    messages::{OutputsRequest, Path, Reason, Response, TextualOutputsResponse, TextualResponse},
     server::{client::Client, client_request::ClientRequest},
 };
 
 use super::Request;
 
+struct RegisteredCycler {
+    fields: BTreeSet<Path>,
+    types: BTreeMap<Path, TypeDescription>,
+    request_sender: Sender<ClientRequest<OutputsRequest>>,
+}
+
 pub fn router(mut request_receiver: Receiver<Request>) -> JoinHandle<()> {
     spawn(async move {
         let mut request_channels_of_cyclers = HashMap::new();
@@ -31,9 +38,17 @@ pub fn router(mut request_receiver: Receiver<Request>) -> JoinHandle<()> {
                 Request::RegisterCycler {
                     cycler_instance,
                     fields,
+                    types,
                     request_sender,
                 } => {
-                    request_channels_of_cyclers.insert(cycler_instance, (fields, request_sender));
+                    request_channels_of_cyclers.insert(
+                        cycler_instance,
+                        RegisteredCycler {
+                            fields,
+                            types,
+                            request_sender,
+                        },
+                    );
                 }
             }
         }
@@ -42,10 +57,7 @@ pub fn router(mut request_receiver: Receiver<Request>) -> JoinHandle<()> {
 
 async fn handle_request(
     request: ClientRequest<OutputsRequest>,
-    request_channels_of_cyclers: &HashMap<
-        String,
-        (BTreeSet<Path>, Sender<ClientRequest<OutputsRequest>>),
-    >,
+    request_channels_of_cyclers: &HashMap<String, RegisteredCycler>,
     cached_cycler_instances: &mut HashMap<(Client, usize), String>,
 ) {
     match &request.request {
@@ -58,8 +70,14 @@ async fn handle_request(
                         id: *id,
                         fields: request_channels_of_cyclers
                             .iter()
-                            .map(|(cycler_instance, (fields, _request_sender))| {
-                                (cycler_instance.clone(), fields.clone())
+                            .map(|(cycler_instance, cycler)| {
+                                (cycler_instance.clone(), cycler.fields.clone())
+                            })
+                            .collect(),
+                        types: request_channels_of_cyclers
+                            .iter()
+                            .map(|(cycler_instance, cycler)| {
+                                (cycler_instance.clone(), cycler.types.clone())
                             })
                             .collect(),
                     },
@@ -83,14 +101,16 @@ async fn handle_request(
             }
 
             match request_channels_of_cyclers.get(cycler_instance) {
-                Some((_fields, request_channel)) => {
-                    request_channel
+                Some(cycler) => {
+                    cycler
+                        .request_sender
                         .send(request)
                         .await
                         .expect("receiver should always wait for all senders");
                 }
                 None => {
-                    let error_message = format!("unknown cycler_instance {cycler_instance:?}");
+                    let reason =
+                        Reason::Failed(format!("unknown cycler_instance {cycler_instance:?}"));
                     request
                         .client
                         .response_sender
@@ -98,12 +118,12 @@ async fn handle_request(
                             if matches!(request.request, OutputsRequest::GetNext { .. }) {
                                 TextualOutputsResponse::GetNext {
                                     id: *id,
-                                    result: Err(error_message),
+                                    result: Err(reason),
                                 }
                             } else {
                                 TextualOutputsResponse::Subscribe {
                                     id: *id,
-                                    result: Err(error_message),
+                                    result: Err(reason),
                                 }
                             },
                         )))
@@ -116,29 +136,31 @@ async fn handle_request(
             id,
             subscription_id,
         } => {
-            let cycler_instance = match cached_cycler_instances
-                .entry((request.client.clone(), *subscription_id))
-            {
-                Entry::Occupied(entry) => entry.remove(),
-                Entry::Vacant(_) => {
-                    request
-                        .client
-                        .response_sender
-                        .send(Response::Textual(TextualResponse::Outputs(
-                            TextualOutputsResponse::Unsubscribe {
-                                id: *id,
-                                result: Err(format!("unknown subscription ID {subscription_id}")),
-                            },
-                        )))
-                        .await
-                        .expect("receiver should always wait for all senders");
-                    return;
-                }
-            };
+            let cycler_instance =
+                match cached_cycler_instances.entry((request.client.clone(), *subscription_id)) {
+                    Entry::Occupied(entry) => entry.remove(),
+                    Entry::Vacant(_) => {
+                        request
+                            .client
+                            .response_sender
+                            .send(Response::Textual(TextualResponse::Outputs(
+                                TextualOutputsResponse::Unsubscribe {
+                                    id: *id,
+                                    result: Err(Reason::Failed(format!(
+                                        "unknown subscription ID {subscription_id}"
+                                    ))),
+                                },
+                            )))
+                            .await
+                            .expect("receiver should always wait for all senders");
+                        return;
+                    }
+                };
 
             match request_channels_of_cyclers.get(&cycler_instance) {
-                Some((_fields, request_channel)) => {
-                    request_channel
+                Some(cycler) => {
+                    cycler
+                        .request_sender
                         .send(request)
                         .await
                         .expect("receiver should always wait for all senders");
@@ -150,7 +172,9 @@ async fn handle_request(
                         .send(Response::Textual(TextualResponse::Outputs(
                             TextualOutputsResponse::Unsubscribe {
                                 id: *id,
-                                result: Err(format!("unknown cycler_instance {cycler_instance:?}")),
+                                result: Err(Reason::Failed(format!(
+                                    "unknown cycler_instance {cycler_instance:?}"
+                                ))),
                             },
                         )))
                         .await
@@ -161,8 +185,9 @@ async fn handle_request(
         OutputsRequest::UnsubscribeEverything => {
             cached_cycler_instances
                 .retain(|(client, _subscription_id), _cycler_instance| client != &request.client);
-            for (_fields, request_channel) in request_channels_of_cyclers.values() {
-                request_channel
+            for cycler in request_channels_of_cyclers.values() {
+                cycler
+                    .request_sender
                     .send(request.clone())
                     .await
                     .expect("receiver should always wait for all senders");
@@ -175,7 +200,7 @@ async fn handle_request(
 mod tests {
     use tokio::sync::mpsc::{channel, error::TryRecvError};
 
-    use crate::messages::Format;
+    use crate::messages::{Format, Rate};
 
     use super::*;
 
@@ -195,11 +220,14 @@ mod tests {
 
         let cycler_instance = "CyclerInstance";
         let fields: BTreeSet<String> = ["a.b.c".to_string()].into();
+        let types: BTreeMap<String, TypeDescription> =
+            [("a.b.c".to_string(), TypeDescription::Struct)].into();
         let (provider_request_sender, _provider_request_receiver) = channel(1);
         request_sender
             .send(Request::RegisterCycler {
                 cycler_instance: cycler_instance.to_string(),
                 fields: fields.clone(),
+                types: types.clone(),
                 request_sender: provider_request_sender,
             })
             .await
@@ -222,7 +250,8 @@ mod tests {
             Response::Textual(TextualResponse::Outputs(
                 TextualOutputsResponse::GetFields {
                     id: 42,
-                    fields: [(cycler_instance.to_string(), fields)].into()
+                    fields: [(cycler_instance.to_string(), fields)].into(),
+                    types: [(cycler_instance.to_string(), types)].into(),
                 }
             )),
         );
@@ -248,6 +277,7 @@ mod tests {
                     cycler_instance: "CyclerInstance".to_string(),
                     path: "a.b.c".to_string(),
                     format: Format::Textual,
+                    rate: Rate::EveryCycle,
                 },
                 client: Client {
                     id: 1337,
@@ -287,6 +317,7 @@ mod tests {
             .send(Request::RegisterCycler {
                 cycler_instance: cycler_instance.to_string(),
                 fields: Default::default(),
+                types: Default::default(),
                 request_sender: provider_request_sender,
             })
             .await
@@ -299,6 +330,7 @@ mod tests {
                 cycler_instance: "CyclerInstance".to_string(),
                 path: "a.b.c".to_string(),
                 format: Format::Textual,
+                rate: Rate::EveryCycle,
             },
             client: Client {
                 id: 1337,
@@ -327,6 +359,7 @@ mod tests {
             .send(Request::RegisterCycler {
                 cycler_instance: cycler_instance.to_string(),
                 fields: Default::default(),
+                types: Default::default(),
                 request_sender: provider_request_sender,
             })
             .await
@@ -343,6 +376,7 @@ mod tests {
                 cycler_instance: "CyclerInstance".to_string(),
                 path: "a.b.c".to_string(),
                 format: Format::Textual,
+                rate: Rate::EveryCycle,
             },
             client: client.clone(),
         };
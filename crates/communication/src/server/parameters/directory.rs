@@ -8,7 +8,10 @@ use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{error, from_str, from_value, to_value, Value};
 use tokio::fs::{read_to_string, write};
 
-use crate::merge_json;
+use crate::{
+    merge_json,
+    messages::{Layer, Path as ParameterPath},
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum DirectoryError {
@@ -30,6 +33,12 @@ pub enum DirectoryError {
     ParametersNotConvertedToJsonValue(#[source] error::Error),
     #[error("failed to set head parameters of location")]
     HeadParametersOfLocationNotSet(#[source] SerializationError),
+    #[error("path {0:?} does not exist")]
+    PathNotFound(ParameterPath),
+    #[error("failed to get parameters of layer")]
+    LayerParametersNotGet(#[source] SerializationError),
+    #[error("failed to set parameters of layer")]
+    LayerParametersNotSet(#[source] SerializationError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -132,6 +141,85 @@ where
         .map_err(DirectoryError::HeadParametersOfLocationNotSet)
 }
 
+/// Returns the values of the given paths which differ from the parameters stored on disk
+pub async fn diff_of_paths<Parameters>(
+    parameters: &Parameters,
+    paths: &[ParameterPath],
+    root_path: impl AsRef<Path>,
+    body_id: &str,
+    head_id: &str,
+) -> Result<Value, DirectoryError>
+where
+    Parameters: Serialize,
+{
+    let parameters =
+        to_value(parameters).map_err(DirectoryError::ParametersNotConvertedToJsonValue)?;
+    let mut selected_parameters = select_paths(&parameters, paths)?;
+    let stored_parameters = deserialize::<Value>(&root_path, body_id, head_id).await?;
+
+    prune_equal_branches(&mut selected_parameters, &stored_parameters);
+
+    Ok(selected_parameters)
+}
+
+/// Merges the diff into the file of the given layer, the file is created if it does not exist
+pub async fn serialize_to_layer(
+    diff: &Value,
+    layer: Layer,
+    root_path: impl AsRef<Path>,
+    body_id: &str,
+    head_id: &str,
+) -> Result<(), DirectoryError> {
+    let layer_file_path = get_layer_file_path(layer, root_path, body_id, head_id);
+
+    let mut layer_parameters = if layer_file_path.exists() {
+        from_path(&layer_file_path)
+            .await
+            .map_err(DirectoryError::LayerParametersNotGet)?
+    } else {
+        Value::Object(Default::default())
+    };
+    merge_json(&mut layer_parameters, diff);
+
+    to_path(layer_file_path, layer_parameters)
+        .await
+        .map_err(DirectoryError::LayerParametersNotSet)
+}
+
+fn select_paths(parameters: &Value, paths: &[ParameterPath]) -> Result<Value, DirectoryError> {
+    let mut selected_parameters = Value::Object(Default::default());
+    for path in paths {
+        let value = path
+            .split('.')
+            .try_fold(parameters, |value, segment| value.get(segment))
+            .ok_or_else(|| DirectoryError::PathNotFound(path.clone()))?;
+        let leaf = path
+            .split('.')
+            .fold(&mut selected_parameters, |value, segment| {
+                &mut value[segment]
+            });
+        *leaf = value.clone();
+    }
+    Ok(selected_parameters)
+}
+
+fn get_layer_file_path(
+    layer: Layer,
+    root_path: impl AsRef<Path>,
+    body_id: &str,
+    head_id: &str,
+) -> PathBuf {
+    let location_directory = root_path.as_ref().join(get_location_directory(head_id));
+    match layer {
+        Layer::Default => root_path.as_ref().join("default.json"),
+        Layer::LocationDefault => location_directory.join("default.json"),
+        Layer::Body => root_path.as_ref().join(format!("body.{body_id}.json")),
+        Layer::Head => root_path.as_ref().join(format!("head.{head_id}.json")),
+        Layer::LocationBody => location_directory.join(format!("body.{body_id}.json")),
+        Layer::LocationHead => location_directory.join(format!("head.{head_id}.json")),
+    }
+}
+
 fn get_location_directory(head_id: &str) -> &'static str {
     let webots_id_found = head_id.starts_with("webots");
     let behavior_simulator_id_found = head_id.starts_with("behavior_simulator");
@@ -226,4 +314,22 @@ mod tests {
 
         assert_eq!(own, from_str::<Value>(r#"{"a":{"b":{"c":42}}}"#).unwrap());
     }
+
+    #[test]
+    fn only_selected_paths_are_kept() {
+        let parameters: Value =
+            from_str(r#"{"a":{"b":{"c":42,"d":true},"e":1337},"f":null}"#).unwrap();
+
+        let selected_parameters =
+            select_paths(&parameters, &["a.b.c".to_string(), "f".to_string()]).unwrap();
+
+        assert_eq!(
+            selected_parameters,
+            from_str::<Value>(r#"{"a":{"b":{"c":42}},"f":null}"#).unwrap()
+        );
+        assert!(matches!(
+            select_paths(&parameters, &["a.x".to_string()]),
+            Err(DirectoryError::PathNotFound(path)) if path == "a.x"
+        ));
+    }
 }
@@ -1,6 +1,6 @@
 use serde_json::Value;
 
-use crate::messages::Path;
+use crate::messages::{Layer, Path};
 
 use super::client::Client;
 
@@ -24,4 +24,20 @@ pub enum StorageRequest {
         client: Client,
         id: usize,
     },
+    GetHistory {
+        client: Client,
+        id: usize,
+    },
+    RevertTo {
+        client: Client,
+        id: usize,
+        change_id: usize,
+    },
+    StoreToLayer {
+        client: Client,
+        id: usize,
+        paths: Vec<Path>,
+        layer: Layer,
+        dry_run: bool,
+    },
 }
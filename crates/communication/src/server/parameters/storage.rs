@@ -1,8 +1,14 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    path::Path,
+    sync::Arc,
+    time::SystemTime,
+};
 
 use framework::Writer;
 use serde::{de::DeserializeOwned, Serialize};
-use serialize_hierarchy::SerializeHierarchy;
+use serde_json::Value;
+use serialize_hierarchy::{check_constraints, Constraints, SerializeHierarchy};
 use tokio::{
     spawn,
     sync::{mpsc::Receiver, Notify},
@@ -10,12 +16,15 @@ use tokio::{
 };
 
 use crate::{
-    messages::{ParametersResponse, Response, TextualResponse},
+    messages::{
+        Layer, ParameterChange, ParametersResponse, Path as ParameterPath, Reason, Response,
+        TextualResponse,
+    },
     server::client::Client,
 };
 
 use super::{
-    directory::{deserialize, serialize},
+    directory::{deserialize, diff_of_paths, serialize, serialize_to_layer, DirectoryError},
     StorageRequest,
 };
 
@@ -32,10 +41,14 @@ where
 {
     spawn(async move {
         let mut parameters = (*parameters_writer.next()).clone();
+        let mut history = History::default();
+        let constraints = Parameters::get_constraints();
         while let Some(request) = request_receiver.recv().await {
             handle_request(
                 request,
                 &mut parameters,
+                &mut history,
+                &constraints,
                 &parameters_writer,
                 &parameters_changed,
                 &parameters_directory,
@@ -47,9 +60,43 @@ where
     })
 }
 
+const MAXIMUM_NUMBER_OF_CHANGES: usize = 100;
+
+/// Bounded log of the changes applied to the parameters, oldest changes are dropped first
+#[derive(Default)]
+struct History {
+    changes: VecDeque<ParameterChange>,
+    next_id: usize,
+}
+
+impl History {
+    fn record(&mut self, path: String, old_value: Value, new_value: Value, client_id: usize) {
+        if self.changes.len() == MAXIMUM_NUMBER_OF_CHANGES {
+            self.changes.pop_front();
+        }
+        self.changes.push_back(ParameterChange {
+            id: self.next_id,
+            path,
+            old_value,
+            new_value,
+            timestamp: SystemTime::now(),
+            client_id,
+        });
+        self.next_id += 1;
+    }
+
+    /// Changes can not be reverted anymore, ids are not reused to not revert unrelated changes
+    fn clear(&mut self) {
+        self.changes.clear();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_request<Parameters>(
     request: StorageRequest,
     parameters: &mut Parameters,
+    history: &mut History,
+    constraints: &BTreeMap<String, Constraints>,
     parameters_writer: &Writer<Parameters>,
     parameters_changed: &Arc<Notify>,
     parameters_directory: impl AsRef<Path>,
@@ -70,25 +117,54 @@ async fn handle_request<Parameters>(
                     client,
                     ParametersResponse::Update {
                         id,
-                        result: Err(format!("path {path:?} does not exist")),
+                        result: Err(Reason::Failed(format!("path {path:?} does not exist"))),
                     },
                 )
                 .await;
                 return;
             }
 
-            if let Err(error) = parameters.deserialize_path(&path, data) {
+            if let Err(violation) = check_constraints(constraints, &path, &data) {
                 respond(
                     client,
                     ParametersResponse::Update {
                         id,
-                        result: Err(format!("failed to deserialize: {error:?}")),
+                        result: Err(Reason::Failed(format!("constraint violated: {violation}"))),
                     },
                 )
                 .await;
                 return;
             }
 
+            let old_value = match parameters.serialize_path(&path, serde_json::value::Serializer) {
+                Ok(old_value) => old_value,
+                Err(error) => {
+                    respond(
+                        client,
+                        ParametersResponse::Update {
+                            id,
+                            result: Err(Reason::Failed(format!("failed to serialize: {error:?}"))),
+                        },
+                    )
+                    .await;
+                    return;
+                }
+            };
+
+            if let Err(error) = parameters.deserialize_path(&path, data.clone()) {
+                respond(
+                    client,
+                    ParametersResponse::Update {
+                        id,
+                        result: Err(Reason::Failed(format!("failed to deserialize: {error:?}"))),
+                    },
+                )
+                .await;
+                return;
+            }
+
+            history.record(path, old_value, data, client.id);
+
             {
                 let mut slot = parameters_writer.next();
                 *slot = parameters.clone();
@@ -98,24 +174,28 @@ async fn handle_request<Parameters>(
             respond(client, ParametersResponse::Update { id, result: Ok(()) }).await;
         }
         StorageRequest::LoadFromDisk { client, id } => {
-            let parameters = match deserialize(parameters_directory, body_id, head_id).await {
+            *parameters = match deserialize(parameters_directory, body_id, head_id).await {
                 Ok(parameters) => parameters,
                 Err(error) => {
                     respond(
                         client,
                         ParametersResponse::LoadFromDisk {
                             id,
-                            result: Err(format!("failed to deserialize parameters: {error:?}")),
+                            result: Err(Reason::Failed(format!(
+                                "failed to deserialize parameters: {error:?}"
+                            ))),
                         },
                     )
                     .await;
                     return;
                 }
             };
+            // old values of recorded changes would overwrite the loaded parameters when reverted
+            history.clear();
 
             {
                 let mut slot = parameters_writer.next();
-                *slot = parameters;
+                *slot = parameters.clone();
             }
             parameters_changed.notify_one();
 
@@ -132,7 +212,9 @@ async fn handle_request<Parameters>(
                     client,
                     ParametersResponse::StoreToDisk {
                         id,
-                        result: Err(format!("failed to serialize parameters: {error:?}")),
+                        result: Err(Reason::Failed(format!(
+                            "failed to serialize parameters: {error:?}"
+                        ))),
                     },
                 )
                 .await;
@@ -145,9 +227,136 @@ async fn handle_request<Parameters>(
             )
             .await;
         }
+        StorageRequest::GetHistory { client, id } => {
+            respond(
+                client,
+                ParametersResponse::GetHistory {
+                    id,
+                    history: history.changes.iter().cloned().collect(),
+                },
+            )
+            .await;
+        }
+        StorageRequest::RevertTo {
+            client,
+            id,
+            change_id,
+        } => {
+            if !history.changes.iter().any(|change| change.id == change_id) {
+                respond(
+                    client,
+                    ParametersResponse::RevertTo {
+                        id,
+                        result: Err(Reason::Failed(format!(
+                            "change {change_id} is not in the history"
+                        ))),
+                    },
+                )
+                .await;
+                return;
+            }
+
+            // undo all changes from the newest down to the requested one on a copy, such that a
+            // failing change leaves the parameters untouched
+            let mut reverted_parameters = parameters.clone();
+            let mut reverts = Vec::new();
+            for change in history
+                .changes
+                .iter()
+                .rev()
+                .take_while(|change| change.id >= change_id)
+            {
+                let reverted = reverted_parameters
+                    .serialize_path(&change.path, serde_json::value::Serializer)
+                    .map_err(|error| format!("failed to serialize: {error:?}"))
+                    .and_then(|current_value| {
+                        reverted_parameters
+                            .deserialize_path(&change.path, change.old_value.clone())
+                            .map_err(|error| format!("failed to deserialize: {error:?}"))?;
+                        Ok(current_value)
+                    });
+                match reverted {
+                    Ok(current_value) => {
+                        reverts.push((change.path.clone(), current_value, change.old_value.clone()))
+                    }
+                    Err(error) => {
+                        respond(
+                            client,
+                            ParametersResponse::RevertTo {
+                                id,
+                                result: Err(Reason::Failed(error)),
+                            },
+                        )
+                        .await;
+                        return;
+                    }
+                }
+            }
+
+            *parameters = reverted_parameters;
+            for (path, old_value, new_value) in reverts {
+                history.record(path, old_value, new_value, client.id);
+            }
+
+            {
+                let mut slot = parameters_writer.next();
+                *slot = parameters.clone();
+            }
+            parameters_changed.notify_one();
+
+            respond(client, ParametersResponse::RevertTo { id, result: Ok(()) }).await;
+        }
+        StorageRequest::StoreToLayer {
+            client,
+            id,
+            paths,
+            layer,
+            dry_run,
+        } => {
+            let result = store_to_layer(
+                parameters,
+                &paths,
+                layer,
+                dry_run,
+                parameters_directory,
+                body_id,
+                head_id,
+            )
+            .await;
+
+            respond(
+                client,
+                ParametersResponse::StoreToLayer {
+                    id,
+                    result: result.map_err(|error| {
+                        Reason::Failed(format!("failed to store parameters: {error:?}"))
+                    }),
+                },
+            )
+            .await;
+        }
     }
 }
 
+async fn store_to_layer<Parameters>(
+    parameters: &Parameters,
+    paths: &[ParameterPath],
+    layer: Layer,
+    dry_run: bool,
+    parameters_directory: impl AsRef<Path>,
+    body_id: &str,
+    head_id: &str,
+) -> Result<Value, DirectoryError>
+where
+    Parameters: Serialize,
+{
+    let diff = diff_of_paths(parameters, paths, &parameters_directory, body_id, head_id).await?;
+    if !dry_run {
+        serialize_to_layer(&diff, layer, parameters_directory, body_id, head_id).await?;
+    }
+    Ok(diff)
+}
+
 async fn respond(client: Client, response: ParametersResponse) {
     client
         .response_sender
@@ -158,12 +367,16 @@ async fn respond(client: Client, response: ParametersResponse) {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::{BTreeSet, HashMap};
+    use std::{
+        collections::{BTreeMap, BTreeSet, HashMap},
+        fs::write,
+    };
 
     use framework::multiple_buffer_with_slots;
     use serde::{Deserialize, Deserializer, Serializer};
     use serde_json::Value;
-    use serialize_hierarchy::Error;
+    use serialize_hierarchy::{Constraints, Error};
+    use tempfile::tempdir;
     use tokio::sync::mpsc::{channel, error::TryRecvError};
 
     use crate::server::client::Client;
@@ -232,6 +445,17 @@ mod tests {
         fn get_fields() -> BTreeSet<String> {
             ["a".to_string(), "a.b".to_string(), "a.b.c".to_string()].into()
         }
+
+        fn get_constraints() -> BTreeMap<String, Constraints> {
+            [(
+                "a.b.c".to_string(),
+                Constraints {
+                    minimum: Some(0.0),
+                    ..Default::default()
+                },
+            )]
+            .into()
+        }
     }
 
     #[tokio::test]
@@ -284,4 +508,225 @@ mod tests {
         drop(request_sender);
         subscriptions_task.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn update_request_violating_constraints_is_rejected() {
+        let path = "a.b.c".to_string();
+        let (parameters_writer, parameters_reader) = multiple_buffer_with_slots([ParametersFake {
+            existing_fields: [(path.clone(), 42)].into(),
+        }]);
+        let parameters_changed = Arc::new(Notify::new());
+        let (request_sender, request_receiver) = channel(1);
+        let subscriptions_task = storage(
+            parameters_writer,
+            parameters_changed,
+            request_receiver,
+            ".",
+            Default::default(),
+            Default::default(),
+        );
+
+        let (response_sender, mut response_receiver) = channel(1);
+        request_sender
+            .send(StorageRequest::UpdateParameter {
+                client: Client {
+                    id: 1337,
+                    response_sender,
+                },
+                id: 42,
+                path: path.clone(),
+                data: Value::from(-1),
+            })
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        assert!(
+            matches!(
+                response,
+                Response::Textual(TextualResponse::Parameters(ParametersResponse::Update {
+                    id: 42,
+                    result: Err(_),
+                }))
+            ),
+            "unexpected {response:?}",
+        );
+        let parameters = parameters_reader.next();
+        assert_eq!(parameters.existing_fields.get(&path), Some(42).as_ref());
+
+        drop(request_sender);
+        subscriptions_task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reverting_restores_values_before_change_and_records_revert() {
+        let path = "a.b.c".to_string();
+        let (parameters_writer, parameters_reader) = multiple_buffer_with_slots([ParametersFake {
+            existing_fields: [(path.clone(), 42)].into(),
+        }]);
+        let parameters_changed = Arc::new(Notify::new());
+        let (request_sender, request_receiver) = channel(1);
+        let subscriptions_task = storage(
+            parameters_writer,
+            parameters_changed,
+            request_receiver,
+            ".",
+            Default::default(),
+            Default::default(),
+        );
+
+        let (response_sender, mut response_receiver) = channel(1);
+        let client = Client {
+            id: 1337,
+            response_sender,
+        };
+        for (id, value) in [(1, 1), (2, 2)] {
+            request_sender
+                .send(StorageRequest::UpdateParameter {
+                    client: client.clone(),
+                    id,
+                    path: path.clone(),
+                    data: Value::from(value),
+                })
+                .await
+                .unwrap();
+            response_receiver.recv().await.unwrap();
+        }
+
+        request_sender
+            .send(StorageRequest::RevertTo {
+                client: client.clone(),
+                id: 3,
+                change_id: 0,
+            })
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        assert_eq!(
+            response,
+            Response::Textual(TextualResponse::Parameters(ParametersResponse::RevertTo {
+                id: 3,
+                result: Ok(()),
+            })),
+        );
+        let parameters = parameters_reader.next();
+        assert_eq!(parameters.existing_fields.get(&path), Some(42).as_ref());
+
+        request_sender
+            .send(StorageRequest::GetHistory { client, id: 4 })
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        let Response::Textual(TextualResponse::Parameters(ParametersResponse::GetHistory {
+            id: 4,
+            history,
+        })) = response
+        else {
+            panic!("unexpected {response:?}");
+        };
+        let values: Vec<_> = history
+            .iter()
+            .map(|change| {
+                (
+                    change.id,
+                    change.old_value.clone(),
+                    change.new_value.clone(),
+                    change.client_id,
+                )
+            })
+            .collect();
+        assert_eq!(
+            values,
+            [
+                (0, Value::from(42), Value::from(1), 1337),
+                (1, Value::from(1), Value::from(2), 1337),
+                (2, Value::from(2), Value::from(1), 1337),
+                (3, Value::from(1), Value::from(42), 1337),
+            ]
+        );
+
+        drop(request_sender);
+        subscriptions_task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn loading_from_disk_clears_history() {
+        let path = "a.b.c".to_string();
+        let parameters_directory = tempdir().unwrap();
+        write(
+            parameters_directory.path().join("default.json"),
+            r#"{"existing_fields": {"a.b.c": 42}}"#,
+        )
+        .unwrap();
+        let (parameters_writer, parameters_reader) = multiple_buffer_with_slots([ParametersFake {
+            existing_fields: [(path.clone(), 42)].into(),
+        }]);
+        let parameters_changed = Arc::new(Notify::new());
+        let (request_sender, request_receiver) = channel(1);
+        let subscriptions_task = storage(
+            parameters_writer,
+            parameters_changed,
+            request_receiver,
+            parameters_directory.path().to_path_buf(),
+            Default::default(),
+            Default::default(),
+        );
+
+        let (response_sender, mut response_receiver) = channel(1);
+        let client = Client {
+            id: 1337,
+            response_sender,
+        };
+        request_sender
+            .send(StorageRequest::UpdateParameter {
+                client: client.clone(),
+                id: 1,
+                path: path.clone(),
+                data: Value::from(1),
+            })
+            .await
+            .unwrap();
+        response_receiver.recv().await.unwrap();
+        request_sender
+            .send(StorageRequest::LoadFromDisk {
+                client: client.clone(),
+                id: 2,
+            })
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        assert_eq!(
+            response,
+            Response::Textual(TextualResponse::Parameters(
+                ParametersResponse::LoadFromDisk {
+                    id: 2,
+                    result: Ok(()),
+                }
+            )),
+        );
+
+        request_sender
+            .send(StorageRequest::RevertTo {
+                client,
+                id: 3,
+                change_id: 0,
+            })
+            .await
+            .unwrap();
+        let response = response_receiver.recv().await.unwrap();
+        assert!(
+            matches!(
+                response,
+                Response::Textual(TextualResponse::Parameters(ParametersResponse::RevertTo {
+                    id: 3,
+                    result: Err(_),
+                }))
+            ),
+            "unexpected {response:?}",
+        );
+        let parameters = parameters_reader.next();
+        assert_eq!(parameters.existing_fields.get(&path), Some(42).as_ref());
+
+        drop(request_sender);
+        subscriptions_task.await.unwrap();
+    }
 }
@@ -1,12 +1,12 @@
 use std::{
-    collections::{hash_map::Entry, BTreeSet, HashMap},
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap},
     sync::Arc,
 };
 
 use framework::Reader;
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use log::error;
-use serialize_hierarchy::SerializeHierarchy;
+use serialize_hierarchy::{Constraints, SerializeHierarchy, TypeDescription};
 use tokio::{
     select, spawn,
     sync::{
@@ -17,7 +17,7 @@ use tokio::{
 };
 
 use crate::{
-    messages::{ParametersRequest, ParametersResponse, Path, Response, TextualResponse},
+    messages::{ParametersRequest, ParametersResponse, Path, Reason, Response, TextualResponse},
     server::{client::Client, client_request::ClientRequest},
 };
 
@@ -34,6 +34,8 @@ where
 {
     spawn(async move {
         let fields = Parameters::get_fields();
+        let constraints = Parameters::get_constraints();
+        let types = Parameters::get_types();
 
         let mut subscriptions = HashMap::new();
         loop {
@@ -48,6 +50,8 @@ where
                         &storage_request_sender,
                         &mut subscriptions,
                         &fields,
+                        &constraints,
+                        &types,
                     ).await;
                 },
                 _ = parameters_changed.notified() => {
@@ -64,6 +68,8 @@ async fn handle_request<Parameters>(
     storage_request_sender: &Sender<StorageRequest>,
     subscriptions: &mut HashMap<(Client, usize), Path>,
     fields: &BTreeSet<String>,
+    constraints: &BTreeMap<String, Constraints>,
+    types: &BTreeMap<String, TypeDescription>,
 ) where
     Parameters: SerializeHierarchy,
 {
@@ -74,6 +80,8 @@ async fn handle_request<Parameters>(
                 ParametersResponse::GetFields {
                     id,
                     fields: fields.clone(),
+                    constraints: constraints.clone(),
+                    types: types.clone(),
                 },
             )
             .await;
@@ -90,7 +98,7 @@ async fn handle_request<Parameters>(
                         request,
                         ParametersResponse::GetCurrent {
                             id,
-                            result: Err(format!("failed to serialize: {error:?}")),
+                            result: Err(Reason::Failed(format!("failed to serialize: {error:?}"))),
                         },
                     )
                     .await;
@@ -108,12 +116,12 @@ async fn handle_request<Parameters>(
         }
         ParametersRequest::Subscribe { id, ref path } => {
             if !Parameters::exists(path) {
-                let error_message = format!("path {path:?} does not exist");
+                let reason = Reason::Failed(format!("path {path:?} does not exist"));
                 respond(
                     request,
                     ParametersResponse::Subscribe {
                         id,
-                        result: Err(error_message),
+                        result: Err(reason),
                     },
                 )
                 .await;
@@ -123,7 +131,7 @@ async fn handle_request<Parameters>(
             let response = match subscriptions.entry((request.client.clone(), id)) {
                 Entry::Occupied(_) => ParametersResponse::Subscribe {
                     id,
-                    result: Err(format!("already subscribed with id {id}")),
+                    result: Err(Reason::Failed(format!("already subscribed with id {id}"))),
                 },
                 Entry::Vacant(entry) => {
                     entry.insert(path.to_string());
@@ -142,7 +150,7 @@ async fn handle_request<Parameters>(
                         request,
                         ParametersResponse::Subscribe {
                             id,
-                            result: Err(format!("failed to serialize: {error:?}")),
+                            result: Err(Reason::Failed(format!("failed to serialize: {error:?}"))),
                         },
                     )
                     .await;
@@ -171,9 +179,9 @@ async fn handle_request<Parameters>(
                     request,
                     ParametersResponse::Unsubscribe {
                         id,
-                        result: Err(format!(
+                        result: Err(Reason::Failed(format!(
                             "never subscribed with subscription id {subscription_id}"
-                        )),
+                        ))),
                     },
                 )
                 .await;
@@ -218,6 +226,42 @@ async fn handle_request<Parameters>(
                 .await
                 .expect("receiver should always wait for all senders");
         }
+        ParametersRequest::GetHistory { id } => {
+            storage_request_sender
+                .send(StorageRequest::GetHistory {
+                    client: request.client,
+                    id,
+                })
+                .await
+                .expect("receiver should always wait for all senders");
+        }
+        ParametersRequest::RevertTo { id, change_id } => {
+            storage_request_sender
+                .send(StorageRequest::RevertTo {
+                    client: request.client,
+                    id,
+                    change_id,
+                })
+                .await
+                .expect("receiver should always wait for all senders");
+        }
+        ParametersRequest::StoreToLayer {
+            id,
+            paths,
+            layer,
+            dry_run,
+        } => {
+            storage_request_sender
+                .send(StorageRequest::StoreToLayer {
+                    client: request.client,
+                    id,
+                    paths,
+                    layer,
+                    dry_run,
+                })
+                .await
+                .expect("receiver should always wait for all senders");
+        }
     }
 }
 
@@ -337,6 +381,8 @@ mod tests {
             Response::Textual(TextualResponse::Parameters(ParametersResponse::GetFields {
                 id: 42,
                 fields: Default::default(),
+                constraints: Default::default(),
+                types: Default::default(),
             })),
         );
         match response_receiver.try_recv() {
@@ -8,11 +8,15 @@ use tokio_tungstenite::{
 use tokio_util::sync::CancellationToken;
 
 use crate::{
-    messages::{OutputsRequest, ParametersRequest, Request, Response},
+    messages::{
+        AuthenticationRequest, AuthenticationResponse, InjectionsRequest, InjectionsResponse,
+        OutputsRequest, ParametersRequest, ParametersResponse, Reason, Request, Response,
+        TextualOutputsResponse, TextualResponse,
+    },
     server::client_request::ClientRequest,
 };
 
-use super::{client::Client, connection::ReceiverOrSenderError, outputs};
+use super::{authentication::Session, client::Client, connection::ReceiverOrSenderError, outputs};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn receiver(
@@ -21,6 +25,7 @@ pub async fn receiver(
     keep_running: CancellationToken,
     keep_only_self_running: CancellationToken,
     client_id: usize,
+    mut session: Session,
     response_sender: Sender<Response>,
     outputs_sender: Sender<outputs::Request>,
     parameters_sender: Sender<ClientRequest<ParametersRequest>>,
@@ -33,6 +38,7 @@ pub async fn receiver(
                     &error_sender,
                     &keep_only_self_running,
                     client_id,
+                    &mut session,
                     &response_sender,
                     &outputs_sender,
                     &parameters_sender,
@@ -65,11 +71,13 @@ pub async fn receiver(
         .expect("receiver should always wait for all senders");
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_message(
     message: Result<Message, tokio_tungstenite::tungstenite::Error>,
     error_sender: &Sender<ReceiverOrSenderError>,
     keep_only_self_running: &CancellationToken,
     client_id: usize,
+    session: &mut Session,
     response_sender: &Sender<Response>,
     outputs_sender: &Sender<outputs::Request>,
     parameters_sender: &Sender<ClientRequest<ParametersRequest>>,
@@ -104,11 +112,32 @@ async fn handle_message(
                 }
             };
 
+            if let Some(required_permission) = request.required_permission() {
+                if let Err(reason) = session.check(required_permission) {
+                    if let Some(response) = rejection(request, reason) {
+                        response_sender
+                            .send(Response::Textual(response))
+                            .await
+                            .expect("receiver should always wait for all senders");
+                    }
+                    return;
+                }
+            }
+
             let client = Client {
                 id: client_id,
                 response_sender: response_sender.clone(),
             };
             match request {
+                Request::Authentication(AuthenticationRequest::Authenticate { id, token }) => {
+                    let result = session.authenticate(&token);
+                    response_sender
+                        .send(Response::Textual(TextualResponse::Authentication(
+                            AuthenticationResponse::Authenticate { id, result },
+                        )))
+                        .await
+                        .expect("receiver should always wait for all senders");
+                }
                 Request::Outputs(request) => {
                     outputs_sender
                         .send(outputs::Request::ClientRequest(ClientRequest {
@@ -140,6 +169,78 @@ async fn handle_message(
     }
 }
 
+fn rejection(request: Request, reason: Reason) -> Option<TextualResponse> {
+    match request {
+        Request::Authentication(_) => None,
+        Request::Injections(request) => match request {
+            InjectionsRequest::Set { id, .. } => Some(InjectionsResponse::Set {
+                id,
+                result: Err(reason),
+            }),
+            InjectionsRequest::Unset { id, .. } => Some(InjectionsResponse::Unset {
+                id,
+                result: Err(reason),
+            }),
+            InjectionsRequest::UnsetEverything => None,
+        }
+        .map(TextualResponse::Injections),
+        Request::Outputs(request) => match request {
+            OutputsRequest::GetFields { .. } | OutputsRequest::UnsubscribeEverything => None,
+            OutputsRequest::GetNext { id, .. } => Some(TextualOutputsResponse::GetNext {
+                id,
+                result: Err(reason),
+            }),
+            OutputsRequest::Subscribe { id, .. } => Some(TextualOutputsResponse::Subscribe {
+                id,
+                result: Err(reason),
+            }),
+            OutputsRequest::Unsubscribe { id, .. } => Some(TextualOutputsResponse::Unsubscribe {
+                id,
+                result: Err(reason),
+            }),
+        }
+        .map(TextualResponse::Outputs),
+        Request::Parameters(request) => match request {
+            ParametersRequest::GetFields { .. }
+            | ParametersRequest::UnsubscribeEverything
+            | ParametersRequest::GetHistory { .. } => None,
+            ParametersRequest::GetCurrent { id, .. } => Some(ParametersResponse::GetCurrent {
+                id,
+                result: Err(reason),
+            }),
+            ParametersRequest::Subscribe { id, .. } => Some(ParametersResponse::Subscribe {
+                id,
+                result: Err(reason),
+            }),
+            ParametersRequest::Unsubscribe { id, .. } => Some(ParametersResponse::Unsubscribe {
+                id,
+                result: Err(reason),
+            }),
+            ParametersRequest::Update { id, .. } => Some(ParametersResponse::Update {
+                id,
+                result: Err(reason),
+            }),
+            ParametersRequest::LoadFromDisk { id } => Some(ParametersResponse::LoadFromDisk {
+                id,
+                result: Err(reason),
+            }),
+            ParametersRequest::StoreToDisk { id } => Some(ParametersResponse::StoreToDisk {
+                id,
+                result: Err(reason),
+            }),
+            ParametersRequest::RevertTo { id, .. } => Some(ParametersResponse::RevertTo {
+                id,
+                result: Err(reason),
+            }),
+            ParametersRequest::StoreToLayer { id, .. } => Some(ParametersResponse::StoreToLayer {
+                id,
+                result: Err(reason),
+            }),
+        }
+        .map(TextualResponse::Parameters),
+    }
+}
+
 async fn send_error(
     error: ReceiverOrSenderError,
     error_sender: &Sender<ReceiverOrSenderError>,
@@ -25,6 +25,7 @@ use crate::server::outputs::router::router;
 
 use super::{
     acceptor::{acceptor, AcceptError},
+    authentication::{Authentication, AuthenticationError},
     outputs::{provider::provider, Request},
     parameters::{
         directory::{deserialize, DirectoryError},
@@ -45,6 +46,8 @@ pub enum StartError {
     RuntimeNotStarted(#[source] io::Error),
     #[error("initial parameters not parsed")]
     InitialParametersNotParsed(#[source] DirectoryError),
+    #[error("authentication not loaded")]
+    AuthenticationNotLoaded(#[source] AuthenticationError),
 }
 
 pub struct Runtime<Parameters> {
@@ -94,6 +97,16 @@ where
                                 return Err(StartError::InitialParametersNotParsed(source));
                             }
                         };
+                    let authentication =
+                        match Authentication::from_directory(&parameters_directory).await {
+                            Ok(authentication) => Arc::new(authentication),
+                            Err(source) => {
+                                runtime_sender.send(None).ok().expect(
+                                "successful thread creation should always wait for runtime_sender",
+                            );
+                                return Err(StartError::AuthenticationNotLoaded(source));
+                            }
+                        };
 
                     let (outputs_sender, outputs_receiver) = channel(1);
 
@@ -121,6 +134,7 @@ where
                         acceptor(
                             addresses,
                             keep_running.clone(),
+                            authentication,
                             outputs_sender,
                             parameters_sender,
                         )
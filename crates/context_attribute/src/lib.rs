@@ -7,17 +7,20 @@ use syn::{
     punctuated::{Pair, Punctuated},
     spanned::Spanned,
     token::Mut,
-    AngleBracketedGenericArguments, Expr, ExprLit, GenericArgument, GenericParam, ItemStruct,
-    Lifetime, LifetimeDef, Lit, Path, PathArguments, PathSegment, PredicateType, TraitBound,
-    TraitBoundModifier, Type, TypeParam, TypeParamBound, TypePath, TypeReference, WhereClause,
-    WherePredicate,
+    AngleBracketedGenericArguments, AttributeArgs, Expr, ExprLit, GenericArgument, GenericParam,
+    Ident, ItemStruct, Lifetime, LifetimeDef, Lit, Meta, MetaNameValue, NestedMeta, Path,
+    PathArguments, PathSegment, PredicateType, TraitBound, TraitBoundModifier, Type, TypeParam,
+    TypeParamBound, TypePath, TypeReference, WhereClause, WherePredicate,
 };
 
 #[proc_macro_attribute]
 #[proc_macro_error]
-pub fn context(_attributes: TokenStream, input: TokenStream) -> TokenStream {
+pub fn context(attributes: TokenStream, input: TokenStream) -> TokenStream {
+    let attributes = parse_macro_input!(attributes as AttributeArgs);
     let mut struct_item = parse_macro_input!(input as ItemStruct);
 
+    check_schedule_attributes(&attributes, &struct_item);
+
     let mut requires_lifetime_parameter = false;
     let mut requires_hardware_interface_parameter = false;
 
@@ -222,6 +225,39 @@ pub fn context(_attributes: TokenStream, input: TokenStream) -> TokenStream {
     struct_item.into_token_stream().into()
 }
 
+fn check_schedule_attributes(attributes: &[NestedMeta], struct_item: &ItemStruct) {
+    if !attributes.is_empty() && struct_item.ident != "CycleContext" {
+        abort!(
+            struct_item.ident,
+            "only `CycleContext` accepts schedule arguments"
+        );
+    }
+    for attribute in attributes {
+        match attribute {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                if path.is_ident("every") =>
+            {
+                match lit {
+                    Lit::Int(every) if every.base10_parse::<usize>().unwrap_or(0) > 0 => {}
+                    _ => abort!(lit, "expected positive integer literal"),
+                }
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                if path.is_ident("run_if") =>
+            {
+                match lit {
+                    Lit::Str(run_if) if run_if.parse::<Ident>().is_ok() => {}
+                    _ => abort!(lit, "expected string literal containing a function name"),
+                }
+            }
+            _ => abort!(
+                attribute,
+                "expected `every = <n>` or `run_if = \"<function>\"`"
+            ),
+        }
+    }
+}
+
 fn pop_string_argument(arguments: &mut AngleBracketedGenericArguments) {
     match arguments.args.pop() {
         Some(
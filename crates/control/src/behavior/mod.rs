@@ -10,6 +10,7 @@ mod lost_ball;
 pub mod node;
 mod penalize;
 mod prepare_jump;
+mod safe_stand;
 mod search;
 mod sit_down;
 mod stand;
@@ -7,8 +7,8 @@ use nalgebra::{point, Point2};
 use spl_network_messages::{GamePhase, GameState, SubState, Team};
 use types::{
     configuration::{Behavior as BehaviorConfiguration, InWalkKicks, LostBall},
-    Action, CycleTime, FieldDimensions, FilteredGameState, GameControllerState, MotionCommand,
-    PathObstacle, PrimaryState, Role, Side, WorldState,
+    Action, CycleTime, FieldDimensions, FilteredGameState, GameControllerState, Health,
+    MotionCommand, PathObstacle, PrimaryState, Role, Side, WorldState,
 };
 
 use super::{
@@ -16,8 +16,8 @@ use super::{
     defend::Defend,
     dribble, fall_safely,
     head::LookAction,
-    initial, jump, look_around, lost_ball, penalize, prepare_jump, search, sit_down, stand,
-    stand_up, support, unstiff, walk_to_kick_off, walk_to_penalty_kick,
+    initial, jump, look_around, lost_ball, penalize, prepare_jump, safe_stand, search, sit_down,
+    stand, stand_up, support, unstiff, walk_to_kick_off, walk_to_penalty_kick,
     walk_to_pose::{WalkAndStand, WalkPathPlanner},
 };
 
@@ -40,6 +40,7 @@ pub struct CycleContext {
     pub active_action: AdditionalOutput<Action, "active_action">,
 
     pub has_ground_contact: Input<bool, "has_ground_contact">,
+    pub health: Input<Health, "health">,
     pub world_state: Input<WorldState, "world_state">,
     pub cycle_time: Input<CycleTime, "cycle_time">,
 
@@ -97,6 +98,7 @@ impl Behavior {
             Action::Initial,
             Action::FallSafely,
             Action::StandUp,
+            Action::SafeStand,
             Action::Stand,
             Action::Calibrate,
         ];
@@ -184,6 +186,7 @@ impl Behavior {
                         fall_safely::execute(world_state, *context.has_ground_contact)
                     }
                     Action::StandUp => stand_up::execute(world_state),
+                    Action::SafeStand => safe_stand::execute(world_state, context.health),
                     Action::Stand => stand::execute(world_state, context.field_dimensions),
                     Action::LookAround => look_around::execute(world_state),
                     Action::Calibrate => calibrate::execute(world_state),
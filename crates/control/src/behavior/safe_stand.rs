@@ -0,0 +1,13 @@
+use types::{HeadMotion, Health, MotionCommand, PrimaryState, WorldState};
+
+pub fn execute(world_state: &WorldState, health: &Health) -> Option<MotionCommand> {
+    match (world_state.robot.primary_state, health.is_healthy()) {
+        (PrimaryState::Ready | PrimaryState::Set | PrimaryState::Playing, false) => {
+            Some(MotionCommand::Stand {
+                head: HeadMotion::Center,
+                is_energy_saving: false,
+            })
+        }
+        _ => None,
+    }
+}
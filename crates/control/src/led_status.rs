@@ -3,7 +3,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use color_eyre::Result;
 use context_attribute::context;
 use framework::{MainOutput, PerceptionInput};
-use types::{Ball, CycleTime, Eye, FilteredWhistle, Leds, PrimaryState, Rgb};
+use types::{Ball, CycleTime, Eye, FilteredWhistle, Health, Leds, PrimaryState, Rgb};
 
 pub struct LedStatus {
     blink_state: bool,
@@ -15,11 +15,12 @@ pub struct LedStatus {
 #[context]
 pub struct CreationContext {}
 
-#[context]
+#[context(every = 4)]
 pub struct CycleContext {
     pub primary_state: Input<PrimaryState, "primary_state">,
     pub cycle_time: Input<CycleTime, "cycle_time">,
     pub filtered_whistle: Input<FilteredWhistle, "filtered_whistle">,
+    pub health: Input<Health, "health">,
 
     pub balls_bottom: PerceptionInput<Option<Vec<Ball>>, "VisionBottom", "balls?">,
     pub balls_top: PerceptionInput<Option<Vec<Ball>>, "VisionTop", "balls?">,
@@ -156,12 +157,18 @@ impl LedStatus {
         }
         .into();
 
+        let feet = match (context.health.is_healthy(), self.blink_state) {
+            (true, _) => Rgb::GREEN,
+            (false, true) => Rgb::RED,
+            (false, false) => Rgb::BLACK,
+        };
+
         let leds = Leds {
             left_ear: ears,
             right_ear: ears,
             chest,
-            left_foot: Rgb::GREEN,
-            right_foot: Rgb::GREEN,
+            left_foot: feet,
+            right_foot: feet,
             left_eye,
             right_eye,
         };
@@ -29,6 +29,7 @@ pub mod sensor_data_receiver;
 pub mod sole_pressure_filter;
 pub mod sonar_filter;
 pub mod support_foot_estimation;
+pub mod watchdog;
 pub mod wee_sound;
 pub mod whistle_filter;
 pub mod world_state_composer;
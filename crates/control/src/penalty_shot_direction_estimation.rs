@@ -17,7 +17,7 @@ pub struct CreationContext {
         Parameter<f32, "penalty_shot_direction_estimation.moving_distance_threshold">,
 }
 
-#[context]
+#[context(run_if = "is_in_penalty_shootout")]
 pub struct CycleContext {
     pub field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
     pub moving_distance_threshold:
@@ -34,6 +34,15 @@ pub struct MainOutputs {
     pub penalty_shot_direction: MainOutput<Option<PenaltyShotDirection>>,
 }
 
+/// Outside of penalty shootouts the node is skipped and keeps its last output, i.e. the direction
+/// of the last penalty shot remains set once the shootout is over and the game finished
+pub fn is_in_penalty_shootout(context: &CycleContext) -> bool {
+    matches!(
+        context.game_controller_state.game_phase,
+        GamePhase::PenaltyShootout { .. }
+    )
+}
+
 impl PenaltyShotDirectionEstimation {
     pub fn new(_context: CreationContext) -> Result<Self> {
         Ok(Self {
@@ -0,0 +1,174 @@
+use std::time::{Duration, SystemTime};
+
+use color_eyre::Result;
+use context_attribute::context;
+use framework::{MainOutput, PerceptionInput};
+use types::{
+    configuration::Watchdog as WatchdogConfiguration, detected_feet::DetectedFeet, CycleTime,
+    CyclerHealth, Health, HealthFailure, MonitoredCycler, Whistle,
+};
+
+pub struct Watchdog {
+    is_first_cycle: bool,
+    consecutive_overruns: usize,
+    audio: PerceptionCyclerMonitor,
+    vision_top: PerceptionCyclerMonitor,
+    vision_bottom: PerceptionCyclerMonitor,
+}
+
+#[context]
+pub struct CreationContext {}
+
+#[context]
+pub struct CycleContext {
+    pub cycle_time: Input<CycleTime, "cycle_time">,
+
+    pub detected_whistle: PerceptionInput<Whistle, "Audio", "detected_whistle">,
+    pub detected_feet_top: PerceptionInput<DetectedFeet, "VisionTop", "detected_feet">,
+    pub detected_feet_bottom: PerceptionInput<DetectedFeet, "VisionBottom", "detected_feet">,
+
+    pub configuration: Parameter<WatchdogConfiguration, "watchdog">,
+}
+
+#[context]
+#[derive(Default)]
+pub struct MainOutputs {
+    pub health: MainOutput<Health>,
+}
+
+impl Watchdog {
+    pub fn new(_context: CreationContext) -> Result<Self> {
+        Ok(Self {
+            is_first_cycle: true,
+            consecutive_overruns: 0,
+            audio: PerceptionCyclerMonitor::default(),
+            vision_top: PerceptionCyclerMonitor::default(),
+            vision_bottom: PerceptionCyclerMonitor::default(),
+        })
+    }
+
+    pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
+        let now = context.cycle_time.start_time;
+        let mut failures = Vec::new();
+
+        // the first cycle period is measured from the epoch
+        let control_cycle_period = match self.is_first_cycle {
+            true => Duration::ZERO,
+            false => context.cycle_time.last_cycle_duration,
+        };
+        self.is_first_cycle = false;
+        if control_cycle_period > context.configuration.maximum_control_cycle_period {
+            self.consecutive_overruns += 1;
+        } else {
+            self.consecutive_overruns = 0;
+        }
+        if self.consecutive_overruns > context.configuration.maximum_consecutive_overruns {
+            failures.push(HealthFailure::CycleOverrun {
+                cycler: MonitoredCycler::Control,
+                cycle_period: control_cycle_period,
+            });
+        }
+
+        let audio = self
+            .audio
+            .update(now, data_timestamps(&context.detected_whistle));
+        let vision_top = self
+            .vision_top
+            .update(now, data_timestamps(&context.detected_feet_top));
+        let vision_bottom = self
+            .vision_bottom
+            .update(now, data_timestamps(&context.detected_feet_bottom));
+        for (cycler, health) in [
+            (MonitoredCycler::Audio, audio),
+            (MonitoredCycler::VisionTop, vision_top),
+            (MonitoredCycler::VisionBottom, vision_bottom),
+        ] {
+            if health.data_age > context.configuration.maximum_perception_data_age {
+                failures.push(HealthFailure::Starvation {
+                    cycler,
+                    data_age: health.data_age,
+                });
+            }
+        }
+
+        Ok(MainOutputs {
+            health: Health {
+                control: CyclerHealth {
+                    cycle_period: control_cycle_period,
+                    data_age: Duration::ZERO,
+                },
+                audio,
+                vision_top,
+                vision_bottom,
+                failures,
+            }
+            .into(),
+        })
+    }
+}
+
+fn data_timestamps<T>(perception_input: &PerceptionInput<Vec<&T>>) -> Vec<SystemTime> {
+    perception_input
+        .persistent
+        .iter()
+        .filter(|(_, data)| !data.is_empty())
+        .map(|(timestamp, _)| *timestamp)
+        .collect()
+}
+
+#[derive(Default)]
+struct PerceptionCyclerMonitor {
+    /// Start of the monitoring, the data age is measured from here until the cycler delivered data
+    first_update: Option<SystemTime>,
+    last_data: Option<SystemTime>,
+    cycle_period: Duration,
+}
+
+impl PerceptionCyclerMonitor {
+    fn update(&mut self, now: SystemTime, data_timestamps: Vec<SystemTime>) -> CyclerHealth {
+        let first_update = *self.first_update.get_or_insert(now);
+        for timestamp in data_timestamps {
+            match self.last_data {
+                Some(last_data) if timestamp <= last_data => {}
+                Some(last_data) => {
+                    self.cycle_period = timestamp.duration_since(last_data).unwrap_or_default();
+                    self.last_data = Some(timestamp);
+                }
+                None => self.last_data = Some(timestamp),
+            }
+        }
+        CyclerHealth {
+            cycle_period: self.cycle_period,
+            data_age: now
+                .duration_since(self.last_data.unwrap_or(first_update))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_age_grows_until_new_data_arrives() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let mut monitor = PerceptionCyclerMonitor::default();
+
+        let health = monitor.update(start, vec![]);
+        assert_eq!(health.data_age, Duration::ZERO);
+
+        let health = monitor.update(start + Duration::from_millis(500), vec![]);
+        assert_eq!(health.data_age, Duration::from_millis(500));
+
+        let health = monitor.update(
+            start + Duration::from_millis(600),
+            vec![
+                start + Duration::from_millis(520),
+                start + Duration::from_millis(553),
+            ],
+        );
+        assert_eq!(health.data_age, Duration::from_millis(47));
+        assert_eq!(health.cycle_period, Duration::from_millis(33));
+    }
+}
@@ -1,6 +1,7 @@
 use build_script_helpers::write_token_stream;
 use code_generation::{
     cycler::{generate_cyclers, get_cyclers},
+    harness::generate_harnesses,
     run::generate_run,
 };
 use color_eyre::{
@@ -58,12 +59,15 @@ fn main() -> Result<()> {
     let cyclers = get_cyclers(&cycler_instances, &nodes, &cycler_types);
 
     let cyclers_token_stream = generate_cyclers(&cyclers).wrap_err("failed to generate cyclers")?;
+    let harnesses_token_stream =
+        generate_harnesses(&cyclers).wrap_err("failed to generate harnesses")?;
     let runtime_token_stream = generate_run(&cyclers);
 
     write_token_stream(
         "cyclers.rs",
         quote! {
             #cyclers_token_stream
+            #harnesses_token_stream
             #runtime_token_stream
         },
     )
@@ -0,0 +1,33 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use control::CyclerInstance;
+use cyclers::harness::control::{Fixture, WhistleFilter};
+use structs::Configuration;
+
+#[test]
+fn whistle_is_detected_from_audio_fixture() {
+    let mut configuration = Configuration::default();
+    configuration.whistle_filter.buffer_length = 4;
+    configuration.whistle_filter.minimum_detections = 2;
+    let fixture: Fixture =
+        serde_json::from_str(include_str!("fixtures/whistle_filter.json")).unwrap();
+
+    let mut whistle_filter = WhistleFilter::new(CyclerInstance::Control, &configuration).unwrap();
+    let database = whistle_filter.cycle(&configuration, &fixture).unwrap();
+
+    let filtered_whistle = database.main_outputs.filtered_whistle;
+    assert!(filtered_whistle.is_detected);
+    assert!(filtered_whistle.started_this_cycle);
+    assert_eq!(
+        filtered_whistle.last_detection,
+        Some(UNIX_EPOCH + Duration::from_secs(10))
+    );
+
+    let database = whistle_filter
+        .cycle(&configuration, &Fixture::default())
+        .unwrap();
+
+    let filtered_whistle = database.main_outputs.filtered_whistle;
+    assert!(filtered_whistle.is_detected);
+    assert!(!filtered_whistle.started_this_cycle);
+}
@@ -25,7 +25,7 @@ fn main() -> Result<()> {
                 let module_name_identifier = format_ident!("{}", module_name);
                 Some(quote! { pub #field_name_identifier: Update<structs::#module_name_identifier::MainOutputs> })
             },
-            CyclerType::RealTime => None,
+            CyclerType::RealTime | CyclerType::Periodic => None,
         }
     });
     let timestamp_array_items = cycler_instances
@@ -37,7 +37,7 @@ fn main() -> Result<()> {
                     let field_name_identifier = format_ident!("{}", instance_name.to_case(Case::Snake));
                     Some(quote! { self.#field_name_identifier.first_timestamp_of_non_finalized_database })
                 },
-                CyclerType::RealTime => None,
+                CyclerType::RealTime | CyclerType::Periodic => None,
             }
         });
     let push_loops =
@@ -59,7 +59,7 @@ fn main() -> Result<()> {
                             }
                         })
                     }
-                    CyclerType::RealTime => None,
+                    CyclerType::RealTime | CyclerType::Periodic => None,
                 }
             });
     let databases_fields = cycler_instances.instances_to_modules.iter().filter_map(|(instance_name, module_name)| {
@@ -69,7 +69,7 @@ fn main() -> Result<()> {
                 let module_name_identifier = format_ident!("{}", module_name);
                 Some(quote! { pub #field_name_identifier: Vec<structs::#module_name_identifier::MainOutputs> })
             },
-            CyclerType::RealTime => None,
+            CyclerType::RealTime | CyclerType::Periodic => None,
         }
     });
 
@@ -2,16 +2,23 @@ mod additional_output;
 mod future_queue;
 mod historic_databases;
 mod historic_input;
+mod lockstep_schedule;
 mod main_output;
 mod multiple_buffer;
 mod perception_databases;
 mod perception_input;
+mod recording;
 
 pub use additional_output::{should_be_filled, AdditionalOutput};
 pub use future_queue::{future_queue, Consumer, Item, Producer};
 pub use historic_databases::HistoricDatabases;
 pub use historic_input::HistoricInput;
+pub use lockstep_schedule::LockstepSchedule;
 pub use main_output::MainOutput;
 pub use multiple_buffer::{multiple_buffer_with_slots, Reader, ReaderGuard, Writer, WriterGuard};
 pub use perception_databases::{Databases, PerceptionDatabases, Update, Updates};
 pub use perception_input::PerceptionInput;
+pub use recording::{
+    decode_database, encode_database, ChunkIndexEntry, Frame, RecordingFrame, RecordingReader,
+    RecordingWriter, RECORDING_FORMAT_VERSION,
+};
@@ -0,0 +1,88 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+pub struct LockstepSchedule<CyclerInstance> {
+    random_number_generator: StdRng,
+    real_time_cycler_instance: CyclerInstance,
+    perception_cycler_instances_with_intervals: Vec<(CyclerInstance, usize)>,
+    round: usize,
+}
+
+impl<CyclerInstance> LockstepSchedule<CyclerInstance>
+where
+    CyclerInstance: Copy,
+{
+    pub fn new(
+        seed: u64,
+        real_time_cycler_instance: CyclerInstance,
+        perception_cycler_instances_with_intervals: Vec<(CyclerInstance, usize)>,
+    ) -> Self {
+        Self {
+            random_number_generator: StdRng::seed_from_u64(seed),
+            real_time_cycler_instance,
+            perception_cycler_instances_with_intervals,
+            round: 0,
+        }
+    }
+
+    pub fn next_round(&mut self) -> Vec<CyclerInstance> {
+        let mut cycler_instances: Vec<_> = self
+            .perception_cycler_instances_with_intervals
+            .iter()
+            .filter(|(_, interval)| self.round % (*interval).max(1) == 0)
+            .map(|(cycler_instance, _)| *cycler_instance)
+            .chain([self.real_time_cycler_instance])
+            .collect();
+        cycler_instances.shuffle(&mut self.random_number_generator);
+        self.round += 1;
+        cycler_instances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum CyclerInstance {
+        Control,
+        VisionTop,
+        Audio,
+    }
+
+    fn schedule(seed: u64) -> LockstepSchedule<CyclerInstance> {
+        LockstepSchedule::new(
+            seed,
+            CyclerInstance::Control,
+            vec![(CyclerInstance::VisionTop, 3), (CyclerInstance::Audio, 1)],
+        )
+    }
+
+    #[test]
+    fn same_seed_results_in_same_rounds() {
+        let mut schedule_a = schedule(42);
+        let mut schedule_b = schedule(42);
+        for _ in 0..100 {
+            assert_eq!(schedule_a.next_round(), schedule_b.next_round());
+        }
+    }
+
+    #[test]
+    fn cycler_instances_are_scheduled_according_to_their_intervals() {
+        let mut schedule = schedule(1337);
+        for round in 0..30 {
+            let cycler_instances = schedule.next_round();
+            let count = |cycler_instance| {
+                cycler_instances
+                    .iter()
+                    .filter(|scheduled| **scheduled == cycler_instance)
+                    .count()
+            };
+            assert_eq!(count(CyclerInstance::Control), 1);
+            assert_eq!(count(CyclerInstance::Audio), 1);
+            assert_eq!(
+                count(CyclerInstance::VisionTop),
+                if round % 3 == 0 { 1 } else { 0 }
+            );
+        }
+    }
+}
@@ -0,0 +1,484 @@
+use std::{
+    io::{self, ErrorKind, Read, Seek, SeekFrom, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+const FILE_MAGIC: [u8; 8] = *b"HULKRCRD";
+/// Increased whenever the file format or the encoding of frame data changes incompatibly
+pub const RECORDING_FORMAT_VERSION: u64 = 1;
+const FILE_HEADER_SIZE: u64 = 16;
+const CHUNK_MAGIC: [u8; 8] = *b"HULKCHNK";
+const CHUNK_HEADER_SIZE: u64 = 40;
+
+/// Encodes a database as frame data, fields are stored by name (MessagePack with struct maps)
+pub fn encode_database(database: &impl Serialize) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    let mut data = Vec::new();
+    database.serialize(&mut rmp_serde::Serializer::new(&mut data).with_struct_map())?;
+    Ok(data)
+}
+
+/// Decodes frame data into any type whose fields are a subset of the recorded ones
+pub fn decode_database<Database>(data: &[u8]) -> Result<Database, rmp_serde::decode::Error>
+where
+    Database: DeserializeOwned,
+{
+    rmp_serde::from_slice(data)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub timestamp: SystemTime,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RecordingFrame {
+    pub cycler_instance: String,
+    pub frame: Frame,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkIndexEntry {
+    pub offset: u64,
+    pub payload_size: u64,
+    pub amount_of_frames: u64,
+    pub first_timestamp: SystemTime,
+    pub last_timestamp: SystemTime,
+}
+
+pub struct RecordingWriter<Writer>
+where
+    Writer: Write,
+{
+    writer: Writer,
+    maximum_chunk_size: usize,
+    maximum_chunk_duration: Duration,
+    chunk: Vec<u8>,
+    amount_of_frames_in_chunk: u64,
+    first_timestamp_in_chunk: Option<SystemTime>,
+    last_timestamp_in_chunk: Option<SystemTime>,
+}
+
+impl<Writer> RecordingWriter<Writer>
+where
+    Writer: Write,
+{
+    pub fn new(
+        mut writer: Writer,
+        maximum_chunk_size: usize,
+        maximum_chunk_duration: Duration,
+    ) -> io::Result<Self> {
+        writer.write_all(&FILE_MAGIC)?;
+        writer.write_all(&RECORDING_FORMAT_VERSION.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            maximum_chunk_size,
+            maximum_chunk_duration,
+            chunk: Vec::new(),
+            amount_of_frames_in_chunk: 0,
+            first_timestamp_in_chunk: None,
+            last_timestamp_in_chunk: None,
+        })
+    }
+
+    pub fn write_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        self.chunk
+            .extend_from_slice(&timestamp_to_nanoseconds(frame.timestamp)?.to_le_bytes());
+        self.chunk
+            .extend_from_slice(&(frame.data.len() as u64).to_le_bytes());
+        self.chunk.extend_from_slice(&frame.data);
+        self.amount_of_frames_in_chunk += 1;
+        let first_timestamp = *self.first_timestamp_in_chunk.get_or_insert(frame.timestamp);
+        self.last_timestamp_in_chunk = Some(frame.timestamp);
+        let chunk_duration = frame
+            .timestamp
+            .duration_since(first_timestamp)
+            .unwrap_or_default();
+        if self.chunk.len() >= self.maximum_chunk_size
+            || chunk_duration >= self.maximum_chunk_duration
+        {
+            self.flush_chunk()?;
+        }
+        Ok(())
+    }
+
+    pub fn flush_chunk(&mut self) -> io::Result<()> {
+        let (first_timestamp, last_timestamp) =
+            match (self.first_timestamp_in_chunk, self.last_timestamp_in_chunk) {
+                (Some(first_timestamp), Some(last_timestamp)) => (first_timestamp, last_timestamp),
+                _ => return Ok(()),
+            };
+        self.writer.write_all(&CHUNK_MAGIC)?;
+        self.writer
+            .write_all(&(self.chunk.len() as u64).to_le_bytes())?;
+        self.writer
+            .write_all(&self.amount_of_frames_in_chunk.to_le_bytes())?;
+        self.writer
+            .write_all(&timestamp_to_nanoseconds(first_timestamp)?.to_le_bytes())?;
+        self.writer
+            .write_all(&timestamp_to_nanoseconds(last_timestamp)?.to_le_bytes())?;
+        self.writer.write_all(&self.chunk)?;
+        self.writer.flush()?;
+        self.chunk.clear();
+        self.amount_of_frames_in_chunk = 0;
+        self.first_timestamp_in_chunk = None;
+        self.last_timestamp_in_chunk = None;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<Writer> {
+        self.flush_chunk()?;
+        Ok(self.writer)
+    }
+}
+
+pub struct RecordingReader<Reader>
+where
+    Reader: Read + Seek,
+{
+    reader: Reader,
+    index: Vec<ChunkIndexEntry>,
+}
+
+impl<Reader> RecordingReader<Reader>
+where
+    Reader: Read + Seek,
+{
+    pub fn new(mut reader: Reader) -> io::Result<Self> {
+        let length = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != FILE_MAGIC {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "unexpected file magic, not a recording",
+            ));
+        }
+        let version = read_u64(&mut reader)?;
+        if version != RECORDING_FORMAT_VERSION {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "unsupported recording format version {version}, expected {RECORDING_FORMAT_VERSION}"
+                ),
+            ));
+        }
+        let mut index = Vec::new();
+        let mut offset = FILE_HEADER_SIZE;
+        while offset + CHUNK_HEADER_SIZE <= length {
+            let mut magic = [0; 8];
+            reader.read_exact(&mut magic)?;
+            if magic != CHUNK_MAGIC {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unexpected chunk magic at offset {offset}"),
+                ));
+            }
+            let payload_size = read_u64(&mut reader)?;
+            let amount_of_frames = read_u64(&mut reader)?;
+            let first_timestamp = nanoseconds_to_timestamp(read_u64(&mut reader)?);
+            let last_timestamp = nanoseconds_to_timestamp(read_u64(&mut reader)?);
+            let payload_offset = offset + CHUNK_HEADER_SIZE;
+            let payload_end = payload_offset.checked_add(payload_size).ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid payload size {payload_size} of chunk at offset {offset}"),
+                )
+            })?;
+            if payload_end > length {
+                // the last chunk was not written completely, e.g. due to a power loss
+                break;
+            }
+            index.push(ChunkIndexEntry {
+                offset: payload_offset,
+                payload_size,
+                amount_of_frames,
+                first_timestamp,
+                last_timestamp,
+            });
+            offset = reader.seek(SeekFrom::Start(payload_end))?;
+        }
+        Ok(Self { reader, index })
+    }
+
+    pub fn index(&self) -> &[ChunkIndexEntry] {
+        &self.index
+    }
+
+    pub fn amount_of_frames(&self) -> u64 {
+        self.index.iter().map(|entry| entry.amount_of_frames).sum()
+    }
+
+    pub fn read_chunk(&mut self, chunk_index: usize) -> io::Result<Vec<Frame>> {
+        let entry = *self.index.get(chunk_index).ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("chunk {chunk_index} does not exist"),
+            )
+        })?;
+        self.reader.seek(SeekFrom::Start(entry.offset))?;
+        let mut payload = vec![0; entry.payload_size as usize];
+        self.reader.read_exact(&mut payload)?;
+        let mut payload = payload.as_slice();
+        (0..entry.amount_of_frames)
+            .map(|_| {
+                let timestamp = nanoseconds_to_timestamp(read_u64(&mut payload)?);
+                let mut data = vec![0; read_u64(&mut payload)? as usize];
+                payload.read_exact(&mut data)?;
+                Ok(Frame { timestamp, data })
+            })
+            .collect()
+    }
+
+    pub fn find_chunk(&self, timestamp: SystemTime) -> Option<usize> {
+        let chunk_index = self
+            .index
+            .partition_point(|entry| entry.last_timestamp < timestamp);
+        (chunk_index < self.index.len()).then_some(chunk_index)
+    }
+
+    pub fn read_frame_at(&mut self, timestamp: SystemTime) -> io::Result<Option<Frame>> {
+        let chunk_index = match self.find_chunk(timestamp) {
+            Some(chunk_index) => chunk_index,
+            None => return Ok(None),
+        };
+        Ok(self
+            .read_chunk(chunk_index)?
+            .into_iter()
+            .find(|frame| frame.timestamp >= timestamp))
+    }
+
+    pub fn find_latest_chunk(&self, timestamp: SystemTime) -> Option<usize> {
+        self.index
+            .partition_point(|entry| entry.first_timestamp <= timestamp)
+            .checked_sub(1)
+    }
+
+    pub fn read_latest_frame(&mut self, timestamp: SystemTime) -> io::Result<Option<Frame>> {
+        let chunk_index = match self.find_latest_chunk(timestamp) {
+            Some(chunk_index) => chunk_index,
+            None => return Ok(None),
+        };
+        Ok(self
+            .read_chunk(chunk_index)?
+            .into_iter()
+            .take_while(|frame| frame.timestamp <= timestamp)
+            .last())
+    }
+
+    pub fn into_inner(self) -> Reader {
+        self.reader
+    }
+}
+
+fn timestamp_to_nanoseconds(timestamp: SystemTime) -> io::Result<u64> {
+    timestamp
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .map_err(|error| io::Error::new(ErrorKind::InvalidInput, error))
+}
+
+fn nanoseconds_to_timestamp(nanoseconds: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_nanos(nanoseconds)
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn frame(seconds: u64, data: &[u8]) -> Frame {
+        Frame {
+            timestamp: UNIX_EPOCH + Duration::from_secs(seconds),
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn written_frames_are_read_back_chunk_by_chunk() {
+        let frames = [
+            frame(1, &[1, 2, 3]),
+            frame(2, &[4, 5]),
+            frame(3, &[]),
+            frame(4, &[6, 7, 8, 9]),
+        ];
+        let mut writer = RecordingWriter::new(Cursor::new(Vec::new()), 20, Duration::MAX).unwrap();
+        for frame in frames.iter() {
+            writer.write_frame(frame).unwrap();
+        }
+        let buffer = writer.finish().unwrap();
+
+        let mut reader = RecordingReader::new(buffer).unwrap();
+        assert_eq!(reader.amount_of_frames(), 4);
+        assert!(reader.index().len() > 1);
+        let read_frames: Vec<_> = (0..reader.index().len())
+            .flat_map(|chunk_index| reader.read_chunk(chunk_index).unwrap())
+            .collect();
+        assert_eq!(read_frames, frames);
+    }
+
+    #[test]
+    fn frames_are_found_by_timestamp() {
+        let mut writer = RecordingWriter::new(Cursor::new(Vec::new()), 1, Duration::MAX).unwrap();
+        for seconds in 1..=5 {
+            writer
+                .write_frame(&frame(seconds * 2, &[seconds as u8]))
+                .unwrap();
+        }
+        let mut reader = RecordingReader::new(writer.finish().unwrap()).unwrap();
+
+        assert_eq!(
+            reader
+                .read_frame_at(UNIX_EPOCH + Duration::from_secs(5))
+                .unwrap(),
+            Some(frame(6, &[3]))
+        );
+        assert_eq!(
+            reader
+                .read_frame_at(UNIX_EPOCH + Duration::from_secs(11))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn latest_frames_are_found_by_timestamp() {
+        let mut writer = RecordingWriter::new(Cursor::new(Vec::new()), 40, Duration::MAX).unwrap();
+        for seconds in 1..=5 {
+            writer
+                .write_frame(&frame(seconds * 2, &[seconds as u8]))
+                .unwrap();
+        }
+        let mut reader = RecordingReader::new(writer.finish().unwrap()).unwrap();
+
+        assert_eq!(
+            reader
+                .read_latest_frame(UNIX_EPOCH + Duration::from_secs(1))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            reader
+                .read_latest_frame(UNIX_EPOCH + Duration::from_secs(7))
+                .unwrap(),
+            Some(frame(6, &[3]))
+        );
+        assert_eq!(
+            reader
+                .read_latest_frame(UNIX_EPOCH + Duration::from_secs(8))
+                .unwrap(),
+            Some(frame(8, &[4]))
+        );
+        assert_eq!(
+            reader
+                .read_latest_frame(UNIX_EPOCH + Duration::from_secs(20))
+                .unwrap(),
+            Some(frame(10, &[5]))
+        );
+    }
+
+    #[test]
+    fn chunks_are_written_after_maximum_duration() {
+        let mut writer =
+            RecordingWriter::new(Cursor::new(Vec::new()), usize::MAX, Duration::from_secs(2))
+                .unwrap();
+        for seconds in 1..=5 {
+            writer
+                .write_frame(&frame(seconds, &[seconds as u8]))
+                .unwrap();
+        }
+        let reader = RecordingReader::new(writer.finish().unwrap()).unwrap();
+
+        let amounts_of_frames: Vec<_> = reader
+            .index()
+            .iter()
+            .map(|entry| entry.amount_of_frames)
+            .collect();
+        assert_eq!(amounts_of_frames, [3, 2]);
+    }
+
+    #[test]
+    fn truncated_last_chunk_is_ignored() {
+        let mut writer = RecordingWriter::new(Cursor::new(Vec::new()), 1, Duration::MAX).unwrap();
+        writer.write_frame(&frame(1, &[1, 2, 3])).unwrap();
+        writer.write_frame(&frame(2, &[4, 5, 6])).unwrap();
+        let mut buffer = writer.finish().unwrap().into_inner();
+        buffer.truncate(buffer.len() - 2);
+
+        let mut reader = RecordingReader::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(reader.index().len(), 1);
+        assert_eq!(reader.read_chunk(0).unwrap(), vec![frame(1, &[1, 2, 3])]);
+    }
+
+    #[test]
+    fn chunks_with_corrupt_payload_sizes_are_rejected() {
+        let mut writer = RecordingWriter::new(Cursor::new(Vec::new()), 1, Duration::MAX).unwrap();
+        writer.write_frame(&frame(1, &[1, 2, 3])).unwrap();
+        let mut buffer = writer.finish().unwrap().into_inner();
+        let payload_size_offset = FILE_HEADER_SIZE as usize + CHUNK_MAGIC.len();
+        buffer[payload_size_offset..payload_size_offset + 8]
+            .copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let error = RecordingReader::new(Cursor::new(buffer)).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn recordings_of_other_format_versions_are_rejected() {
+        let mut buffer = RecordingWriter::new(Cursor::new(Vec::new()), 1, Duration::MAX)
+            .unwrap()
+            .finish()
+            .unwrap()
+            .into_inner();
+        buffer[8..16].copy_from_slice(&(RECORDING_FORMAT_VERSION + 1).to_le_bytes());
+
+        let error = RecordingReader::new(Cursor::new(buffer)).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn databases_are_decoded_into_a_subset_of_their_fields() {
+        #[derive(Serialize)]
+        struct RecordedMainOutputs {
+            sensor_data: u32,
+            removed_output: Vec<f32>,
+        }
+        #[derive(Serialize)]
+        struct RecordedDatabase {
+            main_outputs: RecordedMainOutputs,
+        }
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct MainOutputs {
+            sensor_data: u32,
+        }
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Database {
+            main_outputs: MainOutputs,
+        }
+
+        let data = encode_database(&RecordedDatabase {
+            main_outputs: RecordedMainOutputs {
+                sensor_data: 42,
+                removed_output: vec![1.0, 2.0],
+            },
+        })
+        .unwrap();
+
+        assert_eq!(
+            decode_database::<Database>(&data).unwrap(),
+            Database {
+                main_outputs: MainOutputs { sensor_data: 42 }
+            }
+        );
+    }
+}
@@ -1,11 +1,11 @@
-use std::{fs::File, sync::Arc};
+use std::{fs::File, path::Path, sync::Arc};
 
 use color_eyre::{
-    eyre::{Result, WrapErr},
+    eyre::{eyre, Result, WrapErr},
     install,
 };
 use cyclers::run;
-use hulk::{nao, setup_logger};
+use hulk::{nao, recorder::start_recorder, setup_logger};
 use serde_json::from_reader;
 use tokio_util::sync::CancellationToken;
 use types::hardware::Interface;
@@ -26,12 +26,22 @@ fn main() -> Result<()> {
     let hardware_interface = nao::Interface::new(keep_running.clone(), hardware_parameters)
         .wrap_err("failed to create hardware interface")?;
     let ids = hardware_interface.get_ids();
-    run(
+    let recording_directory =
+        Path::new("logs").join(chrono::Local::now().format("%Y-%m-%d_%H-%M-%S").to_string());
+    let (recording_sender, recorder) =
+        start_recorder(recording_directory, &ids).wrap_err("failed to start recorder")?;
+    let result = run(
         Arc::new(hardware_interface),
         Some("[::]:1337"),
         "etc/configuration",
         ids.body_id,
         ids.head_id,
+        Some(recording_sender),
         keep_running,
-    )
+    );
+    recorder
+        .join()
+        .map_err(|_| eyre!("recorder thread panicked"))?
+        .wrap_err("failed to record")?;
+    result
 }
@@ -0,0 +1,100 @@
+use std::{path::PathBuf, sync::Arc};
+
+use clap::Parser;
+use color_eyre::{
+    eyre::{eyre, Result, WrapErr},
+    install,
+};
+use cyclers::{run, Lockstep};
+use hulk::{
+    recorder::start_recorder,
+    replayer::{self, Playback},
+    setup_logger,
+};
+use tokio_util::sync::CancellationToken;
+use types::hardware::Interface;
+
+#[derive(Parser)]
+struct Arguments {
+    #[arg(short, long, default_value = "[::]:1337")]
+    listen_address: String,
+    #[arg(short, long, default_value = "etc/configuration")]
+    parameters_directory: PathBuf,
+    /// Run all cyclers on a single thread in a reproducible order instead of in real time
+    #[arg(long)]
+    lockstep: bool,
+    /// Seed of the cycler order in lockstep replays
+    #[arg(long, default_value_t = 0, requires = "lockstep")]
+    seed: u64,
+    /// Record the databases of the replayed cyclers into this directory
+    #[arg(long)]
+    record: Option<PathBuf>,
+    recording_directory: PathBuf,
+}
+
+fn main() -> Result<()> {
+    setup_logger(true)?;
+    install()?;
+    let arguments = Arguments::parse();
+    let keep_running = CancellationToken::new();
+    ctrlc::set_handler({
+        let keep_running = keep_running.clone();
+        move || {
+            keep_running.cancel();
+        }
+    })?;
+    let playback = match arguments.lockstep {
+        true => Playback::Lockstep,
+        false => Playback::RealTime,
+    };
+    let hardware_interface = Arc::new(
+        replayer::Interface::new(
+            keep_running.clone(),
+            arguments.recording_directory,
+            playback,
+        )
+        .wrap_err("failed to create hardware interface")?,
+    );
+    let ids = hardware_interface.get_ids();
+    let (recording_sender, recorder) = match &arguments.record {
+        Some(recording_directory) => {
+            let (recording_sender, recorder) =
+                start_recorder(recording_directory, &ids).wrap_err("failed to start recorder")?;
+            (Some(recording_sender), Some(recorder))
+        }
+        None => (None, None),
+    };
+    let result = match arguments.lockstep {
+        true => {
+            let schedule = hardware_interface.lockstep_schedule(arguments.seed)?;
+            let lockstep = Lockstep::new(
+                hardware_interface.clone(),
+                Some(arguments.listen_address),
+                arguments.parameters_directory,
+                ids.body_id,
+                ids.head_id,
+                recording_sender,
+                keep_running.clone(),
+            )?;
+            lockstep.run(schedule, keep_running, |cycler_instance| {
+                hardware_interface.begin_lockstep_cycle(cycler_instance)
+            })
+        }
+        false => run(
+            hardware_interface,
+            Some(arguments.listen_address),
+            arguments.parameters_directory,
+            ids.body_id,
+            ids.head_id,
+            recording_sender,
+            keep_running,
+        ),
+    };
+    if let Some(recorder) = recorder {
+        recorder
+            .join()
+            .map_err(|_| eyre!("recorder thread panicked"))?
+            .wrap_err("failed to record")?;
+    }
+    result
+}
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use color_eyre::{eyre::Result, install};
+use hulk::{replayer::timeline_server, setup_logger};
+use tokio_util::sync::CancellationToken;
+
+#[derive(Parser)]
+struct Arguments {
+    #[arg(short, long, default_value = "[::]:1337")]
+    listen_address: String,
+    #[arg(short, long, default_value = "etc/timeline")]
+    parameters_directory: PathBuf,
+    recording_directory: PathBuf,
+}
+
+fn main() -> Result<()> {
+    setup_logger(true)?;
+    install()?;
+    let arguments = Arguments::parse();
+    let keep_running = CancellationToken::new();
+    ctrlc::set_handler({
+        let keep_running = keep_running.clone();
+        move || {
+            keep_running.cancel();
+        }
+    })?;
+    timeline_server::run(
+        Some(arguments.listen_address),
+        arguments.parameters_directory,
+        arguments.recording_directory,
+        keep_running,
+    )
+}
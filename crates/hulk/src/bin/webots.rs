@@ -32,6 +32,7 @@ fn main() -> Result<()> {
         "etc/configuration",
         ids.body_id,
         ids.head_id,
+        None,
         keep_running,
     )
 }
@@ -4,6 +4,9 @@ use color_eyre::eyre::Result;
 
 #[cfg(feature = "nao")]
 pub mod nao;
+pub mod recorder;
+#[cfg(feature = "replayer")]
+pub mod replayer;
 #[cfg(feature = "webots")]
 pub mod webots;
 
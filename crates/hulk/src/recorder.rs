@@ -0,0 +1,123 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs::{create_dir_all, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::mpsc::{sync_channel, Receiver, RecvTimeoutError, SyncSender},
+    thread::{Builder, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use framework::{RecordingFrame, RecordingWriter};
+use serde_json::to_writer_pretty;
+use types::hardware::Ids;
+
+const AMOUNT_OF_BUFFERED_FRAMES: usize = 64;
+const MAXIMUM_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+const MAXIMUM_CHUNK_DURATION: Duration = Duration::from_secs(1);
+
+pub fn start_recorder(
+    recording_directory: impl AsRef<Path>,
+    ids: &Ids,
+) -> Result<(SyncSender<RecordingFrame>, JoinHandle<Result<()>>)> {
+    let recording_directory = recording_directory.as_ref().to_path_buf();
+    let ids = ids.clone();
+    let (sender, receiver) = sync_channel(AMOUNT_OF_BUFFERED_FRAMES);
+    let handle = Builder::new()
+        .name("recorder".to_string())
+        .spawn(move || record(&recording_directory, &ids, receiver))
+        .wrap_err("failed to spawn thread for recorder")?;
+    Ok((sender, handle))
+}
+
+pub fn ids_file_path(recording_directory: impl AsRef<Path>) -> PathBuf {
+    recording_directory.as_ref().join("ids.json")
+}
+
+pub fn recording_file_path(
+    recording_directory: impl AsRef<Path>,
+    cycler_instance: &str,
+) -> PathBuf {
+    recording_directory
+        .as_ref()
+        .join(format!("{cycler_instance}.recording"))
+}
+
+fn create_recording_directory(recording_directory: &Path, ids: &Ids) -> Result<()> {
+    create_dir_all(recording_directory).wrap_err_with(|| {
+        format!("failed to create recording directory {recording_directory:?}")
+    })?;
+    let ids_file_path = ids_file_path(recording_directory);
+    let file = File::create(&ids_file_path)
+        .wrap_err_with(|| format!("failed to create {ids_file_path:?}"))?;
+    to_writer_pretty(file, ids).wrap_err("failed to write ids")
+}
+
+fn record(recording_directory: &Path, ids: &Ids, receiver: Receiver<RecordingFrame>) -> Result<()> {
+    let mut writers = HashMap::new();
+    let mut next_flush = Instant::now() + MAXIMUM_CHUNK_DURATION;
+    loop {
+        match receiver.recv_timeout(next_flush.saturating_duration_since(Instant::now())) {
+            Ok(recording_frame) => {
+                write_frame(recording_directory, ids, &mut writers, recording_frame)?
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if Instant::now() >= next_flush {
+            // chunks of cyclers which stopped sending frames are written nevertheless
+            flush_chunks(&mut writers)?;
+            next_flush = Instant::now() + MAXIMUM_CHUNK_DURATION;
+        }
+    }
+    for (cycler_instance, writer) in writers {
+        writer
+            .finish()
+            .wrap_err_with(|| format!("failed to finish recording of `{cycler_instance}`"))?;
+    }
+    Ok(())
+}
+
+fn write_frame(
+    recording_directory: &Path,
+    ids: &Ids,
+    writers: &mut HashMap<String, RecordingWriter<BufWriter<File>>>,
+    RecordingFrame {
+        cycler_instance,
+        frame,
+    }: RecordingFrame,
+) -> Result<()> {
+    // cyclers only send frames while their recording is enabled
+    if writers.is_empty() {
+        create_recording_directory(recording_directory, ids)?;
+    }
+    let writer = match writers.entry(cycler_instance) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => {
+            let file_path = recording_file_path(recording_directory, entry.key());
+            let file = File::create(&file_path)
+                .wrap_err_with(|| format!("failed to create recording file {file_path:?}"))?;
+            entry.insert(
+                RecordingWriter::new(
+                    BufWriter::new(file),
+                    MAXIMUM_CHUNK_SIZE,
+                    MAXIMUM_CHUNK_DURATION,
+                )
+                .wrap_err_with(|| format!("failed to write header to {file_path:?}"))?,
+            )
+        }
+    };
+    writer
+        .write_frame(&frame)
+        .wrap_err("failed to write frame to recording")
+}
+
+fn flush_chunks(writers: &mut HashMap<String, RecordingWriter<BufWriter<File>>>) -> Result<()> {
+    for (cycler_instance, writer) in writers.iter_mut() {
+        writer
+            .flush_chunk()
+            .wrap_err_with(|| format!("failed to write chunk of `{cycler_instance}`"))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,331 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    path::Path,
+    thread::{self, sleep, ThreadId},
+    time::{Duration, Instant, SystemTime},
+};
+
+use color_eyre::{
+    eyre::{bail, eyre, WrapErr},
+    Result,
+};
+use cyclers::CyclerInstance;
+use framework::{decode_database, LockstepSchedule};
+use log::info;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::from_reader;
+use tokio_util::sync::CancellationToken;
+use types::{
+    hardware::{self, Ids},
+    messages::{IncomingMessage, OutgoingMessage},
+    samples::Samples,
+    ycbcr422_image::YCbCr422Image,
+    CameraPosition, Joints, Leds, SensorData,
+};
+
+use crate::recorder::{ids_file_path, recording_file_path};
+
+use super::recording_stream::RecordingStream;
+
+const MAXIMUM_SLEEP_DURATION: Duration = Duration::from_millis(10);
+
+/// Only the main outputs returned by the hardware interface are read from recorded databases, all
+/// other outputs are recomputed by the replayed nodes and may have changed since recording
+#[derive(Deserialize)]
+struct RecordedDatabase<MainOutputs> {
+    main_outputs: MainOutputs,
+}
+
+#[derive(Deserialize)]
+struct RecordedControlOutputs {
+    sensor_data: SensorData,
+}
+
+#[derive(Deserialize)]
+struct RecordedVisionOutputs {
+    image: YCbCr422Image,
+}
+
+#[derive(Deserialize)]
+struct RecordedAudioOutputs {
+    samples: Samples,
+}
+
+#[derive(Deserialize)]
+struct RecordedSplNetworkOutputs {
+    message: IncomingMessage,
+}
+
+struct Stream {
+    cycler_instance: CyclerInstance,
+    recording: Mutex<Option<RecordingStream>>,
+}
+
+impl Stream {
+    fn open(recording_directory: &Path, cycler_instance: CyclerInstance) -> Result<Self> {
+        let file_path = recording_file_path(recording_directory, &format!("{cycler_instance:?}"));
+        let recording = match file_path.exists() {
+            true => {
+                Some(RecordingStream::open(&file_path).wrap_err_with(|| {
+                    format!("failed to open recording of `{cycler_instance:?}`")
+                })?)
+            }
+            false => {
+                info!("No recording of `{cycler_instance:?}` found, its cycler will not be fed");
+                None
+            }
+        };
+        Ok(Self {
+            cycler_instance,
+            recording: Mutex::new(recording),
+        })
+    }
+
+    fn amount_of_frames(&self) -> usize {
+        self.recording
+            .lock()
+            .as_ref()
+            .map_or(0, |recording| recording.amount_of_frames())
+    }
+
+    fn peek_timestamp(&self) -> Result<Option<SystemTime>> {
+        match self.recording.lock().as_mut() {
+            Some(recording) => recording.peek_timestamp(),
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Playback {
+    /// Frames are fed at the pace they were recorded
+    RealTime,
+    /// Frames are fed as fast as the cyclers request them, cyclers are run by a `LockstepSchedule`
+    Lockstep,
+}
+
+pub struct Interface {
+    ids: Ids,
+    keep_running: CancellationToken,
+    playback: Playback,
+    recording_start: SystemTime,
+    replay_start: Instant,
+    initial_now: SystemTime,
+    /// Timestamp of the last frame read for each cycler instance
+    now: Mutex<HashMap<CyclerInstance, SystemTime>>,
+    /// Cycler instance reading on each thread, only used in real-time playback
+    cycler_instances_of_threads: Mutex<HashMap<ThreadId, CyclerInstance>>,
+    /// Cycler instance currently cycled, only used in lockstep playback where all cyclers share
+    /// one thread
+    lockstep_cycler_instance: Mutex<Option<CyclerInstance>>,
+    control: Stream,
+    vision_top: Stream,
+    vision_bottom: Stream,
+    audio: Stream,
+    spl_network: Stream,
+}
+
+impl Interface {
+    pub fn new(
+        keep_running: CancellationToken,
+        recording_directory: impl AsRef<Path>,
+        playback: Playback,
+    ) -> Result<Self> {
+        let recording_directory = recording_directory.as_ref();
+        let ids_file_path = ids_file_path(recording_directory);
+        let file = File::open(&ids_file_path)
+            .wrap_err_with(|| format!("failed to open {ids_file_path:?}"))?;
+        let ids = from_reader(file).wrap_err("failed to parse recorded ids")?;
+
+        let control = Stream::open(recording_directory, CyclerInstance::Control)?;
+        let vision_top = Stream::open(recording_directory, CyclerInstance::VisionTop)?;
+        let vision_bottom = Stream::open(recording_directory, CyclerInstance::VisionBottom)?;
+        let audio = Stream::open(recording_directory, CyclerInstance::Audio)?;
+        let spl_network = Stream::open(recording_directory, CyclerInstance::SplNetwork)?;
+
+        let mut first_timestamps = Vec::new();
+        for stream in [&control, &vision_top, &vision_bottom, &audio, &spl_network] {
+            first_timestamps.extend(stream.peek_timestamp()?);
+        }
+        let recording_start = first_timestamps.into_iter().min().ok_or_else(|| {
+            eyre!("recording {recording_directory:?} does not contain any frames")
+        })?;
+        let first_control_timestamp = control.peek_timestamp()?.unwrap_or(recording_start);
+
+        Ok(Self {
+            ids,
+            keep_running,
+            playback,
+            recording_start,
+            replay_start: Instant::now(),
+            initial_now: first_control_timestamp,
+            now: Mutex::new(HashMap::new()),
+            cycler_instances_of_threads: Mutex::new(HashMap::new()),
+            lockstep_cycler_instance: Mutex::new(None),
+            control,
+            vision_top,
+            vision_bottom,
+            audio,
+            spl_network,
+        })
+    }
+
+    fn read_next<Database>(&self, stream: &Stream) -> Result<Database>
+    where
+        Database: DeserializeOwned,
+    {
+        let frame = match stream.recording.lock().as_mut() {
+            Some(recording) => recording.next_frame().wrap_err_with(|| {
+                format!("failed to read recording of `{:?}`", stream.cycler_instance)
+            })?,
+            None => None,
+        };
+        let frame = match frame {
+            Some(frame) => frame,
+            None => {
+                info!("Reached end of recording of `{:?}`", stream.cycler_instance);
+                if self.playback == Playback::Lockstep {
+                    // all cyclers share the calling thread, nobody else is able to end the replay
+                    self.keep_running.cancel();
+                }
+                while !self.keep_running.is_cancelled() {
+                    sleep(MAXIMUM_SLEEP_DURATION);
+                }
+                bail!("termination requested");
+            }
+        };
+        if self.playback == Playback::RealTime {
+            self.wait_until(frame.timestamp)?;
+        }
+        self.now
+            .lock()
+            .insert(stream.cycler_instance, frame.timestamp);
+        if self.playback == Playback::RealTime {
+            self.cycler_instances_of_threads
+                .lock()
+                .insert(thread::current().id(), stream.cycler_instance);
+        }
+        let database = decode_database(&frame.data).wrap_err_with(|| {
+            format!(
+                "failed to deserialize recorded database of `{:?}`",
+                stream.cycler_instance
+            )
+        })?;
+        Ok(database)
+    }
+
+    /// Has to be called before each cycle of a lockstep run, see `cyclers::Lockstep::run()`
+    pub fn begin_lockstep_cycle(&self, cycler_instance: CyclerInstance) {
+        *self.lockstep_cycler_instance.lock() = Some(cycler_instance);
+    }
+
+    fn calling_cycler_instance(&self) -> Option<CyclerInstance> {
+        match self.playback {
+            Playback::RealTime => self
+                .cycler_instances_of_threads
+                .lock()
+                .get(&thread::current().id())
+                .copied(),
+            Playback::Lockstep => *self.lockstep_cycler_instance.lock(),
+        }
+    }
+
+    /// Schedules all recorded perception cyclers relative to `Control` by their amount of frames
+    pub fn lockstep_schedule(&self, seed: u64) -> Result<LockstepSchedule<CyclerInstance>> {
+        let control_frames = self.control.amount_of_frames();
+        if control_frames == 0 {
+            bail!("lockstep replay requires a recording of `Control`");
+        }
+        let cycler_instances_with_intervals = [
+            &self.vision_top,
+            &self.vision_bottom,
+            &self.audio,
+            &self.spl_network,
+        ]
+        .into_iter()
+        .filter_map(|stream| {
+            let frames = stream.amount_of_frames();
+            (frames > 0).then_some((stream.cycler_instance, (control_frames / frames).max(1)))
+        })
+        .collect();
+        Ok(LockstepSchedule::new(
+            seed,
+            self.control.cycler_instance,
+            cycler_instances_with_intervals,
+        ))
+    }
+
+    fn wait_until(&self, timestamp: SystemTime) -> Result<()> {
+        let deadline = self.replay_start
+            + timestamp
+                .duration_since(self.recording_start)
+                .unwrap_or_default();
+        loop {
+            if self.keep_running.is_cancelled() {
+                bail!("termination requested");
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(());
+            }
+            sleep((deadline - now).min(MAXIMUM_SLEEP_DURATION));
+        }
+    }
+}
+
+impl hardware::Interface for Interface {
+    fn read_from_microphones(&self) -> Result<Samples> {
+        let database: RecordedDatabase<RecordedAudioOutputs> = self.read_next(&self.audio)?;
+        Ok(database.main_outputs.samples)
+    }
+
+    /// Cyclers which did not read a frame yet (e.g. periodic ones) see the latest replayed time
+    fn get_now(&self) -> SystemTime {
+        let cycler_instance = self.calling_cycler_instance();
+        let now = self.now.lock();
+        cycler_instance
+            .and_then(|cycler_instance| now.get(&cycler_instance))
+            .or_else(|| now.values().max())
+            .copied()
+            .unwrap_or(self.initial_now)
+    }
+
+    fn get_ids(&self) -> Ids {
+        self.ids.clone()
+    }
+
+    fn read_from_sensors(&self) -> Result<SensorData> {
+        let database: RecordedDatabase<RecordedControlOutputs> = self.read_next(&self.control)?;
+        Ok(database.main_outputs.sensor_data)
+    }
+
+    fn write_to_actuators(
+        &self,
+        _positions: Joints<f32>,
+        _stiffnesses: Joints<f32>,
+        _leds: Leds,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn read_from_network(&self) -> Result<IncomingMessage> {
+        let database: RecordedDatabase<RecordedSplNetworkOutputs> =
+            self.read_next(&self.spl_network)?;
+        Ok(database.main_outputs.message)
+    }
+
+    fn write_to_network(&self, _message: OutgoingMessage) -> Result<()> {
+        Ok(())
+    }
+
+    fn read_from_camera(&self, camera_position: CameraPosition) -> Result<YCbCr422Image> {
+        let stream = match camera_position {
+            CameraPosition::Top => &self.vision_top,
+            CameraPosition::Bottom => &self.vision_bottom,
+        };
+        let database: RecordedDatabase<RecordedVisionOutputs> = self.read_next(stream)?;
+        Ok(database.main_outputs.image)
+    }
+}
@@ -0,0 +1,5 @@
+mod interface;
+mod recording_stream;
+pub mod timeline_server;
+
+pub use interface::{Interface, Playback};
@@ -0,0 +1,59 @@
+use std::{collections::VecDeque, fs::File, io::BufReader, path::Path, time::SystemTime};
+
+use color_eyre::{eyre::WrapErr, Result};
+use framework::{Frame, RecordingReader};
+
+pub struct RecordingStream {
+    reader: RecordingReader<BufReader<File>>,
+    next_chunk_index: usize,
+    frames: VecDeque<Frame>,
+}
+
+impl RecordingStream {
+    pub fn open(file_path: impl AsRef<Path>) -> Result<Self> {
+        let file_path = file_path.as_ref();
+        let file = File::open(file_path)
+            .wrap_err_with(|| format!("failed to open recording file {file_path:?}"))?;
+        let reader = RecordingReader::new(BufReader::new(file))
+            .wrap_err_with(|| format!("failed to read index of recording file {file_path:?}"))?;
+        Ok(Self {
+            reader,
+            next_chunk_index: 0,
+            frames: VecDeque::new(),
+        })
+    }
+
+    pub fn amount_of_frames(&self) -> usize {
+        self.reader
+            .index()
+            .iter()
+            .map(|entry| entry.amount_of_frames as usize)
+            .sum()
+    }
+
+    pub fn peek_timestamp(&mut self) -> Result<Option<SystemTime>> {
+        if self.frames.is_empty() {
+            self.load_next_chunk()?;
+        }
+        Ok(self.frames.front().map(|frame| frame.timestamp))
+    }
+
+    pub fn next_frame(&mut self) -> Result<Option<Frame>> {
+        if self.frames.is_empty() {
+            self.load_next_chunk()?;
+        }
+        Ok(self.frames.pop_front())
+    }
+
+    fn load_next_chunk(&mut self) -> Result<()> {
+        while self.frames.is_empty() && self.next_chunk_index < self.reader.index().len() {
+            self.frames = self
+                .reader
+                .read_chunk(self.next_chunk_index)
+                .wrap_err_with(|| format!("failed to read chunk {}", self.next_chunk_index))?
+                .into();
+            self.next_chunk_index += 1;
+        }
+        Ok(())
+    }
+}
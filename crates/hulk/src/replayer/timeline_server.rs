@@ -0,0 +1,513 @@
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread::{Builder, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+use color_eyre::{
+    eyre::{bail, eyre, WrapErr},
+    Result,
+};
+use communication::server::Runtime;
+use framework::{
+    decode_database, multiple_buffer_with_slots, Frame, Reader, RecordingReader, Writer,
+};
+use log::{error, info};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+use tokio::{net::ToSocketAddrs, select, sync::Notify, time::interval};
+use tokio_util::sync::CancellationToken;
+use types::{FallState, GameControllerState, TimelineMarker, TimelineMarkerDetector};
+
+use crate::recorder::recording_file_path;
+
+const PLAYBACK_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Clone, Serialize, Deserialize, SerializeHierarchy)]
+struct Configuration {
+    seek: SeekRequest,
+    play: PlayRequest,
+    playback_speed: f32,
+}
+
+/// Handled once per new `id`, so seeking to the same time again takes effect
+#[derive(Clone, Copy, Serialize, Deserialize, SerializeHierarchy)]
+struct SeekRequest {
+    id: u64,
+    time: Duration,
+}
+
+/// Handled once per new `id`, so playing again after playback paused at the end takes effect
+#[derive(Clone, Copy, Serialize, Deserialize, SerializeHierarchy)]
+struct PlayRequest {
+    id: u64,
+    playing: bool,
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize, SerializeHierarchy)]
+struct FrameTimes {
+    current: Option<Duration>,
+    previous: Option<Duration>,
+    next: Option<Duration>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, SerializeHierarchy)]
+struct MainOutputs {
+    recording_start: Option<SystemTime>,
+    duration: Duration,
+    current_time: Duration,
+    playing: bool,
+    markers: Vec<TimelineMarker<Duration>>,
+    control: FrameTimes,
+    vision_top: FrameTimes,
+    vision_bottom: FrameTimes,
+    audio: FrameTimes,
+    spl_network: FrameTimes,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, SerializeHierarchy)]
+struct TimelineDatabase {
+    main_outputs: MainOutputs,
+}
+
+/// The outputs of `Control` needed to detect markers, decoding whole databases is much slower
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct MarkerOutputs {
+    game_controller_state: Option<GameControllerState>,
+    fall_state: FallState,
+}
+
+#[derive(Deserialize)]
+struct MarkerDatabase {
+    main_outputs: MarkerOutputs,
+}
+
+struct CyclerTimeline<Database> {
+    cycler_instance: &'static str,
+    recording: Option<RecordingReader<BufReader<File>>>,
+    loaded_chunk: Option<(usize, Vec<Frame>)>,
+    shown_timestamp: Option<SystemTime>,
+    database_writer: Writer<Database>,
+    database_changed: Arc<Notify>,
+}
+
+impl<Database> CyclerTimeline<Database>
+where
+    Database: Default + DeserializeOwned + SerializeHierarchy + Send + Sync + 'static,
+{
+    fn new(
+        recording_directory: &Path,
+        cycler_instance: &'static str,
+        communication_server: &Runtime<Configuration>,
+    ) -> Result<Self> {
+        let file_path = recording_file_path(recording_directory, cycler_instance);
+        let recording = match file_path.exists() {
+            true => {
+                let file = File::open(&file_path)
+                    .wrap_err_with(|| format!("failed to open recording file {file_path:?}"))?;
+                Some(
+                    RecordingReader::new(BufReader::new(file)).wrap_err_with(|| {
+                        format!("failed to read index of recording file {file_path:?}")
+                    })?,
+                )
+            }
+            false => {
+                info!("No recording of `{cycler_instance}` found");
+                None
+            }
+        };
+
+        let (database_writer, database_reader) = multiple_buffer_with_slots([
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        ]);
+        let database_changed = Arc::new(Notify::new());
+        let (subscribed_outputs_writer, _subscribed_outputs_reader) = multiple_buffer_with_slots([
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        ]);
+        communication_server.register_cycler_instance(
+            cycler_instance,
+            database_changed.clone(),
+            database_reader,
+            subscribed_outputs_writer,
+        );
+
+        Ok(Self {
+            cycler_instance,
+            recording,
+            loaded_chunk: None,
+            shown_timestamp: None,
+            database_writer,
+            database_changed,
+        })
+    }
+
+    fn time_range(&self) -> Option<(SystemTime, SystemTime)> {
+        let index = self.recording.as_ref()?.index();
+        Some((index.first()?.first_timestamp, index.last()?.last_timestamp))
+    }
+
+    fn show(&mut self, timestamp: SystemTime, recording_start: SystemTime) -> Result<FrameTimes> {
+        let recording = match self.recording.as_mut() {
+            Some(recording) => recording,
+            None => return Ok(FrameTimes::default()),
+        };
+        let to_offset = |timestamp: SystemTime| {
+            timestamp
+                .duration_since(recording_start)
+                .unwrap_or_default()
+        };
+
+        let chunk_index = match recording.find_latest_chunk(timestamp) {
+            Some(chunk_index) => chunk_index,
+            None => {
+                if self.shown_timestamp.take().is_some() {
+                    *self.database_writer.next() = Default::default();
+                    self.database_changed.notify_waiters();
+                }
+                return Ok(FrameTimes {
+                    next: recording
+                        .index()
+                        .first()
+                        .map(|entry| to_offset(entry.first_timestamp)),
+                    ..Default::default()
+                });
+            }
+        };
+        let frames = match &mut self.loaded_chunk {
+            Some((loaded_index, frames)) if *loaded_index == chunk_index => frames,
+            loaded_chunk => {
+                let frames = recording
+                    .read_chunk(chunk_index)
+                    .wrap_err_with(|| format!("failed to read chunk {chunk_index}"))?;
+                &mut loaded_chunk.insert((chunk_index, frames)).1
+            }
+        };
+
+        let position = frames
+            .partition_point(|frame| frame.timestamp <= timestamp)
+            .max(1);
+        let frame = &frames[position - 1];
+        let previous = match position {
+            1 => chunk_index
+                .checked_sub(1)
+                .map(|previous_index| recording.index()[previous_index].last_timestamp),
+            _ => Some(frames[position - 2].timestamp),
+        };
+        let next = match frames.get(position) {
+            Some(next_frame) => Some(next_frame.timestamp),
+            None => recording
+                .index()
+                .get(chunk_index + 1)
+                .map(|entry| entry.first_timestamp),
+        };
+
+        if self.shown_timestamp != Some(frame.timestamp) {
+            let database = decode_database(&frame.data).wrap_err_with(|| {
+                format!(
+                    "failed to deserialize recorded database of `{}`",
+                    self.cycler_instance
+                )
+            })?;
+            *self.database_writer.next() = database;
+            self.database_changed.notify_waiters();
+            self.shown_timestamp = Some(frame.timestamp);
+        }
+
+        Ok(FrameTimes {
+            current: Some(to_offset(frame.timestamp)),
+            previous: previous.map(to_offset),
+            next: next.map(to_offset),
+        })
+    }
+}
+
+struct Timeline {
+    recording_start: SystemTime,
+    duration: Duration,
+    markers: Vec<TimelineMarker<Duration>>,
+    marker_detection: Option<JoinHandle<Result<Vec<TimelineMarker<Duration>>>>>,
+    control: CyclerTimeline<cyclers::control::Database>,
+    vision_top: CyclerTimeline<cyclers::vision::Database>,
+    vision_bottom: CyclerTimeline<cyclers::vision::Database>,
+    audio: CyclerTimeline<cyclers::audio::Database>,
+    spl_network: CyclerTimeline<cyclers::spl_network::Database>,
+}
+
+impl Timeline {
+    fn new(
+        recording_directory: &Path,
+        communication_server: &Runtime<Configuration>,
+    ) -> Result<Self> {
+        let control = CyclerTimeline::new(recording_directory, "Control", communication_server)?;
+        let vision_top =
+            CyclerTimeline::new(recording_directory, "VisionTop", communication_server)?;
+        let vision_bottom =
+            CyclerTimeline::new(recording_directory, "VisionBottom", communication_server)?;
+        let audio = CyclerTimeline::new(recording_directory, "Audio", communication_server)?;
+        let spl_network =
+            CyclerTimeline::new(recording_directory, "SplNetwork", communication_server)?;
+
+        let time_ranges = [
+            control.time_range(),
+            vision_top.time_range(),
+            vision_bottom.time_range(),
+            audio.time_range(),
+            spl_network.time_range(),
+        ];
+        let recording_start = time_ranges
+            .iter()
+            .flatten()
+            .map(|(start, _)| *start)
+            .min()
+            .ok_or_else(|| {
+                eyre!("recording {recording_directory:?} does not contain any frames")
+            })?;
+        let recording_end = time_ranges
+            .iter()
+            .flatten()
+            .map(|(_, end)| *end)
+            .max()
+            .unwrap_or(recording_start);
+        // reading the whole recording takes a while, markers are shown once they are detected
+        let marker_detection = {
+            let file_path = recording_file_path(recording_directory, "Control");
+            Builder::new()
+                .name("markers".to_string())
+                .spawn(move || detect_markers(&file_path, recording_start))
+                .wrap_err("failed to spawn thread for timeline marker detection")?
+        };
+
+        Ok(Self {
+            recording_start,
+            duration: recording_end
+                .duration_since(recording_start)
+                .unwrap_or_default(),
+            markers: Vec::new(),
+            marker_detection: Some(marker_detection),
+            control,
+            vision_top,
+            vision_bottom,
+            audio,
+            spl_network,
+        })
+    }
+
+    fn collect_detected_markers(&mut self) {
+        let marker_detection = match self.marker_detection.take() {
+            Some(marker_detection) if marker_detection.is_finished() => marker_detection,
+            marker_detection => {
+                self.marker_detection = marker_detection;
+                return;
+            }
+        };
+        match marker_detection.join() {
+            Ok(Ok(markers)) => self.markers = markers,
+            Ok(Err(error)) => error!("failed to detect timeline markers: {error:?}"),
+            Err(_) => error!("timeline marker detection panicked"),
+        }
+    }
+
+    fn show(&mut self, time: Duration, playing: bool) -> Result<MainOutputs> {
+        self.collect_detected_markers();
+        let timestamp = self.recording_start + time;
+        Ok(MainOutputs {
+            recording_start: Some(self.recording_start),
+            duration: self.duration,
+            current_time: time,
+            playing,
+            markers: self.markers.clone(),
+            control: self.control.show(timestamp, self.recording_start)?,
+            vision_top: self.vision_top.show(timestamp, self.recording_start)?,
+            vision_bottom: self.vision_bottom.show(timestamp, self.recording_start)?,
+            audio: self.audio.show(timestamp, self.recording_start)?,
+            spl_network: self.spl_network.show(timestamp, self.recording_start)?,
+        })
+    }
+}
+
+fn detect_markers(
+    file_path: &Path,
+    recording_start: SystemTime,
+) -> Result<Vec<TimelineMarker<Duration>>> {
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = File::open(file_path)
+        .wrap_err_with(|| format!("failed to open recording file {file_path:?}"))?;
+    let mut recording = RecordingReader::new(BufReader::new(file))
+        .wrap_err_with(|| format!("failed to read index of recording file {file_path:?}"))?;
+    info!("Detecting timeline markers in recording of `Control`...");
+    let mut detector = TimelineMarkerDetector::default();
+    let mut markers = Vec::new();
+    for chunk_index in 0..recording.index().len() {
+        let frames = recording
+            .read_chunk(chunk_index)
+            .wrap_err_with(|| format!("failed to read chunk {chunk_index}"))?;
+        for frame in frames {
+            let database: MarkerDatabase = decode_database(&frame.data)
+                .wrap_err("failed to deserialize recorded database of `Control`")?;
+            let position = frame
+                .timestamp
+                .duration_since(recording_start)
+                .unwrap_or_default();
+            detector.detect_game_controller_changes(
+                position,
+                database.main_outputs.game_controller_state.as_ref(),
+                &mut markers,
+            );
+            detector.detect_falls(position, database.main_outputs.fall_state, &mut markers);
+        }
+    }
+    info!("Detected {} timeline markers", markers.len());
+    Ok(markers)
+}
+
+async fn timeline_server(
+    keep_running: CancellationToken,
+    parameters_reader: Reader<Configuration>,
+    parameters_changed: Arc<Notify>,
+    outputs_writer: Writer<TimelineDatabase>,
+    outputs_changed: Arc<Notify>,
+    mut timeline: Timeline,
+) -> Result<()> {
+    // Hack to provide the time range to clients initially.
+    // Can be removed if communication sends data for
+    // subscribed outputs immediately after subscribing
+    let mut outputs_interval = interval(Duration::from_secs(1));
+    let mut playback_interval = interval(PLAYBACK_INTERVAL);
+
+    let mut handled_seek_id = None;
+    let mut handled_play_id = None;
+    let mut current_time = Duration::ZERO;
+    let mut playing = false;
+    let mut last_update = Instant::now();
+
+    loop {
+        select! {
+            _ = parameters_changed.notified() => { }
+            _ = outputs_interval.tick() => { }
+            _ = playback_interval.tick(), if playing => { }
+            _ = keep_running.cancelled() => {
+                break
+            }
+        }
+
+        let parameters = parameters_reader.next().clone();
+        let now = Instant::now();
+        if handled_seek_id != Some(parameters.seek.id) {
+            handled_seek_id = Some(parameters.seek.id);
+            current_time = parameters.seek.time;
+        } else if playing {
+            current_time += (now - last_update).mul_f32(parameters.playback_speed.max(0.0));
+        }
+        if handled_play_id != Some(parameters.play.id) {
+            handled_play_id = Some(parameters.play.id);
+            playing = parameters.play.playing;
+            if playing && current_time >= timeline.duration {
+                current_time = Duration::ZERO;
+            }
+        }
+        current_time = current_time.min(timeline.duration);
+        if current_time == timeline.duration {
+            playing = false;
+        }
+        last_update = now;
+
+        let main_outputs = timeline.show(current_time, playing)?;
+        outputs_writer.next().main_outputs = main_outputs;
+        outputs_changed.notify_waiters();
+    }
+
+    Ok(())
+}
+
+pub fn run(
+    addresses: Option<impl ToSocketAddrs + Send + Sync + 'static>,
+    parameters_directory: PathBuf,
+    recording_directory: impl AsRef<Path>,
+    keep_running: CancellationToken,
+) -> Result<()> {
+    let parameter_slots = 3; // 2 for communication writer + 1 reader for timeline_server
+    let communication_server = Runtime::<Configuration>::start(
+        addresses,
+        parameters_directory,
+        "timeline".to_string(),
+        "timeline".to_string(),
+        parameter_slots,
+        keep_running.clone(),
+    )?;
+
+    let (outputs_writer, outputs_reader) =
+        multiple_buffer_with_slots([Default::default(), Default::default(), Default::default()]);
+    let outputs_changed = Arc::new(Notify::new());
+    let (subscribed_outputs_writer, _subscribed_outputs_reader) =
+        multiple_buffer_with_slots([Default::default(), Default::default(), Default::default()]);
+    communication_server.register_cycler_instance(
+        "Timeline",
+        outputs_changed.clone(),
+        outputs_reader,
+        subscribed_outputs_writer,
+    );
+
+    let timeline = Timeline::new(recording_directory.as_ref(), &communication_server)
+        .wrap_err("failed to load recording")?;
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let timeline_task = {
+        let keep_running = keep_running.clone();
+        let parameters_changed = communication_server.get_parameters_changed();
+        let parameters_reader = communication_server.get_parameters_reader();
+        runtime.spawn(async move {
+            let result = timeline_server(
+                keep_running.clone(),
+                parameters_reader,
+                parameters_changed,
+                outputs_writer,
+                outputs_changed,
+                timeline,
+            )
+            .await;
+            if result.is_err() {
+                keep_running.cancel();
+            }
+            result
+        })
+    };
+
+    let mut encountered_error = false;
+    match communication_server.join() {
+        Ok(Err(error)) => {
+            encountered_error = true;
+            error!("{error:?}");
+        }
+        Err(error) => {
+            encountered_error = true;
+            error!("{error:?}");
+        }
+        _ => {}
+    }
+    match runtime.block_on(timeline_task) {
+        Ok(Err(error)) => {
+            encountered_error = true;
+            error!("{error:?}");
+        }
+        Err(error) => {
+            encountered_error = true;
+            error!("{error:?}");
+        }
+        _ => {}
+    }
+
+    if encountered_error {
+        bail!("timeline server exited with error");
+    }
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use std::{
+    fs::{read_to_string, write, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use cyclers::CyclerInstance;
+use framework::{encode_database, Frame, RecordingFrame, RecordingReader};
+use hulk::{
+    recorder::{recording_file_path, start_recorder},
+    replayer::{Interface, Playback},
+};
+use serde_json::{from_str, to_string_pretty, Value};
+use tempfile::tempdir;
+use tokio_util::sync::CancellationToken;
+use types::{hardware::Ids, samples::Samples};
+
+const AMOUNT_OF_CONTROL_FRAMES: usize = 100;
+const CONTROL_FRAMES_PER_AUDIO_FRAME: usize = 4;
+const AMOUNT_OF_AUDIO_SAMPLES: usize = 2048;
+
+fn repository_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+fn control_frame(index: usize) -> RecordingFrame {
+    let mut database = cyclers::control::Database::default();
+    let sensor_data = &mut database.main_outputs.sensor_data;
+    sensor_data.inertial_measurement_unit.linear_acceleration.z = -9.81;
+    sensor_data.positions.head.yaw = index as f32 * 0.01;
+    sensor_data.force_sensitive_resistors.left.front_left = 0.5;
+    sensor_data.force_sensitive_resistors.right.front_right = 0.5;
+    RecordingFrame {
+        cycler_instance: "Control".to_string(),
+        frame: Frame {
+            timestamp: UNIX_EPOCH + Duration::from_millis(12 * index as u64),
+            data: encode_database(&database).unwrap(),
+        },
+    }
+}
+
+fn audio_frame(index: usize) -> RecordingFrame {
+    let mut database = cyclers::audio::Database::default();
+    let channel: Vec<_> = (0..AMOUNT_OF_AUDIO_SAMPLES)
+        .map(|sample| ((index * AMOUNT_OF_AUDIO_SAMPLES + sample) as f32 * 0.3).sin())
+        .collect();
+    database.main_outputs.samples = Samples {
+        rate: 44100,
+        channels_of_samples: Arc::new(vec![channel; 4]),
+    };
+    RecordingFrame {
+        cycler_instance: "Audio".to_string(),
+        frame: Frame {
+            timestamp: UNIX_EPOCH
+                + Duration::from_millis((12 * CONTROL_FRAMES_PER_AUDIO_FRAME * index + 5) as u64),
+            data: encode_database(&database).unwrap(),
+        },
+    }
+}
+
+fn write_recording(recording_directory: &Path) {
+    let ids = Ids {
+        body_id: "lockstep_body".to_string(),
+        head_id: "lockstep_head".to_string(),
+    };
+    let (sender, handle) = start_recorder(recording_directory, &ids).unwrap();
+    for index in 0..AMOUNT_OF_CONTROL_FRAMES {
+        sender.send(control_frame(index)).unwrap();
+        if index % CONTROL_FRAMES_PER_AUDIO_FRAME == 0 {
+            sender
+                .send(audio_frame(index / CONTROL_FRAMES_PER_AUDIO_FRAME))
+                .unwrap();
+        }
+    }
+    drop(sender);
+    handle.join().unwrap().unwrap();
+}
+
+fn write_parameters(parameters_directory: &Path) {
+    let mut parameters: Value = from_str(
+        &read_to_string(repository_root().join("etc/configuration/default.json")).unwrap(),
+    )
+    .unwrap();
+    parameters["recording"]["control"]["enabled"] = Value::Bool(true);
+    parameters["recording"]["audio"]["enabled"] = Value::Bool(true);
+    write(
+        parameters_directory.join("default.json"),
+        to_string_pretty(&parameters).unwrap(),
+    )
+    .unwrap();
+}
+
+fn read_frames(recording_directory: &Path, cycler_instance: &str) -> Vec<Frame> {
+    let file = File::open(recording_file_path(recording_directory, cycler_instance)).unwrap();
+    let mut reader = RecordingReader::new(BufReader::new(file)).unwrap();
+    (0..reader.index().len())
+        .flat_map(|chunk_index| reader.read_chunk(chunk_index).unwrap())
+        .collect()
+}
+
+/// Runs the replayer in its own process, nodes load files relative to the repository root
+fn replay(
+    recording_directory: &Path,
+    parameters_directory: &Path,
+    seed: u64,
+    output_directory: &Path,
+) {
+    let status = Command::new(env!("CARGO_BIN_EXE_replayer"))
+        .current_dir(repository_root())
+        .arg("--listen-address")
+        .arg("127.0.0.1:0")
+        .arg("--parameters-directory")
+        .arg(parameters_directory)
+        .arg("--lockstep")
+        .arg("--seed")
+        .arg(seed.to_string())
+        .arg("--record")
+        .arg(output_directory)
+        .arg(recording_directory)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn first_rounds(recording_directory: &Path, seed: u64) -> Vec<Vec<CyclerInstance>> {
+    let hardware_interface = Interface::new(
+        CancellationToken::new(),
+        recording_directory,
+        Playback::Lockstep,
+    )
+    .unwrap();
+    let mut schedule = hardware_interface.lockstep_schedule(seed).unwrap();
+    (0..AMOUNT_OF_CONTROL_FRAMES)
+        .map(|_| schedule.next_round())
+        .collect()
+}
+
+#[test]
+fn recorded_cyclers_are_interleaved_by_the_seed() {
+    let recording_directory = tempdir().unwrap();
+    write_recording(recording_directory.path());
+
+    let rounds = first_rounds(recording_directory.path(), 42);
+    for (index, round) in rounds.iter().enumerate() {
+        let expected_length = match index % CONTROL_FRAMES_PER_AUDIO_FRAME {
+            0 => 2,
+            _ => 1,
+        };
+        assert_eq!(round.len(), expected_length);
+        assert!(round.contains(&CyclerInstance::Control));
+    }
+    let audio_first: Vec<_> = rounds
+        .iter()
+        .filter(|round| round.len() == 2)
+        .map(|round| round[0] == CyclerInstance::Audio)
+        .collect();
+    assert!(audio_first.contains(&true));
+    assert!(audio_first.contains(&false));
+
+    assert_eq!(first_rounds(recording_directory.path(), 42), rounds);
+    assert_ne!(first_rounds(recording_directory.path(), 43), rounds);
+}
+
+#[test]
+fn replaying_a_recording_twice_results_in_the_same_databases() {
+    let recording_directory = tempdir().unwrap();
+    let parameters_directory = tempdir().unwrap();
+    let first_output_directory = tempdir().unwrap();
+    let second_output_directory = tempdir().unwrap();
+    write_recording(recording_directory.path());
+    write_parameters(parameters_directory.path());
+
+    for output_directory in [&first_output_directory, &second_output_directory] {
+        replay(
+            recording_directory.path(),
+            parameters_directory.path(),
+            42,
+            output_directory.path(),
+        );
+    }
+
+    for cycler_instance in ["Control", "Audio"] {
+        let first_replay = read_frames(first_output_directory.path(), cycler_instance);
+        let second_replay = read_frames(second_output_directory.path(), cycler_instance);
+        assert!(!first_replay.is_empty());
+        assert_eq!(first_replay, second_replay);
+    }
+    assert_eq!(
+        read_frames(first_output_directory.path(), "Control").len(),
+        AMOUNT_OF_CONTROL_FRAMES
+    );
+}
@@ -11,7 +11,7 @@ use color_eyre::{
 };
 use communication::{
     client::{Communication, ConnectionStatus, CyclerOutput, SubscriberMessage},
-    messages::Format,
+    messages::{Format, Rate},
 };
 use serde_json::json;
 
@@ -241,6 +241,7 @@ impl Nao {
                                 "Control.main_outputs.motion_selection.current_motion",
                             )?,
                             Format::Textual,
+                            Rate::EveryCycle,
                         )
                         .await;
 
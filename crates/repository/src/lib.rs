@@ -52,6 +52,10 @@ impl Repository {
         self.root.join("crates")
     }
 
+    pub fn lint_allowlist_file_path(&self) -> PathBuf {
+        self.root.join("etc/lint_allowlist.json")
+    }
+
     pub fn find_latest_file(&self, pattern: &str) -> Result<PathBuf> {
         let path = self.root.join(pattern);
         let matching_paths: Vec<_> = glob(
@@ -176,7 +180,7 @@ impl Repository {
         .await
     }
 
-    fn configuration_root(&self) -> PathBuf {
+    pub fn configuration_root(&self) -> PathBuf {
         self.root.join("etc/configuration")
     }
 
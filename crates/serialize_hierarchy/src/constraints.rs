@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Restrictions of the values a field accepts, declared via `#[serialize_hierarchy(...)]`
+///
+/// `minimum` and `maximum` apply to numbers and element-wise to arrays of numbers (e.g. vectors),
+/// `non_empty` applies to strings, arrays and objects, `unit` is informational only.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Constraints {
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub non_empty: bool,
+    pub unit: Option<String>,
+}
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ConstraintViolation {
+    #[error("{path:?} is not a number")]
+    NotANumber { path: String },
+    #[error("{path:?} is {value} but must be at least {minimum}")]
+    BelowMinimum {
+        path: String,
+        value: f64,
+        minimum: f64,
+    },
+    #[error("{path:?} is {value} but must be at most {maximum}")]
+    AboveMaximum {
+        path: String,
+        value: f64,
+        maximum: f64,
+    },
+    #[error("{path:?} must not be empty")]
+    Empty { path: String },
+}
+
+impl Constraints {
+    pub fn check(&self, path: &str, value: &Value) -> Result<(), ConstraintViolation> {
+        if self.minimum.is_some() || self.maximum.is_some() {
+            self.check_bounds(path, value)?;
+        }
+        if self.non_empty {
+            let is_empty = match value {
+                Value::String(string) => string.is_empty(),
+                Value::Array(array) => array.is_empty(),
+                Value::Object(object) => object.is_empty(),
+                _ => false,
+            };
+            if is_empty {
+                return Err(ConstraintViolation::Empty {
+                    path: path.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_bounds(&self, path: &str, value: &Value) -> Result<(), ConstraintViolation> {
+        let number = match value {
+            Value::Array(elements) => {
+                return elements
+                    .iter()
+                    .try_for_each(|element| self.check_bounds(path, element))
+            }
+            Value::Number(number) => number.as_f64(),
+            _ => None,
+        }
+        .ok_or_else(|| ConstraintViolation::NotANumber {
+            path: path.to_string(),
+        })?;
+        match (self.minimum, self.maximum) {
+            (Some(minimum), _) if number < minimum => Err(ConstraintViolation::BelowMinimum {
+                path: path.to_string(),
+                value: number,
+                minimum,
+            }),
+            (_, Some(maximum)) if number > maximum => Err(ConstraintViolation::AboveMaximum {
+                path: path.to_string(),
+                value: number,
+                maximum,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Checks a value that is about to be written to `path` against all constraints affecting it,
+/// i.e. constraints of the path itself, of its parents and of fields nested in the value
+pub fn check_constraints(
+    constraints: &BTreeMap<String, Constraints>,
+    path: &str,
+    value: &Value,
+) -> Result<(), ConstraintViolation> {
+    for (constrained_path, constraints) in constraints {
+        let is_affected = constrained_path == path
+            || matches!(
+                path.strip_prefix(constrained_path.as_str()),
+                Some(suffix) if suffix.starts_with('.')
+            );
+        if is_affected {
+            constraints.check(path, value)?;
+            continue;
+        }
+        let nested_value = constrained_path
+            .strip_prefix(path)
+            .and_then(|suffix| suffix.strip_prefix('.'))
+            .and_then(|suffix| {
+                suffix
+                    .split('.')
+                    .try_fold(value, |value, segment| value.get(segment))
+            });
+        if let Some(nested_value) = nested_value {
+            constraints.check(constrained_path, nested_value)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn values_of_path_parents_and_children_are_checked() {
+        let constraints = [
+            (
+                "filter.noise".to_string(),
+                Constraints {
+                    minimum: Some(0.0),
+                    ..Default::default()
+                },
+            ),
+            (
+                "filter.name".to_string(),
+                Constraints {
+                    non_empty: true,
+                    ..Default::default()
+                },
+            ),
+        ]
+        .into();
+
+        assert_eq!(
+            check_constraints(&constraints, "filter.noise", &json!([1.0, 2.0])),
+            Ok(())
+        );
+        assert_eq!(
+            check_constraints(&constraints, "filter.noise.y", &json!(-2.0)),
+            Err(ConstraintViolation::BelowMinimum {
+                path: "filter.noise.y".to_string(),
+                value: -2.0,
+                minimum: 0.0
+            })
+        );
+        assert_eq!(
+            check_constraints(&constraints, "filter.noise", &json!([1.0, null])),
+            Err(ConstraintViolation::NotANumber {
+                path: "filter.noise".to_string()
+            })
+        );
+        assert_eq!(
+            check_constraints(
+                &constraints,
+                "filter",
+                &json!({"noise": [1.0, 2.0], "name": ""})
+            ),
+            Err(ConstraintViolation::Empty {
+                path: "filter.name".to_string()
+            })
+        );
+        assert_eq!(
+            check_constraints(&constraints, "filter.other", &json!(-1.0)),
+            Ok(())
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     ops::{Deref, Range},
     sync::Arc,
 };
@@ -7,7 +7,7 @@ use std::{
 use nalgebra::{ArrayStorage, Const, Matrix, Point, Scalar, U1};
 use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{error::Error, SerializeHierarchy};
+use crate::{error::Error, Constraints, SerializeHierarchy, TypeDescription};
 
 impl<T> SerializeHierarchy for Arc<T>
 where
@@ -37,6 +37,18 @@ where
     fn get_fields() -> BTreeSet<String> {
         T::get_fields()
     }
+
+    fn get_constraints() -> BTreeMap<String, Constraints> {
+        T::get_constraints()
+    }
+
+    fn get_type() -> TypeDescription {
+        T::get_type()
+    }
+
+    fn get_types() -> BTreeMap<String, TypeDescription> {
+        T::get_types()
+    }
 }
 
 impl<T> SerializeHierarchy for Option<T>
@@ -72,6 +84,18 @@ where
     fn get_fields() -> BTreeSet<String> {
         T::get_fields()
     }
+
+    fn get_constraints() -> BTreeMap<String, Constraints> {
+        T::get_constraints()
+    }
+
+    fn get_type() -> TypeDescription {
+        TypeDescription::Option(Box::new(T::get_type()))
+    }
+
+    fn get_types() -> BTreeMap<String, TypeDescription> {
+        T::get_types()
+    }
 }
 
 impl<T> SerializeHierarchy for Range<T>
@@ -141,6 +165,22 @@ where
             .into_iter()
             .collect()
     }
+
+    fn get_type() -> TypeDescription {
+        TypeDescription::Struct
+    }
+
+    fn get_types() -> BTreeMap<String, TypeDescription> {
+        ["start", "end"]
+            .into_iter()
+            .flat_map(|field| {
+                T::get_types()
+                    .into_iter()
+                    .map(move |(path, description)| (format!("{field}.{path}"), description))
+                    .chain([(field.to_string(), T::get_type())])
+            })
+            .collect()
+    }
 }
 
 impl<T: Serialize + DeserializeOwned, const N: usize> SerializeHierarchy
@@ -197,6 +237,20 @@ impl<T: Serialize + DeserializeOwned, const N: usize> SerializeHierarchy
             .map(|path| String::from(*path))
             .collect()
     }
+
+    fn get_type() -> TypeDescription {
+        TypeDescription::Matrix {
+            rows: N,
+            columns: 1,
+        }
+    }
+
+    fn get_types() -> BTreeMap<String, TypeDescription> {
+        Matrix::<T, Const<N>, U1, ArrayStorage<T, N, 1>>::get_fields()
+            .into_iter()
+            .map(|path| (path, TypeDescription::of::<T>()))
+            .collect()
+    }
 }
 
 impl<T: Serialize + DeserializeOwned + Clone + Scalar, const N: usize> SerializeHierarchy
@@ -227,4 +281,12 @@ impl<T: Serialize + DeserializeOwned + Clone + Scalar, const N: usize> Serialize
     fn get_fields() -> BTreeSet<String> {
         Matrix::<T, Const<N>, U1, ArrayStorage<T, N, 1>>::get_fields()
     }
+
+    fn get_type() -> TypeDescription {
+        Matrix::<T, Const<N>, U1, ArrayStorage<T, N, 1>>::get_type()
+    }
+
+    fn get_types() -> BTreeMap<String, TypeDescription> {
+        Matrix::<T, Const<N>, U1, ArrayStorage<T, N, 1>>::get_types()
+    }
 }
@@ -1,17 +1,21 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub use bincode;
+pub use constraints::{check_constraints, ConstraintViolation, Constraints};
 pub use error::Error;
 
 pub use jpeg::{DecodeJpeg, EncodeJpeg};
 use serde::{Deserializer, Serializer};
 pub use serde_json;
 pub use serialize_hierarchy_derive::SerializeHierarchy;
+pub use type_description::{Primitive, TypeDescription};
 
+pub mod constraints;
 pub mod error;
 mod implementation;
 mod jpeg;
 mod not_supported;
+mod type_description;
 
 pub trait SerializeHierarchy {
     fn serialize_path<S>(&self, path: &str, serializer: S) -> Result<S::Ok, Error<S::Error>>
@@ -29,6 +33,20 @@ pub trait SerializeHierarchy {
     fn exists(path: &str) -> bool;
 
     fn get_fields() -> BTreeSet<String>;
+
+    /// Constraints of all (nested) fields declaring some, keyed by their path
+    fn get_constraints() -> BTreeMap<String, Constraints> {
+        Default::default()
+    }
+
+    fn get_type() -> TypeDescription {
+        TypeDescription::opaque::<Self>()
+    }
+
+    /// Types of all (nested) fields, keyed by their path
+    fn get_types() -> BTreeMap<String, TypeDescription> {
+        Default::default()
+    }
 }
 
 #[cfg(test)]
@@ -49,6 +67,34 @@ mod tests {
         field: bool,
     }
 
+    #[derive(Deserialize, Serialize, SerializeHierarchy)]
+    struct Constrained {
+        #[serialize_hierarchy(minimum = "-1.5", maximum = 3, unit = "m")]
+        distance: f32,
+        #[serialize_hierarchy(non_empty)]
+        name: String,
+        inner: Inner,
+    }
+
+    #[derive(Deserialize, Serialize, SerializeHierarchy)]
+    enum Mode {
+        Off,
+        On { level: u8 },
+    }
+
+    #[derive(Deserialize, Serialize, SerializeHierarchy)]
+    struct Typed {
+        mode: Mode,
+        inner: Option<Inner>,
+        #[serialize_hierarchy(leaf)]
+        samples: Vec<f32>,
+    }
+
+    #[derive(Deserialize, Serialize, SerializeHierarchy)]
+    struct OuterConstrained {
+        constrained: Constrained,
+    }
+
     #[test]
     fn primitive_fields_are_empty() {
         assert_eq!(bool::get_fields(), Default::default());
@@ -66,4 +112,62 @@ mod tests {
             ["inner".to_string(), "inner.field".to_string()].into()
         );
     }
+
+    #[test]
+    fn nested_constraints_are_prefixed() {
+        assert_eq!(Outer::get_constraints(), Default::default());
+        assert_eq!(
+            OuterConstrained::get_constraints(),
+            [
+                (
+                    "constrained.distance".to_string(),
+                    Constraints {
+                        minimum: Some(-1.5),
+                        maximum: Some(3.0),
+                        non_empty: false,
+                        unit: Some("m".to_string()),
+                    }
+                ),
+                (
+                    "constrained.name".to_string(),
+                    Constraints {
+                        non_empty: true,
+                        ..Default::default()
+                    }
+                ),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn nested_types_are_described() {
+        assert_eq!(Typed::get_type(), TypeDescription::Struct);
+        assert_eq!(
+            Typed::get_types(),
+            [
+                (
+                    "mode".to_string(),
+                    TypeDescription::Enum {
+                        variants: vec!["Off".to_string(), "On".to_string()]
+                    }
+                ),
+                (
+                    "inner".to_string(),
+                    TypeDescription::Option(Box::new(TypeDescription::Struct))
+                ),
+                (
+                    "inner.field".to_string(),
+                    TypeDescription::Primitive(Primitive::Boolean)
+                ),
+                (
+                    "samples".to_string(),
+                    TypeDescription::Sequence(Box::new(TypeDescription::Primitive(
+                        Primitive::Float
+                    )))
+                ),
+            ]
+            .into()
+        );
+    }
 }
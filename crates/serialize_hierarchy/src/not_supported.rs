@@ -7,10 +7,10 @@ use std::{
 use nalgebra::{Isometry2, Isometry3, SMatrix, UnitComplex, UnitQuaternion};
 use serde::{Deserializer, Serializer};
 
-use crate::{error::Error, SerializeHierarchy};
+use crate::{error::Error, Primitive, SerializeHierarchy, TypeDescription};
 
 macro_rules! implement_as_not_supported {
-    ($type:ty) => {
+    ($type:ty $(=> $description:expr)?) => {
         impl SerializeHierarchy for $type {
             fn serialize_path<S>(
                 &self,
@@ -47,9 +47,15 @@ macro_rules! implement_as_not_supported {
             fn get_fields() -> BTreeSet<String> {
                 Default::default()
             }
+
+            $(
+                fn get_type() -> TypeDescription {
+                    $description
+                }
+            )?
         }
     };
-    ($type:ty, $generic:tt) => {
+    ($type:ty, $generic:tt $(=> $description:expr)?) => {
         impl<$generic> SerializeHierarchy for $type {
             fn serialize_path<S>(
                 &self,
@@ -86,30 +92,36 @@ macro_rules! implement_as_not_supported {
             fn get_fields() -> BTreeSet<String> {
                 Default::default()
             }
+
+            $(
+                fn get_type() -> TypeDescription {
+                    $description
+                }
+            )?
         }
     };
 }
 
 // primary types
-implement_as_not_supported!(bool);
-implement_as_not_supported!(f32);
-implement_as_not_supported!(i16);
-implement_as_not_supported!(i32);
-implement_as_not_supported!(u8);
-implement_as_not_supported!(u16);
-implement_as_not_supported!(u32);
-implement_as_not_supported!(u64);
-implement_as_not_supported!(usize);
+implement_as_not_supported!(bool => TypeDescription::Primitive(Primitive::Boolean));
+implement_as_not_supported!(f32 => TypeDescription::Primitive(Primitive::Float));
+implement_as_not_supported!(i16 => TypeDescription::Primitive(Primitive::Integer));
+implement_as_not_supported!(i32 => TypeDescription::Primitive(Primitive::Integer));
+implement_as_not_supported!(u8 => TypeDescription::Primitive(Primitive::UnsignedInteger));
+implement_as_not_supported!(u16 => TypeDescription::Primitive(Primitive::UnsignedInteger));
+implement_as_not_supported!(u32 => TypeDescription::Primitive(Primitive::UnsignedInteger));
+implement_as_not_supported!(u64 => TypeDescription::Primitive(Primitive::UnsignedInteger));
+implement_as_not_supported!(usize => TypeDescription::Primitive(Primitive::UnsignedInteger));
 // nalgebra
-implement_as_not_supported!(SMatrix<f32, 3, 3>);
+implement_as_not_supported!(SMatrix<f32, 3, 3> => TypeDescription::Matrix { rows: 3, columns: 3 });
 implement_as_not_supported!(Isometry2<f32>);
 implement_as_not_supported!(Isometry3<f32>);
 implement_as_not_supported!(UnitComplex<f32>);
 implement_as_not_supported!(UnitQuaternion<f32>);
 // stdlib
-implement_as_not_supported!(SystemTime);
-implement_as_not_supported!(Duration);
-implement_as_not_supported!(String);
-implement_as_not_supported!(PathBuf);
-implement_as_not_supported!(Vec<T>, T);
-implement_as_not_supported!(HashSet<T>, T);
+implement_as_not_supported!(SystemTime => TypeDescription::Primitive(Primitive::SystemTime));
+implement_as_not_supported!(Duration => TypeDescription::Primitive(Primitive::Duration));
+implement_as_not_supported!(String => TypeDescription::Primitive(Primitive::String));
+implement_as_not_supported!(PathBuf => TypeDescription::Primitive(Primitive::Path));
+implement_as_not_supported!(Vec<T>, T => TypeDescription::Sequence(Box::new(TypeDescription::of::<T>())));
+implement_as_not_supported!(HashSet<T>, T => TypeDescription::Sequence(Box::new(TypeDescription::of::<T>())));
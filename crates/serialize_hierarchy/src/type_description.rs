@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Primitive {
+    Boolean,
+    Integer,
+    UnsignedInteger,
+    Float,
+    String,
+    Path,
+    Duration,
+    SystemTime,
+}
+
+/// Describes the type at a path, fields of structs are described at their own paths
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum TypeDescription {
+    Primitive(Primitive),
+    Struct,
+    Enum {
+        variants: Vec<String>,
+    },
+    Option(Box<TypeDescription>),
+    Sequence(Box<TypeDescription>),
+    Matrix {
+        rows: usize,
+        columns: usize,
+    },
+    /// Types without further introspection, named by their Rust type
+    Opaque {
+        name: String,
+    },
+}
+
+impl TypeDescription {
+    pub fn opaque<T: ?Sized>() -> Self {
+        Self::Opaque {
+            name: std::any::type_name::<T>().to_string(),
+        }
+    }
+
+    /// Describes types without implementing `SerializeHierarchy` (e.g. elements of sequences)
+    /// by their name: primitives, sequences and options are recognized, other types are opaque
+    pub fn of<T: ?Sized>() -> Self {
+        Self::from_type_name(std::any::type_name::<T>())
+    }
+
+    fn from_type_name(name: &str) -> Self {
+        let primitive = match name {
+            "bool" => Some(Primitive::Boolean),
+            "i8" | "i16" | "i32" | "i64" | "isize" => Some(Primitive::Integer),
+            "u8" | "u16" | "u32" | "u64" | "usize" => Some(Primitive::UnsignedInteger),
+            "f32" | "f64" => Some(Primitive::Float),
+            "alloc::string::String" => Some(Primitive::String),
+            "std::path::PathBuf" => Some(Primitive::Path),
+            "core::time::Duration" => Some(Primitive::Duration),
+            "std::time::SystemTime" => Some(Primitive::SystemTime),
+            _ => None,
+        };
+        if let Some(primitive) = primitive {
+            return Self::Primitive(primitive);
+        }
+        let (outer, inner) = match name.strip_suffix('>').and_then(|name| name.split_once('<')) {
+            Some(generic) => generic,
+            None => {
+                return Self::Opaque {
+                    name: name.to_string(),
+                }
+            }
+        };
+        match outer {
+            "alloc::vec::Vec"
+            | "alloc::collections::vec_deque::VecDeque"
+            | "std::collections::hash::set::HashSet"
+            | "alloc::collections::btree::set::BTreeSet" => {
+                Self::Sequence(Box::new(Self::from_type_name(inner)))
+            }
+            "core::option::Option" => Self::Option(Box::new(Self::from_type_name(inner))),
+            _ => Self::Opaque {
+                name: name.to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn sequences_are_described_with_their_elements() {
+        assert_eq!(
+            TypeDescription::of::<Vec<Option<f32>>>(),
+            TypeDescription::Sequence(Box::new(TypeDescription::Option(Box::new(
+                TypeDescription::Primitive(Primitive::Float)
+            ))))
+        );
+        assert_eq!(
+            TypeDescription::of::<HashSet<Vec<String>>>(),
+            TypeDescription::Sequence(Box::new(TypeDescription::Sequence(Box::new(
+                TypeDescription::Primitive(Primitive::String)
+            ))))
+        );
+        assert_eq!(
+            TypeDescription::of::<Vec<(f32, f32)>>(),
+            TypeDescription::Sequence(Box::new(TypeDescription::opaque::<(f32, f32)>()))
+        );
+    }
+}
@@ -26,6 +26,20 @@ fn process_input(mut input: DeriveInput) -> TokenStream {
             )
         }
     };
+    let type_description = match &input.data {
+        Data::Enum(data) => {
+            let variants = data
+                .variants
+                .iter()
+                .map(|variant| variant.ident.to_string());
+            quote! {
+                serialize_hierarchy::TypeDescription::Enum {
+                    variants: vec![#(#variants.to_string()),*],
+                }
+            }
+        }
+        _ => quote! { serialize_hierarchy::TypeDescription::Struct },
+    };
     let type_attributes = parse_attributes(&input.attrs);
     let contains_as_jpeg = type_attributes.contains(&TypeAttribute::AsJpeg);
 
@@ -45,25 +59,36 @@ fn process_input(mut input: DeriveInput) -> TokenStream {
     let field_exists_getters = generate_field_exists_getters(&serializable_fields);
     let field_chains = generate_field_chains(&serializable_fields);
     let path_field_chains = generate_path_field_chains(&serializable_fields);
-    let (jpeg_serialization, jpeg_exists_getter, jpeg_field_chain) = if contains_as_jpeg {
-        (
-            quote! {
-                "jpeg" => self
-                    .encode_as_jpeg(Self::DEFAULT_QUALITY)
-                    .map_err(|error| serialize_hierarchy::Error::SerializationFailed(serde::ser::Error::custom(error)))?
-                    .serialize(serializer)
-                    .map_err(serialize_hierarchy::Error::SerializationFailed),
-            },
-            quote! {
-                "jpeg" => true,
-            },
-            quote! {
-                .chain(std::iter::once("jpeg".to_string()))
-            },
-        )
-    } else {
-        Default::default()
-    };
+    let constraint_chains = generate_constraint_chains(&serializable_fields);
+    let path_constraint_chains = generate_path_constraint_chains(&serializable_fields);
+    let type_chains = generate_type_chains(&serializable_fields);
+    let path_type_chains = generate_path_type_chains(&serializable_fields);
+    let (jpeg_serialization, jpeg_exists_getter, jpeg_field_chain, jpeg_type_chain) =
+        if contains_as_jpeg {
+            (
+                quote! {
+                    "jpeg" => self
+                        .encode_as_jpeg(Self::DEFAULT_QUALITY)
+                        .map_err(|error| serialize_hierarchy::Error::SerializationFailed(serde::ser::Error::custom(error)))?
+                        .serialize(serializer)
+                        .map_err(serialize_hierarchy::Error::SerializationFailed),
+                },
+                quote! {
+                    "jpeg" => true,
+                },
+                quote! {
+                    .chain(std::iter::once("jpeg".to_string()))
+                },
+                quote! {
+                    .chain(std::iter::once((
+                        "jpeg".to_string(),
+                        serialize_hierarchy::TypeDescription::Opaque { name: "jpeg".to_string() },
+                    )))
+                },
+            )
+        } else {
+            Default::default()
+        };
 
     let implementation = quote! {
         impl #impl_generics serialize_hierarchy::SerializeHierarchy for #name #ty_generics #where_clause {
@@ -142,6 +167,25 @@ fn process_input(mut input: DeriveInput) -> TokenStream {
                     #jpeg_field_chain
                     .collect()
             }
+
+            fn get_constraints() -> std::collections::BTreeMap<String, serialize_hierarchy::Constraints> {
+                std::iter::empty::<(std::string::String, serialize_hierarchy::Constraints)>()
+                    #(#constraint_chains)*
+                    #(#path_constraint_chains)*
+                    .collect()
+            }
+
+            fn get_type() -> serialize_hierarchy::TypeDescription {
+                #type_description
+            }
+
+            fn get_types() -> std::collections::BTreeMap<String, serialize_hierarchy::TypeDescription> {
+                std::iter::empty::<(std::string::String, serialize_hierarchy::TypeDescription)>()
+                    #(#type_chains)*
+                    #(#path_type_chains)*
+                    #jpeg_type_chain
+                    .collect()
+            }
         }
     };
     implementation
@@ -273,6 +317,99 @@ fn generate_path_field_chains(fields: &[&Field]) -> Vec<TokenStream> {
         .collect()
 }
 
+fn generate_constraint_chains(fields: &[&Field]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .filter(|field| field.constraints.is_constrained())
+        .map(|field| {
+            let name_string = field.identifier.to_string();
+            let FieldConstraints {
+                minimum,
+                maximum,
+                non_empty,
+                unit,
+            } = &field.constraints;
+            let minimum = option_to_tokens(minimum);
+            let maximum = option_to_tokens(maximum);
+            let unit = option_to_tokens(&unit.as_ref().map(|unit| quote! { #unit.to_string() }));
+            quote! {
+                .chain(std::iter::once((
+                    #name_string.to_string(),
+                    serialize_hierarchy::Constraints {
+                        minimum: #minimum,
+                        maximum: #maximum,
+                        non_empty: #non_empty,
+                        unit: #unit,
+                    },
+                )))
+            }
+        })
+        .collect()
+}
+
+fn generate_path_constraint_chains(fields: &[&Field]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .filter(|field| !field.attributes.contains(&FieldAttribute::Leaf))
+        .map(|field| {
+            let identifier = &field.identifier;
+            let pattern = format!("{identifier}.{{}}");
+            let ty = &field.ty;
+            quote! {
+                .chain(
+                    <#ty as serialize_hierarchy::SerializeHierarchy>::get_constraints()
+                        .into_iter()
+                        .map(|(name, constraints)| (format!(#pattern, name), constraints))
+                )
+            }
+        })
+        .collect()
+}
+
+fn generate_type_chains(fields: &[&Field]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .map(|field| {
+            let name_string = field.identifier.to_string();
+            let ty = &field.ty;
+            let description = if field.attributes.contains(&FieldAttribute::Leaf) {
+                quote! { serialize_hierarchy::TypeDescription::of::<#ty>() }
+            } else {
+                quote! { <#ty as serialize_hierarchy::SerializeHierarchy>::get_type() }
+            };
+            quote! {
+                .chain(std::iter::once((#name_string.to_string(), #description)))
+            }
+        })
+        .collect()
+}
+
+fn generate_path_type_chains(fields: &[&Field]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .filter(|field| !field.attributes.contains(&FieldAttribute::Leaf))
+        .map(|field| {
+            let identifier = &field.identifier;
+            let pattern = format!("{identifier}.{{}}");
+            let ty = &field.ty;
+            quote! {
+                .chain(
+                    <#ty as serialize_hierarchy::SerializeHierarchy>::get_types()
+                        .into_iter()
+                        .map(|(name, description)| (format!(#pattern, name), description))
+                )
+            }
+        })
+        .collect()
+}
+
+fn option_to_tokens(option: &Option<impl ToTokens>) -> TokenStream {
+    match option {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 enum TypeAttribute {
     AsJpeg,
@@ -326,9 +463,24 @@ enum FieldAttribute {
     Leaf,
 }
 
+#[derive(Debug, Default)]
+struct FieldConstraints {
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    non_empty: bool,
+    unit: Option<String>,
+}
+
+impl FieldConstraints {
+    fn is_constrained(&self) -> bool {
+        self.minimum.is_some() || self.maximum.is_some() || self.non_empty || self.unit.is_some()
+    }
+}
+
 #[derive(Debug)]
 struct Field {
     attributes: HashSet<FieldAttribute>,
+    constraints: FieldConstraints,
     identifier: Ident,
     ty: Type,
 }
@@ -349,17 +501,35 @@ fn read_fields(input: &DataStruct) -> Vec<Field> {
         .fields
         .iter()
         .map(|field| {
-            let attributes = field
-                .attrs
-                .iter()
-                .flat_map(parse_meta_items)
-                .map(|meta| match meta {
+            let mut attributes = HashSet::new();
+            let mut constraints = FieldConstraints::default();
+            for meta in field.attrs.iter().flat_map(parse_meta_items) {
+                match meta {
                     NestedMeta::Meta(Meta::Path(word)) if word.is_ident("skip") => {
-                        FieldAttribute::Skip
+                        attributes.insert(FieldAttribute::Skip);
                     }
                     NestedMeta::Meta(Meta::Path(word)) if word.is_ident("leaf") => {
-                        FieldAttribute::Leaf
+                        attributes.insert(FieldAttribute::Leaf);
                     }
+                    NestedMeta::Meta(Meta::Path(word)) if word.is_ident("non_empty") => {
+                        constraints.non_empty = true;
+                    }
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path, lit: literal, ..
+                    })) if path.is_ident("minimum") => {
+                        constraints.minimum = Some(parse_bound(&literal));
+                    }
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path, lit: literal, ..
+                    })) if path.is_ident("maximum") => {
+                        constraints.maximum = Some(parse_bound(&literal));
+                    }
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path, lit: literal, ..
+                    })) if path.is_ident("unit") => match literal {
+                        Lit::Str(unit) => constraints.unit = Some(unit.value()),
+                        _ => abort!(literal, "expected unit to be a string: `unit = \"...\"`"),
+                    },
                     NestedMeta::Meta(meta_item) => {
                         let path = meta_item
                             .path()
@@ -372,8 +542,8 @@ fn read_fields(input: &DataStruct) -> Vec<Field> {
                     NestedMeta::Lit(lit) => {
                         abort!(lit, "unexpected literal in attribute")
                     }
-                })
-                .collect();
+                }
+            }
             let identifier = field
                 .ident
                 .clone()
@@ -381,9 +551,28 @@ fn read_fields(input: &DataStruct) -> Vec<Field> {
             let ty = field.ty.clone();
             Field {
                 attributes,
+                constraints,
                 identifier,
                 ty,
             }
         })
         .collect()
 }
+
+/// Parses bounds given as number (`minimum = 0.5`) or, to allow negative values, as string
+/// (`minimum = "-0.5"`)
+fn parse_bound(literal: &Lit) -> f64 {
+    let bound = match literal {
+        Lit::Int(integer) => integer.base10_parse(),
+        Lit::Float(float) => float.base10_parse(),
+        Lit::Str(string) => string.value().parse().map_err(|error| {
+            syn::Error::new(string.span(), format!("failed to parse bound: {error}"))
+        }),
+        _ => abort!(literal, "expected bound to be a number or a string"),
+    };
+    match bound {
+        Ok(bound) if f64::is_finite(bound) => bound,
+        Ok(_) => abort!(literal, "expected bound to be finite"),
+        Err(error) => abort!(error.span(), error.to_string()),
+    }
+}
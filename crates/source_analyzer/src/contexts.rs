@@ -5,11 +5,12 @@ use color_eyre::{
     Result,
 };
 use syn::{
-    spanned::Spanned, Expr, ExprLit, File, GenericArgument, Ident, Item, Lit, PathArguments, Type,
+    spanned::Spanned, Attribute, Expr, ExprLit, File, GenericArgument, Ident, Item, Lit, Meta,
+    MetaNameValue, NestedMeta, PathArguments, Type,
 };
 
 use crate::{
-    into_eyre_result::new_syn_error_as_eyre_result,
+    into_eyre_result::{new_syn_error_as_eyre_result, SynContext},
     to_absolute::ToAbsolute,
     uses::{uses_from_items, Uses},
 };
@@ -19,6 +20,69 @@ pub struct Contexts {
     pub creation_context: Vec<Field>,
     pub cycle_context: Vec<Field>,
     pub main_outputs: Vec<Field>,
+    pub schedule: Schedule,
+}
+
+/// When to execute a node, given by the arguments of `#[context]` on its `CycleContext`
+///
+/// Nodes skipped in a cycle keep their main outputs of the last cycle.
+#[derive(Debug, Default)]
+pub struct Schedule {
+    /// Execute only in every n-th cycle of the cycler
+    pub every: Option<usize>,
+    /// Function in the node module deciding from the `CycleContext` whether to execute
+    pub run_if: Option<Ident>,
+}
+
+impl Schedule {
+    fn try_from_attribute(file_path: impl AsRef<Path>, attribute: &Attribute) -> Result<Self> {
+        let mut schedule = Self::default();
+        let nested_metas = match attribute.parse_meta().syn_context(&file_path)? {
+            Meta::Path(_) => return Ok(schedule),
+            Meta::List(list) => list.nested,
+            meta => {
+                return new_syn_error_as_eyre_result(
+                    meta.span(),
+                    "expected `#[context]` or `#[context(...)]`",
+                    file_path,
+                )
+            }
+        };
+        for nested_meta in nested_metas {
+            match &nested_meta {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Int(every),
+                    ..
+                })) if path.is_ident("every") => {
+                    let every = every.base10_parse().syn_context(&file_path)?;
+                    if every == 0 {
+                        return new_syn_error_as_eyre_result(
+                            nested_meta.span(),
+                            "expected positive integer",
+                            file_path,
+                        );
+                    }
+                    schedule.every = Some(every);
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(run_if),
+                    ..
+                })) if path.is_ident("run_if") => {
+                    schedule.run_if = Some(run_if.parse().syn_context(&file_path)?);
+                }
+                _ => {
+                    return new_syn_error_as_eyre_result(
+                        nested_meta.span(),
+                        "expected `every = <n>` or `run_if = \"<function>\"`",
+                        file_path,
+                    )
+                }
+            }
+        }
+        Ok(schedule)
+    }
 }
 
 impl Contexts {
@@ -27,6 +91,7 @@ impl Contexts {
         let mut creation_context = vec![];
         let mut cycle_context = vec![];
         let mut main_outputs = vec![];
+        let mut schedule = Schedule::default();
         for item in file.items.iter() {
             match item {
                 Item::Struct(struct_item)
@@ -50,6 +115,13 @@ impl Contexts {
                         }
                         "CycleContext" => {
                             cycle_context.append(&mut fields);
+                            let attribute = struct_item
+                                .attrs
+                                .iter()
+                                .find(|attribute| attribute.path.is_ident("context"))
+                                .unwrap();
+                            schedule = Schedule::try_from_attribute(&file_path, attribute)
+                                .wrap_err("failed to parse schedule")?;
                         }
                         "MainOutputs" => {
                             main_outputs.append(&mut fields);
@@ -71,6 +143,7 @@ impl Contexts {
             creation_context,
             cycle_context,
             main_outputs,
+            schedule,
         })
     }
 }
@@ -1000,4 +1073,27 @@ mod tests {
 
         assert!(matched_cases.into_iter().all(identity));
     }
+
+    #[test]
+    fn schedule_is_parsed_from_cycle_context_attribute() {
+        let file: File =
+            parse_str("#[context(every = 4, run_if = \"is_playing\")] pub struct CycleContext {}")
+                .unwrap();
+        let contexts = Contexts::try_from_file("file_path", &file).unwrap();
+        assert_eq!(contexts.schedule.every, Some(4));
+        assert_eq!(contexts.schedule.run_if.unwrap(), "is_playing");
+
+        let file: File = parse_str("#[context] pub struct CycleContext {}").unwrap();
+        let contexts = Contexts::try_from_file("file_path", &file).unwrap();
+        assert_eq!(contexts.schedule.every, None);
+        assert!(contexts.schedule.run_if.is_none());
+
+        for attribute in ["every = 0", "every = \"4\"", "run_if = 4", "sometimes = 4"] {
+            let file: File = parse_str(&format!(
+                "#[context({attribute})] pub struct CycleContext {{}}"
+            ))
+            .unwrap();
+            assert!(Contexts::try_from_file("file_path", &file).is_err());
+        }
+    }
 }
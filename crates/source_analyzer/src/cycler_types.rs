@@ -1,8 +1,12 @@
 use std::{collections::HashMap, path::Path};
 
-use color_eyre::{eyre::WrapErr, Result};
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use syn::{Expr, ExprLit, File, Item, Lit};
 
-use crate::{CyclerInstances, Field, Nodes};
+use crate::{parse::parse_rust_file, CyclerInstances, Field, Nodes};
 
 #[derive(Debug)]
 pub struct CyclerTypes {
@@ -13,6 +17,8 @@ pub struct CyclerTypes {
 pub enum CyclerType {
     Perception,
     RealTime,
+    /// Declared by `pub const PERIODIC: bool = true;` in the lib.rs of the cycler crate
+    Periodic,
 }
 
 impl CyclerTypes {
@@ -22,7 +28,7 @@ impl CyclerTypes {
         let nodes =
             Nodes::try_from_crates_directory(&crates_directory).wrap_err("failed to get nodes")?;
 
-        Ok(Self {
+        let cycler_types = Self {
             cycler_modules_to_cycler_types: cycler_instances
                 .modules_to_instances
                 .keys()
@@ -39,15 +45,86 @@ impl CyclerTypes {
                                     if &cycler_instances.instances_to_modules[cycler_instance] == cycler_module_name
                                 ))
                         });
-                    (
-                        cycler_module_name.clone(),
-                        match at_least_one_node_uses_this_cycler_module_via_perception_input {
-                            true => CyclerType::Perception,
-                            false => CyclerType::RealTime,
-                        },
-                    )
+                    let rust_file_path = crates_directory
+                        .as_ref()
+                        .join(cycler_module_name)
+                        .join("src/lib.rs");
+                    let is_periodic = is_periodic(&parse_rust_file(&rust_file_path).wrap_err_with(
+                        || format!("failed to parse file {rust_file_path:?}"),
+                    )?);
+                    let cycler_type = match (
+                        is_periodic,
+                        at_least_one_node_uses_this_cycler_module_via_perception_input,
+                    ) {
+                        (true, true) => bail!(
+                            "periodic cycler `{cycler_module_name}` cannot be used via perception inputs"
+                        ),
+                        (true, false) => CyclerType::Periodic,
+                        (false, true) => CyclerType::Perception,
+                        (false, false) => CyclerType::RealTime,
+                    };
+                    Ok((cycler_module_name.clone(), cycler_type))
                 })
-                .collect(),
-        })
+                .collect::<Result<_>>()?,
+        };
+
+        for (node_name, node) in nodes.nodes.iter() {
+            if cycler_types
+                .cycler_modules_to_cycler_types
+                .get(&node.cycler_module)
+                != Some(&CyclerType::Periodic)
+            {
+                continue;
+            }
+            let uses_historic_or_perception_input = node
+                .contexts
+                .creation_context
+                .iter()
+                .chain(node.contexts.cycle_context.iter())
+                .any(|field| {
+                    matches!(
+                        field,
+                        Field::HistoricInput { .. } | Field::PerceptionInput { .. }
+                    )
+                });
+            if uses_historic_or_perception_input {
+                bail!("node `{node_name}` of periodic cycler `{}` cannot use historic or perception inputs", node.cycler_module);
+            }
+        }
+
+        Ok(cycler_types)
+    }
+}
+
+fn is_periodic(file: &File) -> bool {
+    file.items.iter().any(|item| {
+        matches!(
+            item,
+            Item::Const(const_item) if const_item.ident == "PERIODIC" && matches!(
+                &*const_item.expr,
+                Expr::Lit(ExprLit { lit: Lit::Bool(value), .. }) if value.value
+            )
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_str;
+
+    use super::*;
+
+    #[test]
+    fn periodic_is_declared_by_constant() {
+        let periodic_file: File = parse_str(
+            "#[derive(Clone, Copy, Debug)] pub enum CyclerInstance { Strategy } pub const PERIODIC: bool = true;",
+        )
+        .unwrap();
+        assert!(is_periodic(&periodic_file));
+
+        let real_time_file: File =
+            parse_str("pub enum CyclerInstance { Control } pub const PERIODIC: bool = false;")
+                .unwrap();
+        assert!(!is_periodic(&real_time_file));
     }
 }
@@ -3,16 +3,18 @@ mod cycler_crates;
 mod cycler_instances;
 mod cycler_types;
 mod into_eyre_result;
+mod lints;
 mod nodes;
 mod parse;
 mod structs;
 mod to_absolute;
 mod uses;
 
-pub use contexts::{expand_variables_from_path, Contexts, Field, PathSegment};
+pub use contexts::{expand_variables_from_path, Contexts, Field, PathSegment, Schedule};
 pub use cycler_crates::cycler_crates_from_crates_directory;
 pub use cycler_instances::CyclerInstances;
 pub use cycler_types::{CyclerType, CyclerTypes};
+pub use lints::{Allowlist, Finding, Lints};
 pub use nodes::{Node, Nodes};
 pub use parse::parse_rust_file;
 pub use structs::{CyclerStructs, StructHierarchy, Structs};
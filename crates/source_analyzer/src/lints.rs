@@ -0,0 +1,330 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use glob::glob;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{CyclerInstances, Field, Nodes, StructHierarchy, Structs};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Finding {
+    UnusedConfigurationKey { file_path: PathBuf, path: String },
+    UnknownOverridePath { file_path: PathBuf, path: String },
+    UnconsumedMainOutput { node: String, main_output: String },
+}
+
+impl Display for Finding {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Finding::UnusedConfigurationKey { file_path, path } => write!(
+                formatter,
+                "{}: key `{path}` is not read by any node",
+                file_path.display()
+            ),
+            Finding::UnknownOverridePath { file_path, path } => write!(
+                formatter,
+                "{}: overridden path `{path}` does not exist",
+                file_path.display()
+            ),
+            Finding::UnconsumedMainOutput { node, main_output } => write!(
+                formatter,
+                "main output `{main_output}` of node `{node}` is not consumed by any other node"
+            ),
+        }
+    }
+}
+
+/// Findings which are intended, e.g. main outputs only consumed by twix or recordings
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Allowlist {
+    /// Configuration keys or override paths not read by nodes but by other tooling
+    pub configuration_keys: BTreeSet<String>,
+    /// Main outputs not consumed by other nodes, formatted as `<node>.<main output>`
+    pub main_outputs: BTreeSet<String>,
+}
+
+impl Allowlist {
+    pub fn try_from_file(file_path: impl AsRef<Path>) -> Result<Self> {
+        let file_path = file_path.as_ref();
+        if !file_path.exists() {
+            return Ok(Self::default());
+        }
+        let content = read_to_string(file_path)
+            .wrap_err_with(|| format!("failed to read {}", file_path.display()))?;
+        serde_json::from_str(&content)
+            .wrap_err_with(|| format!("failed to parse {}", file_path.display()))
+    }
+
+    fn allows(&self, finding: &Finding) -> bool {
+        match finding {
+            Finding::UnusedConfigurationKey { path, .. }
+            | Finding::UnknownOverridePath { path, .. } => self.configuration_keys.contains(path),
+            Finding::UnconsumedMainOutput { node, main_output } => {
+                self.main_outputs.contains(&format!("{node}.{main_output}"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Lints {
+    pub findings: Vec<Finding>,
+}
+
+impl Lints {
+    pub fn try_from_directories(
+        crates_directory: impl AsRef<Path>,
+        configuration_directory: impl AsRef<Path>,
+        allowlist: &Allowlist,
+    ) -> Result<Self> {
+        let structs = Structs::try_from_crates_directory(&crates_directory)
+            .wrap_err("failed to get structs")?;
+        let cycler_instances = CyclerInstances::try_from_crates_directory(&crates_directory)
+            .wrap_err("failed to get cycler instances")?;
+        let nodes =
+            Nodes::try_from_crates_directory(&crates_directory).wrap_err("failed to get nodes")?;
+
+        let mut findings = Vec::new();
+
+        let default_file_path = configuration_directory.as_ref().join("default.json");
+        for path in unknown_paths(&read_json(&default_file_path)?, &structs.configuration) {
+            findings.push(Finding::UnusedConfigurationKey {
+                file_path: default_file_path.clone(),
+                path,
+            });
+        }
+        for file_path in override_file_paths(configuration_directory)? {
+            for path in unknown_paths(&read_json(&file_path)?, &structs.configuration) {
+                findings.push(Finding::UnknownOverridePath {
+                    file_path: file_path.clone(),
+                    path,
+                });
+            }
+        }
+
+        let main_outputs_to_nodes: BTreeMap<_, _> = nodes
+            .nodes
+            .iter()
+            .flat_map(|(node_name, node)| {
+                node.contexts
+                    .main_outputs
+                    .iter()
+                    .filter_map(move |field| match field {
+                        Field::MainOutput { name, .. } => {
+                            Some(((node.cycler_module.clone(), name.to_string()), node_name))
+                        }
+                        _ => None,
+                    })
+            })
+            .collect();
+        let mut consumed_main_outputs = BTreeSet::new();
+        for (consumer, node) in nodes.nodes.iter() {
+            for field in node
+                .contexts
+                .creation_context
+                .iter()
+                .chain(node.contexts.cycle_context.iter())
+            {
+                let (cycler_instance, path) = match field {
+                    Field::HistoricInput { path, .. } => (None, path),
+                    Field::Input {
+                        cycler_instance,
+                        path,
+                        ..
+                    }
+                    | Field::RequiredInput {
+                        cycler_instance,
+                        path,
+                        ..
+                    } => (cycler_instance.as_ref(), path),
+                    Field::PerceptionInput {
+                        cycler_instance,
+                        path,
+                        ..
+                    } => (Some(cycler_instance), path),
+                    _ => continue,
+                };
+                let cycler_module = match cycler_instance {
+                    Some(cycler_instance) => cycler_instances
+                        .instances_to_modules
+                        .get(cycler_instance)
+                        .ok_or_else(|| eyre!("unknown cycler instance `{cycler_instance}`"))?,
+                    None => &node.cycler_module,
+                };
+                let Some(first_segment) = path.first() else {
+                    continue;
+                };
+                let key = (cycler_module.clone(), first_segment.name.clone());
+                if main_outputs_to_nodes.get(&key) != Some(&consumer) {
+                    consumed_main_outputs.insert(key);
+                }
+            }
+        }
+        for (key, node) in main_outputs_to_nodes.iter() {
+            if !consumed_main_outputs.contains(key) {
+                findings.push(Finding::UnconsumedMainOutput {
+                    node: node.to_string(),
+                    main_output: key.1.clone(),
+                });
+            }
+        }
+
+        findings.retain(|finding| !allowlist.allows(finding));
+
+        Ok(Self { findings })
+    }
+}
+
+fn read_json(file_path: &Path) -> Result<Value> {
+    let content = read_to_string(file_path)
+        .wrap_err_with(|| format!("failed to read {}", file_path.display()))?;
+    serde_json::from_str(&content)
+        .wrap_err_with(|| format!("failed to parse {}", file_path.display()))
+}
+
+fn override_file_paths(configuration_directory: impl AsRef<Path>) -> Result<BTreeSet<PathBuf>> {
+    let mut file_paths = BTreeSet::new();
+    for pattern in [
+        "head.*.json",
+        "body.*.json",
+        "*/default.json",
+        "*/head.*.json",
+        "*/body.*.json",
+    ] {
+        let pattern = configuration_directory.as_ref().join(pattern);
+        for file_path in glob(&pattern.to_string_lossy()).wrap_err("failed to glob")? {
+            let file_path = file_path.wrap_err("failed to get globbed path")?;
+            // location symlinks would otherwise report the same file twice
+            if file_path
+                .parent()
+                .map_or(false, |parent| parent.is_symlink())
+            {
+                continue;
+            }
+            file_paths.insert(file_path);
+        }
+    }
+    Ok(file_paths)
+}
+
+/// Paths of all keys in the value not covered by the hierarchy, without descending into them
+fn unknown_paths(value: &Value, hierarchy: &StructHierarchy) -> Vec<String> {
+    let mut paths = Vec::new();
+    collect_unknown_paths(value, hierarchy, "", &mut paths);
+    paths
+}
+
+fn collect_unknown_paths(
+    value: &Value,
+    hierarchy: &StructHierarchy,
+    prefix: &str,
+    paths: &mut Vec<String>,
+) {
+    match (value, hierarchy) {
+        (_, StructHierarchy::Field { .. }) => {}
+        (_, StructHierarchy::Optional { child }) => {
+            collect_unknown_paths(value, child, prefix, paths)
+        }
+        (Value::Object(object), StructHierarchy::Struct { fields }) => {
+            for (key, value) in object {
+                let path = match prefix.is_empty() {
+                    true => key.clone(),
+                    false => format!("{prefix}.{key}"),
+                };
+                match fields.get(key) {
+                    Some(hierarchy) => collect_unknown_paths(value, hierarchy, &path, paths),
+                    None => paths.push(path),
+                }
+            }
+        }
+        // type mismatches are reported when deserializing the configuration
+        (_, StructHierarchy::Struct { .. }) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn keys_outside_of_hierarchy_are_unknown() {
+        let hierarchy = StructHierarchy::Struct {
+            fields: BTreeMap::from_iter([
+                (
+                    "a".to_string(),
+                    StructHierarchy::Struct {
+                        fields: BTreeMap::from_iter([(
+                            "b".to_string(),
+                            StructHierarchy::Field {
+                                data_type: parse_quote! { f32 },
+                            },
+                        )]),
+                    },
+                ),
+                (
+                    "c".to_string(),
+                    StructHierarchy::Optional {
+                        child: Box::new(StructHierarchy::Struct {
+                            fields: BTreeMap::from_iter([(
+                                "d".to_string(),
+                                StructHierarchy::Field {
+                                    data_type: parse_quote! { Foo },
+                                },
+                            )]),
+                        }),
+                    },
+                ),
+            ]),
+        };
+        let value = json!({
+            "a": { "b": 1.0, "e": 2.0 },
+            "c": { "d": { "anything": "goes" }, "f": null },
+            "g": { "h": true },
+        });
+
+        assert_eq!(
+            unknown_paths(&value, &hierarchy),
+            vec!["a.e".to_string(), "c.f".to_string(), "g".to_string()]
+        );
+        assert!(unknown_paths(&json!({ "c": null }), &hierarchy).is_empty());
+    }
+
+    #[test]
+    fn allowlisted_findings_are_allowed() {
+        let allowlist: Allowlist = serde_json::from_value(json!({
+            "configuration_keys": ["a.b"],
+            "main_outputs": ["Node.output"],
+        }))
+        .unwrap();
+
+        assert!(allowlist.allows(&Finding::UnknownOverridePath {
+            file_path: PathBuf::from("head.json"),
+            path: "a.b".to_string(),
+        }));
+        assert!(!allowlist.allows(&Finding::UnusedConfigurationKey {
+            file_path: PathBuf::from("default.json"),
+            path: "a".to_string(),
+        }));
+        assert!(allowlist.allows(&Finding::UnconsumedMainOutput {
+            node: "Node".to_string(),
+            main_output: "output".to_string(),
+        }));
+        assert!(!allowlist.allows(&Finding::UnconsumedMainOutput {
+            node: "OtherNode".to_string(),
+            main_output: "output".to_string(),
+        }));
+    }
+}
@@ -7,11 +7,13 @@ use color_eyre::{
 use convert_case::{Case, Casing};
 use quote::{format_ident, ToTokens};
 use syn::{
-    punctuated::Punctuated, AngleBracketedGenericArguments, GenericArgument, PathArguments, Type,
-    TypePath,
+    parse_quote, punctuated::Punctuated, AngleBracketedGenericArguments, GenericArgument,
+    PathArguments, Type, TypePath,
 };
 
-use crate::{expand_variables_from_path, CyclerInstances, Field, Nodes, PathSegment};
+use crate::{
+    expand_variables_from_path, CyclerInstances, CyclerType, CyclerTypes, Field, Nodes, PathSegment,
+};
 
 #[derive(Debug, Default)]
 pub struct Structs {
@@ -27,6 +29,8 @@ impl Structs {
             .wrap_err("failed to get cycler instances")?;
         let nodes =
             Nodes::try_from_crates_directory(&crates_directory).wrap_err("failed to get nodes")?;
+        let cycler_types = CyclerTypes::try_from_crates_directory(&crates_directory)
+            .wrap_err("failed to get cycler types")?;
 
         for (cycler_module, node_names) in nodes.cycler_modules_to_nodes.iter() {
             let cycler_structs = structs
@@ -178,6 +182,90 @@ impl Structs {
             }
         }
 
+        for cycler_instance in cycler_instances.modules_to_instances.values().flatten() {
+            let recording_parameters: [(&str, Type); 2] = [
+                ("enabled", parse_quote! { bool }),
+                ("additional_outputs", parse_quote! { Vec<String> }),
+            ];
+            for (name, data_type) in recording_parameters {
+                let path = [
+                    PathSegment::from("recording"),
+                    PathSegment::from(cycler_instance.to_case(Case::Snake).as_str()),
+                    PathSegment::from(name),
+                ];
+                let insertion_rules = path_to_insertion_rules(&path, &data_type);
+                structs
+                    .configuration
+                    .insert(insertion_rules)
+                    .wrap_err_with(|| {
+                        format!("failed to insert recording parameter `{name}` of cycler instance `{cycler_instance}` into configuration")
+                    })?;
+            }
+            let path = [
+                PathSegment::from("cycle_timings"),
+                PathSegment::from(cycler_instance.to_case(Case::Snake).as_str()),
+                PathSegment::from("budget"),
+            ];
+            let insertion_rules =
+                path_to_insertion_rules(&path, &parse_quote! { Option<std::time::Duration> });
+            structs
+                .configuration
+                .insert(insertion_rules)
+                .wrap_err_with(|| {
+                    format!("failed to insert cycle timing budget of cycler instance `{cycler_instance}` into configuration")
+                })?;
+        }
+
+        for (cycler_module, cycler_type) in cycler_types.cycler_modules_to_cycler_types.iter() {
+            if *cycler_type != CyclerType::Periodic {
+                continue;
+            }
+            for cycler_instance in &cycler_instances.modules_to_instances[cycler_module] {
+                let path = [
+                    PathSegment::from("cycle_periods"),
+                    PathSegment::from(cycler_instance.to_case(Case::Snake).as_str()),
+                ];
+                let insertion_rules =
+                    path_to_insertion_rules(&path, &parse_quote! { std::time::Duration });
+                structs
+                    .configuration
+                    .insert(insertion_rules)
+                    .wrap_err_with(|| {
+                        format!("failed to insert cycle period of cycler instance `{cycler_instance}` into configuration")
+                    })?;
+            }
+        }
+
+        for (cycler_module, node_names) in nodes.cycler_modules_to_nodes.iter() {
+            for cycler_instance in &cycler_instances.modules_to_instances[cycler_module] {
+                for node_name in node_names {
+                    let path = [
+                        PathSegment::from("nodes"),
+                        PathSegment::from(cycler_instance.to_case(Case::Snake).as_str()),
+                        PathSegment::from(node_name.to_case(Case::Snake).as_str()),
+                    ];
+                    let insertion_rules =
+                        path_to_insertion_rules(&path, &parse_quote! { types::NodeSettings });
+                    structs
+                        .configuration
+                        .insert(insertion_rules)
+                        .wrap_err_with(|| {
+                            format!("failed to insert settings of node `{node_name}` of cycler instance `{cycler_instance}` into configuration")
+                        })?;
+                }
+            }
+        }
+
+        for cycler_structs in structs.cycler_structs.values_mut() {
+            let path = [PathSegment::from("cycle_timings")];
+            let insertion_rules =
+                path_to_insertion_rules(&path, &parse_quote! { Option<types::CycleTimings> });
+            cycler_structs
+                .additional_outputs
+                .insert(insertion_rules)
+                .wrap_err("failed to insert cycle timings into additional outputs")?;
+        }
+
         Ok(structs)
     }
 }
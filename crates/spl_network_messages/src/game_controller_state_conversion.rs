@@ -231,6 +231,24 @@ impl Penalty {
     pub fn is_none(&self) -> bool {
         matches!(self, Penalty::None)
     }
+
+    pub fn remaining_mut(&mut self) -> Option<&mut Duration> {
+        match self {
+            Penalty::None => None,
+            Penalty::IllegalBallContact { remaining }
+            | Penalty::PlayerPushing { remaining }
+            | Penalty::IllegalMotionInSet { remaining }
+            | Penalty::InactivePlayer { remaining }
+            | Penalty::IllegalPosition { remaining }
+            | Penalty::LeavingTheField { remaining }
+            | Penalty::RequestForPickup { remaining }
+            | Penalty::LocalGameStuck { remaining }
+            | Penalty::IllegalPositionInSet { remaining }
+            | Penalty::PlayerStance { remaining }
+            | Penalty::Substitute { remaining }
+            | Penalty::Manual { remaining } => Some(remaining),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, SerializeHierarchy)]
@@ -10,6 +10,9 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use source_analyzer::{cycler_crates_from_crates_directory, StructHierarchy, Structs};
 
+/// Fields of the configuration which are filled with defaults if missing, e.g. generated node settings
+const DEFAULTED_CONFIGURATION_FIELDS: [&str; 1] = ["nodes"];
+
 fn main() -> Result<()> {
     for crate_directory in cycler_crates_from_crates_directory("..")
         .wrap_err("failed to get cycler crate directories from crates directory")?
@@ -26,6 +29,7 @@ fn main() -> Result<()> {
                 "Configuration",
                 fields,
                 quote! { #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, serialize_hierarchy::SerializeHierarchy)] },
+                &DEFAULTED_CONFIGURATION_FIELDS,
             )
             .wrap_err("failed to generate struct `Configuration`")?;
             quote! {
@@ -35,6 +39,7 @@ fn main() -> Result<()> {
         StructHierarchy::Optional { .. } => bail!("unexpected optional variant as root-struct"),
         StructHierarchy::Field { .. } => bail!("unexpected field variant as root-struct"),
     };
+    // recorded databases of older code lack new outputs and are filled with defaults when shown
     let cyclers = structs
         .cycler_structs
         .iter()
@@ -44,7 +49,11 @@ fn main() -> Result<()> {
                 StructHierarchy::Struct { fields } => struct_hierarchy_to_token_stream(
                     "MainOutputs",
                     fields,
-                    quote! { #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, serialize_hierarchy::SerializeHierarchy)] },
+                    quote! {
+                        #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, serialize_hierarchy::SerializeHierarchy)]
+                        #[serde(default)]
+                    },
+                    &[],
                 )
                 .wrap_err("failed to generate struct `MainOutputs`")?,
                 StructHierarchy::Optional { .. } => {
@@ -56,7 +65,11 @@ fn main() -> Result<()> {
                 StructHierarchy::Struct { fields } => struct_hierarchy_to_token_stream(
                     "AdditionalOutputs",
                     fields,
-                    quote! { #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, serialize_hierarchy::SerializeHierarchy)] },
+                    quote! {
+                        #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, serialize_hierarchy::SerializeHierarchy)]
+                        #[serde(default)]
+                    },
+                    &[],
                 )
                 .wrap_err("failed to generate struct `AdditionalOutputs`")?,
                 StructHierarchy::Optional { .. } => {
@@ -69,6 +82,7 @@ fn main() -> Result<()> {
                     "PersistentState",
                     fields,
                     quote! { #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, serialize_hierarchy::SerializeHierarchy)] },
+                    &[],
                 )
                 .wrap_err("failed to generate struct `PersistentState`")?,
                 StructHierarchy::Optional { .. } => {
@@ -102,33 +116,38 @@ fn struct_hierarchy_to_token_stream(
     struct_name: &str,
     fields: &BTreeMap<String, StructHierarchy>,
     derives: TokenStream,
+    defaulted_fields: &[&str],
 ) -> Result<TokenStream> {
     let struct_name_identifier = format_ident!("{}", struct_name);
     let struct_fields: Vec<_> = fields
         .iter()
         .map(|(name, struct_hierarchy)| {
             let name_identifier = format_ident!("{}", name);
+            let attributes = match defaulted_fields.contains(&name.as_str()) {
+                true => quote! { #[serde(default)] },
+                false => quote! {},
+            };
             match struct_hierarchy {
                 StructHierarchy::Struct { .. } => {
                     let struct_name_identifier =
                         format_ident!("{}{}", struct_name, name.to_case(Case::Pascal));
-                    Ok(quote! { pub #name_identifier: #struct_name_identifier })
+                    Ok(quote! { #attributes pub #name_identifier: #struct_name_identifier })
                 }
                 StructHierarchy::Optional { child } => match &**child {
                     StructHierarchy::Struct { .. } => {
                         let struct_name_identifier =
                             format_ident!("{}{}", struct_name, name.to_case(Case::Pascal));
-                        Ok(quote! { pub #name_identifier: Option<#struct_name_identifier> })
+                        Ok(quote! { #attributes pub #name_identifier: Option<#struct_name_identifier> })
                     }
                     StructHierarchy::Optional { .. } => {
                         bail!("unexpected optional in an optional struct")
                     }
                     StructHierarchy::Field { data_type } => {
-                        Ok(quote! { pub #name_identifier: Option<#data_type> })
+                        Ok(quote! { #attributes pub #name_identifier: Option<#data_type> })
                     }
                 },
                 StructHierarchy::Field { data_type } => {
-                    Ok(quote! { pub #name_identifier: #data_type })
+                    Ok(quote! { #attributes pub #name_identifier: #data_type })
                 }
             }
         })
@@ -136,24 +155,31 @@ fn struct_hierarchy_to_token_stream(
         .wrap_err("failed to generate struct fields")?;
     let child_structs: Vec<_> = fields
         .iter()
-        .map(|(name, struct_hierarchy)| match struct_hierarchy {
-            StructHierarchy::Struct { fields } => {
-                let struct_name = format!("{}{}", struct_name, name.to_case(Case::Pascal));
-                struct_hierarchy_to_token_stream(&struct_name, fields, derives.clone())
-                    .wrap_err_with(|| format!("failed to generate struct `{struct_name}`"))
-            }
-            StructHierarchy::Optional { child } => match &**child {
+        .map(|(name, struct_hierarchy)| {
+            // children of defaulted fields are filled with defaults as well
+            let derives = match defaulted_fields.contains(&name.as_str()) {
+                true => quote! { #derives #[serde(default)] },
+                false => derives.clone(),
+            };
+            match struct_hierarchy {
                 StructHierarchy::Struct { fields } => {
                     let struct_name = format!("{}{}", struct_name, name.to_case(Case::Pascal));
-                    struct_hierarchy_to_token_stream(&struct_name, fields, derives.clone())
+                    struct_hierarchy_to_token_stream(&struct_name, fields, derives, &[])
                         .wrap_err_with(|| format!("failed to generate struct `{struct_name}`"))
                 }
-                StructHierarchy::Optional { .. } => {
-                    bail!("unexpected optional in an optional struct")
-                }
+                StructHierarchy::Optional { child } => match &**child {
+                    StructHierarchy::Struct { fields } => {
+                        let struct_name = format!("{}{}", struct_name, name.to_case(Case::Pascal));
+                        struct_hierarchy_to_token_stream(&struct_name, fields, derives, &[])
+                            .wrap_err_with(|| format!("failed to generate struct `{struct_name}`"))
+                    }
+                    StructHierarchy::Optional { .. } => {
+                        bail!("unexpected optional in an optional struct")
+                    }
+                    StructHierarchy::Field { .. } => Ok(Default::default()),
+                },
                 StructHierarchy::Field { .. } => Ok(Default::default()),
-            },
-            StructHierarchy::Field { .. } => Ok(Default::default()),
+            }
         })
         .collect::<Result<_, _>>()
         .wrap_err("failed to generate child structs")?;
@@ -9,6 +9,7 @@ pub enum Action {
     Initial,
     FallSafely,
     StandUp,
+    SafeStand,
     Stand,
     LookAround,
     Calibrate,
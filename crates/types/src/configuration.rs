@@ -17,7 +17,9 @@ pub struct Audio {
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct WhistleDetection {
+    #[serialize_hierarchy(unit = "Hz")]
     pub detection_band: Range<f32>,
+    #[serialize_hierarchy(minimum = 0)]
     pub background_noise_scaling: f32,
     pub whistle_scaling: f32,
     pub number_of_chunks: usize,
@@ -370,11 +372,16 @@ pub struct FieldColorDetection {
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct BallDetection {
     pub minimal_radius: f32,
+    #[serialize_hierarchy(non_empty)]
     pub preclassifier_neural_network: PathBuf,
+    #[serialize_hierarchy(non_empty)]
     pub classifier_neural_network: PathBuf,
+    #[serialize_hierarchy(non_empty)]
     pub positioner_neural_network: PathBuf,
     pub maximum_number_of_candidate_evaluations: usize,
+    #[serialize_hierarchy(minimum = 0, maximum = 1)]
     pub preclassifier_confidence_threshold: f32,
+    #[serialize_hierarchy(minimum = 0, maximum = 1)]
     pub classifier_confidence_threshold: f32,
     pub confidence_merge_factor: f32,
     pub correction_proximity_merge_factor: f32,
@@ -437,18 +444,27 @@ pub struct ObstacleFilter {
     pub robot_detection_measurement_matching_distance: f32,
     pub goal_post_measurement_matching_distance: f32,
     pub hypothesis_merge_distance: f32,
+    #[serialize_hierarchy(minimum = 0)]
     pub process_noise: Vector2<f32>,
+    #[serialize_hierarchy(minimum = 0)]
     pub feet_measurement_noise: Vector2<f32>,
+    #[serialize_hierarchy(minimum = 0)]
     pub robot_measurement_noise: Vector2<f32>,
+    #[serialize_hierarchy(minimum = 0)]
     pub sonar_measurement_noise: Vector2<f32>,
+    #[serialize_hierarchy(minimum = 0)]
     pub network_robot_measurement_noise: Vector2<f32>,
+    #[serialize_hierarchy(minimum = 0)]
     pub initial_covariance: Vector2<f32>,
     pub measurement_count_threshold: usize,
     pub use_feet_detection_measurements: bool,
     pub use_robot_detection_measurements: bool,
     pub use_sonar_measurements: bool,
+    #[serialize_hierarchy(minimum = 0, unit = "m")]
     pub robot_obstacle_radius_at_hip_height: f32,
+    #[serialize_hierarchy(minimum = 0, unit = "m")]
     pub robot_obstacle_radius_at_foot_height: f32,
+    #[serialize_hierarchy(minimum = 0, unit = "m")]
     pub unknown_obstacle_radius: f32,
     pub goal_post_obstacle_radius: f32,
 }
@@ -514,3 +530,10 @@ pub struct RobotDetection {
 pub struct PenaltyShotDirectionEstimation {
     pub moving_distance_threshold: f32,
 }
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct Watchdog {
+    pub maximum_control_cycle_period: Duration,
+    pub maximum_consecutive_overruns: usize,
+    pub maximum_perception_data_age: Duration,
+}
@@ -0,0 +1,134 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+
+const HISTOGRAM_BIN_WIDTH: Duration = Duration::from_micros(500);
+// the last bin collects all durations of 20 ms and longer
+const NUMBER_OF_HISTOGRAM_BINS: usize = 40;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct Timing {
+    pub last: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+    pub number_of_samples: u64,
+    /// Number of samples per 0.5 ms bin
+    pub histogram: Vec<u64>,
+}
+
+impl Timing {
+    pub fn record(&mut self, duration: Duration) {
+        self.last = duration;
+        self.max = self.max.max(duration);
+        self.number_of_samples += 1;
+        let mean = self.mean.as_secs_f64();
+        self.mean = Duration::from_secs_f64(
+            mean + (duration.as_secs_f64() - mean) / self.number_of_samples as f64,
+        );
+        if self.histogram.is_empty() {
+            self.histogram = vec![0; NUMBER_OF_HISTOGRAM_BINS];
+        }
+        let bin = (duration.as_nanos() / HISTOGRAM_BIN_WIDTH.as_nanos()) as usize;
+        self.histogram[bin.min(NUMBER_OF_HISTOGRAM_BINS - 1)] += 1;
+    }
+}
+
+/// Durations of a whole cycle and of every node executed in it
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct CycleTimings {
+    pub cycle: Timing,
+    #[serialize_hierarchy(leaf)]
+    pub nodes: BTreeMap<String, Timing>,
+}
+
+impl CycleTimings {
+    pub fn record_node(&mut self, node_name: &str, duration: Duration) {
+        match self.nodes.get_mut(node_name) {
+            Some(timing) => timing.record(duration),
+            None => {
+                let mut timing = Timing::default();
+                timing.record(duration);
+                self.nodes.insert(node_name.to_string(), timing);
+            }
+        }
+    }
+
+    pub fn record_cycle(&mut self, duration: Duration) {
+        self.cycle.record(duration);
+    }
+}
+
+/// Counts cycles exceeding their budget to warn about them at most once per interval
+#[derive(Debug, Default)]
+pub struct BudgetExceedances {
+    amount_since_last_report: usize,
+    last_report: Option<Instant>,
+}
+
+impl BudgetExceedances {
+    /// Returns the amount of exceeding cycles since the last report if it is due
+    pub fn count(&mut self, now: Instant, interval: Duration) -> Option<usize> {
+        self.amount_since_last_report += 1;
+        let report_is_due = match self.last_report {
+            Some(last_report) => now.duration_since(last_report) >= interval,
+            None => true,
+        };
+        if !report_is_due {
+            return None;
+        }
+        self.last_report = Some(now);
+        Some(std::mem::take(&mut self.amount_since_last_report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timing_accumulates_statistics() {
+        let mut timing = Timing::default();
+        timing.record(Duration::from_millis(2));
+        timing.record(Duration::from_millis(6));
+        timing.record(Duration::from_millis(4));
+
+        assert_eq!(timing.last, Duration::from_millis(4));
+        assert_eq!(timing.max, Duration::from_millis(6));
+        assert_eq!(timing.number_of_samples, 3);
+        assert!((timing.mean.as_secs_f64() - 0.004).abs() < 1e-9);
+        assert_eq!(timing.histogram.len(), NUMBER_OF_HISTOGRAM_BINS);
+        assert_eq!(timing.histogram[4], 1);
+        assert_eq!(timing.histogram[8], 1);
+        assert_eq!(timing.histogram[12], 1);
+    }
+
+    #[test]
+    fn budget_exceedances_are_reported_once_per_interval() {
+        let mut exceedances = BudgetExceedances::default();
+        let start = Instant::now();
+        let interval = Duration::from_secs(1);
+        let reports: Vec<_> = [0, 10, 20, 990, 1000, 1500, 2500]
+            .into_iter()
+            .map(|milliseconds| {
+                exceedances.count(start + Duration::from_millis(milliseconds), interval)
+            })
+            .collect();
+
+        assert_eq!(reports, [Some(1), None, None, None, Some(4), None, Some(2)]);
+    }
+
+    #[test]
+    fn long_durations_end_up_in_last_histogram_bin() {
+        let mut timings = CycleTimings::default();
+        timings.record_node("image_segmenter", Duration::from_millis(50));
+
+        assert_eq!(
+            timings.nodes["image_segmenter"].histogram[NUMBER_OF_HISTOGRAM_BINS - 1],
+            1
+        );
+    }
+}
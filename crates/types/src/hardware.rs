@@ -1,6 +1,7 @@
 use std::{fmt::Debug, time::SystemTime};
 
 use color_eyre::Result;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     messages::{IncomingMessage, OutgoingMessage},
@@ -29,7 +30,7 @@ pub trait Interface {
     fn read_from_camera(&self, camera_position: CameraPosition) -> Result<YCbCr422Image>;
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Ids {
     pub body_id: String,
     pub head_id: String,
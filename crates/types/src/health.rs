@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum MonitoredCycler {
+    Control,
+    Audio,
+    VisionTop,
+    VisionBottom,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum HealthFailure {
+    /// The control cycler missed its deadline several cycles in a row
+    CycleOverrun {
+        cycler: MonitoredCycler,
+        cycle_period: Duration,
+    },
+    /// A perception cycler did not deliver data for too long
+    Starvation {
+        cycler: MonitoredCycler,
+        data_age: Duration,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct CyclerHealth {
+    /// Time between the starts of the last two cycles
+    pub cycle_period: Duration,
+    /// Time since the cycler last delivered data
+    pub data_age: Duration,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct Health {
+    pub control: CyclerHealth,
+    pub audio: CyclerHealth,
+    pub vision_top: CyclerHealth,
+    pub vision_bottom: CyclerHealth,
+    pub failures: Vec<HealthFailure>,
+}
+
+impl Health {
+    pub fn is_healthy(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
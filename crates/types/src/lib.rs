@@ -10,6 +10,7 @@ mod color;
 pub mod condition_input;
 pub mod configuration;
 mod cycle_time;
+mod cycle_timings;
 pub mod detected_feet;
 pub mod detected_robots;
 mod fall_state;
@@ -24,6 +25,7 @@ mod game_controller_state;
 mod geometry;
 pub mod grayscale_image;
 pub mod hardware;
+mod health;
 pub mod horizon;
 mod image_segments;
 mod initial_pose;
@@ -41,6 +43,7 @@ pub mod messages;
 mod motion_command;
 mod motion_selection;
 pub mod multivariate_normal_distribution;
+mod node_settings;
 pub mod obstacle_filter;
 mod obstacles;
 pub mod orientation_filter;
@@ -64,6 +67,7 @@ mod sonar_values;
 mod step_adjustment;
 mod step_plan;
 mod support_foot;
+mod timeline_marker;
 mod walk_command;
 mod whistle;
 mod world_state;
@@ -80,6 +84,7 @@ pub use camera_position::CameraPosition;
 pub use color::{Intensity, Rgb, RgbChannel, YCbCr422, YCbCr444};
 pub use condition_input::ConditionInput;
 pub use cycle_time::CycleTime;
+pub use cycle_timings::{BudgetExceedances, CycleTimings, Timing};
 pub use fall_state::FallState;
 pub use field_border::FieldBorder;
 pub use field_color::FieldColor;
@@ -94,6 +99,7 @@ pub use game_controller_state::GameControllerState;
 pub use geometry::{
     rotate_towards, Arc, Circle, LineSegment, Orientation, Rectangle, TwoLineSegments,
 };
+pub use health::{CyclerHealth, Health, HealthFailure, MonitoredCycler};
 pub use image_segments::{EdgeType, ImageSegments, ScanGrid, ScanLine, Segment};
 pub use initial_pose::InitialPose;
 pub use joints::{
@@ -113,6 +119,7 @@ pub use motion_command::{
     KickVariant, MotionCommand, OrientationMode, SitDirection,
 };
 pub use motion_selection::{MotionSafeExits, MotionSelection, MotionType};
+pub use node_settings::{DisabledNodeOutputs, NodeSettings};
 pub use obstacles::{Obstacle, ObstacleKind};
 pub use path_obstacles::{PathObstacle, PathObstacleShape};
 pub use penalty_shot_direction::PenaltyShotDirection;
@@ -136,6 +143,7 @@ pub use sonar_values::SonarValues;
 pub use step_adjustment::StepAdjustment;
 pub use step_plan::Step;
 pub use support_foot::{Side, SupportFoot};
+pub use timeline_marker::{TimelineMarker, TimelineMarkerDetector, TimelineMarkerKind};
 pub use walk_command::WalkCommand;
 pub use whistle::{DetectionInfo, Whistle};
 pub use world_state::{BallState, RobotState, WorldState};
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+
+/// Runtime settings of a single node, generated for every node as `nodes.<cycler_instance>.<node>`
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SerializeHierarchy)]
+#[serde(default)]
+pub struct NodeSettings {
+    pub enabled: bool,
+    pub outputs_when_disabled: DisabledNodeOutputs,
+    /// Incrementing this re-creates the node from its `CreationContext`
+    pub recreations: usize,
+}
+
+impl Default for NodeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            outputs_when_disabled: Default::default(),
+            recreations: 0,
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, SerializeHierarchy,
+)]
+pub enum DisabledNodeOutputs {
+    #[default]
+    KeepLast,
+    Default,
+}
@@ -1,12 +1,12 @@
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     iter::once,
     ops::{Index, IndexMut},
 };
 
 use color_eyre::Result;
 use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
-use serialize_hierarchy::{Error, SerializeHierarchy};
+use serialize_hierarchy::{Error, SerializeHierarchy, TypeDescription};
 use spl_network_messages::{Penalty, Player, PlayerNumber, TeamState};
 
 #[derive(Clone, Copy, Default, Debug, Deserialize, Serialize)]
@@ -257,4 +257,20 @@ where
             )
             .collect()
     }
+
+    fn get_type() -> TypeDescription {
+        TypeDescription::Struct
+    }
+
+    fn get_types() -> BTreeMap<String, TypeDescription> {
+        ["one", "two", "three", "four", "five"]
+            .into_iter()
+            .flat_map(|player| {
+                T::get_types()
+                    .into_iter()
+                    .map(move |(name, description)| (format!("{player}.{name}"), description))
+                    .chain(once((player.to_string(), T::get_type())))
+            })
+            .collect()
+    }
 }
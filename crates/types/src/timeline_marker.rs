@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use spl_network_messages::{GameState, Penalty};
+
+use crate::{FallState, GameControllerState, Players};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum TimelineMarkerKind {
+    GameStateChange,
+    Fall,
+    Penalty,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TimelineMarker<Position> {
+    pub position: Position,
+    pub kind: TimelineMarkerKind,
+    pub description: String,
+}
+
+#[derive(Default)]
+pub struct TimelineMarkerDetector {
+    last_game_state: Option<GameState>,
+    penalized: Players<bool>,
+    fallen: bool,
+}
+
+impl TimelineMarkerDetector {
+    pub fn detect_game_controller_changes<Position>(
+        &mut self,
+        position: Position,
+        game_controller_state: Option<&GameControllerState>,
+        markers: &mut Vec<TimelineMarker<Position>>,
+    ) where
+        Position: Copy,
+    {
+        let game_controller_state = match game_controller_state {
+            Some(game_controller_state) => game_controller_state,
+            None => return,
+        };
+
+        if self.last_game_state != Some(game_controller_state.game_state) {
+            markers.push(TimelineMarker {
+                position,
+                kind: TimelineMarkerKind::GameStateChange,
+                description: format!("{:?}", game_controller_state.game_state),
+            });
+            self.last_game_state = Some(game_controller_state.game_state);
+        }
+
+        let mut penalized = Players::<bool>::default();
+        for (player_number, penalty) in game_controller_state.penalties.iter() {
+            penalized[player_number] = !matches!(penalty, Penalty::None);
+            if penalized[player_number] && !self.penalized[player_number] {
+                markers.push(TimelineMarker {
+                    position,
+                    kind: TimelineMarkerKind::Penalty,
+                    description: format!("Player {player_number}: {penalty:?}"),
+                });
+            }
+        }
+        self.penalized = penalized;
+    }
+
+    pub fn detect_falls<Position>(
+        &mut self,
+        position: Position,
+        fall_state: FallState,
+        markers: &mut Vec<TimelineMarker<Position>>,
+    ) {
+        let fallen = !matches!(fall_state, FallState::Upright);
+        if fallen && !self.fallen {
+            markers.push(TimelineMarker {
+                position,
+                kind: TimelineMarkerKind::Fall,
+                description: format!("{fall_state:?}"),
+            });
+        }
+        self.fallen = fallen;
+    }
+}
@@ -0,0 +1,61 @@
+function spawn_robot(number)
+    table.insert(state.robots, create_robot(number))
+end
+
+spawn_robot(1)
+spawn_robot(2)
+spawn_robot(3)
+spawn_robot(4)
+spawn_robot(5)
+
+state.game_controller = {
+    kicking_team = "Hulks",
+}
+
+state.ball = {
+    position = { 0.0, 0.0 },
+    velocity = { 0.0, 0.0 },
+}
+
+local goals = 0
+
+function on_goal()
+    print("Goal scored, resetting ball!")
+    goals = goals + 1
+    state.ball = {
+        position = { 0.0, 0.0 },
+        velocity = { 0.0, 0.0 },
+    }
+end
+
+function on_cycle()
+    if state.cycle_count == 100 then
+        game_controller("Ready")
+    end
+
+    if state.cycle_count == 6000 then
+        game_controller({ Penalize = { player_number = 3, penalty = { PlayerPushing = { remaining = { secs = 45, nanos = 0 } } } } })
+    end
+
+    if state.cycle_count == 8000 then
+        game_controller({ SetPlay = { sub_state = "KickIn", kicking_team = "Hulks" } })
+    end
+
+    if goals >= 2 or state.cycle_count == 30000 then
+        state.finished = true
+    end
+end
+
+expect_eventually("goal scored", function()
+    return state.game_controller.hulks_score > 0
+end)
+
+expect_eventually("ready after a goal with the opponent kicking off", function()
+    return state.game_controller.hulks_score > 0
+        and state.game_controller.game_state == "Ready"
+        and state.game_controller.kicking_team == "Opponent"
+end)
+
+expect_eventually("penalized player is removed from the game", function()
+    return state.game_controller_state.penalties.three ~= "None"
+end, 6100)
@@ -0,0 +1,75 @@
+function spawn_robot(number)
+    table.insert(state.robots, create_robot(number))
+end
+
+spawn_robot(1)
+spawn_robot(2)
+spawn_robot(3)
+spawn_robot(4)
+spawn_robot(5)
+
+state.ball = {
+    position = { 0.0, 0.0 },
+    velocity = { 0.0, 0.0 },
+}
+
+local goals = 0
+local game_end_time = 10000
+
+function on_goal()
+    print("Goal scored, resetting ball!")
+    state.ball = nil
+    goals = goals + 1
+    game_end_time = state.cycle_count + 200
+end
+
+function on_cycle()
+    if state.cycle_count == 100 then
+        state.game_controller_state.game_state = "Ready"
+        state.filtered_game_state = {
+            Ready = {
+                kicking_team = "Hulks"
+            }
+        }
+    end
+
+    if state.cycle_count == 1100 then
+        state.game_controller_state.game_state = "Set"
+        state.filtered_game_state = "Set"
+    end
+
+    if state.cycle_count == 1600 then
+        state.game_controller_state.game_state = "Playing"
+        state.filtered_game_state = {
+            Playing = {
+                ball_is_free = true
+            }
+        }
+    end
+
+    if state.cycle_count >= game_end_time then
+        state.finished = true
+    end
+end
+
+expect_eventually("goal scored", function()
+    return goals > 0
+end, 10000)
+
+local multiple_strikers_since = nil
+
+expect_always("no two robots have role Striker for more than 2s", function()
+    local is_playing = type(state.filtered_game_state) == "table" and state.filtered_game_state.Playing ~= nil
+    local strikers = 0
+    for _, role in pairs(state.roles) do
+        if role == "Striker" then
+            strikers = strikers + 1
+        end
+    end
+    if not is_playing or strikers <= 1 then
+        multiple_strikers_since = nil
+        return true
+    end
+    multiple_strikers_since = multiple_strikers_since or state.time_elapsed
+    return state.time_elapsed - multiple_strikers_since <= 2.0
+end)
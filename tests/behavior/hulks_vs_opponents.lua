@@ -0,0 +1,64 @@
+function spawn_robot(number)
+    table.insert(state.robots, create_robot(number))
+end
+
+spawn_robot(1)
+spawn_robot(2)
+spawn_robot(3)
+spawn_robot(4)
+spawn_robot(5)
+
+state.opponents = {
+    { position = { 2.0, 0.5 },  policy = "ChaseBall" },
+    { position = { 4.0, 0.0 },  policy = "Block" },
+    { position = { 0.5, -1.0 }, policy = { Mark = { player_number = 4 } } },
+    { position = { 4.4, 0.0 },  policy = "Stand" },
+}
+
+local goals = 0
+local game_end_time = -1
+
+function on_goal()
+    print("Goal scored, resetting ball!")
+    state.ball = nil
+    goals = goals + 1
+    game_end_time = state.cycle_count + 200
+end
+
+function on_cycle()
+    if state.ball == nil and state.cycle_count % 1000 == 0 then
+        state.ball = {
+            position = { 0.0, 0.0 },
+            velocity = { 0.0, 0.0 },
+        }
+    end
+
+    if state.cycle_count == 100 then
+        state.game_controller_state.game_state = "Ready"
+        state.filtered_game_state = {
+            Ready = {
+                kicking_team = "Hulks"
+            }
+        }
+    end
+
+    if state.cycle_count == 1100 then
+        state.filtered_game_state = "Set"
+    end
+
+    if state.cycle_count == 1600 then
+        state.filtered_game_state = {
+            Playing = {
+                ball_is_free = true
+            }
+        }
+    end
+
+    if state.cycle_count == game_end_time or state.cycle_count == 20000 then
+        state.finished = true
+    end
+end
+
+expect_eventually("goal scored against opponents", function()
+    return goals > 0
+end)
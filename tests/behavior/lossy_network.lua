@@ -0,0 +1,75 @@
+function spawn_robot(number)
+    table.insert(state.robots, create_robot(number))
+end
+
+spawn_robot(1)
+spawn_robot(2)
+spawn_robot(3)
+spawn_robot(4)
+spawn_robot(5)
+
+state.network_model = {
+    loss_probability = 0.2,
+    latency = 0.05,
+    jitter = 0.1,
+    outages = {
+        { player_number = 4, start_time = 30.0, end_time = 60.0 },
+    },
+}
+
+state.ball = {
+    position = { 0.0, 0.0 },
+    velocity = { 0.0, 0.0 },
+}
+
+local goals = 0
+local game_end_time = 20000
+
+function on_goal()
+    print("Goal scored, resetting ball!")
+    state.ball = nil
+    goals = goals + 1
+    game_end_time = state.cycle_count + 200
+end
+
+function on_cycle()
+    if state.cycle_count == 100 then
+        state.game_controller_state.game_state = "Ready"
+        state.filtered_game_state = {
+            Ready = {
+                kicking_team = "Hulks"
+            }
+        }
+    end
+
+    if state.cycle_count == 1100 then
+        state.game_controller_state.game_state = "Set"
+        state.filtered_game_state = "Set"
+    end
+
+    if state.cycle_count == 1600 then
+        state.game_controller_state.game_state = "Playing"
+        state.filtered_game_state = {
+            Playing = {
+                ball_is_free = true
+            }
+        }
+    end
+
+    if state.cycle_count >= game_end_time then
+        state.finished = true
+    end
+end
+
+expect_eventually("goal scored despite lossy network", function()
+    return goals > 0
+end)
+
+expect_eventually("every robot sends messages", function()
+    for player_number = 1, 5 do
+        if (state.network_statistics.sent[player_number] or 0) == 0 then
+            return false
+        end
+    end
+    return true
+end)
@@ -0,0 +1,68 @@
+function spawn_robot(number)
+    table.insert(state.robots, create_robot(number))
+end
+
+spawn_robot(1)
+spawn_robot(2)
+spawn_robot(3)
+spawn_robot(4)
+spawn_robot(5)
+
+state.perception_model = {
+    ball_noise = 0.1,
+    pose_translation_noise = 0.05,
+    pose_rotation_noise = 0.02,
+    limited_field_of_view = true,
+    miss_probability = 0.3,
+    false_positive_probability = 0.01,
+    latency = 0.05,
+    ball_timeout = 2.0,
+}
+
+state.ball = {
+    position = { 0.0, 0.0 },
+    velocity = { 0.0, 0.0 },
+}
+
+local goals = 0
+local game_end_time = 20000
+
+function on_goal()
+    print("Goal scored, resetting ball!")
+    state.ball = nil
+    goals = goals + 1
+    game_end_time = state.cycle_count + 200
+end
+
+function on_cycle()
+    if state.cycle_count == 100 then
+        state.game_controller_state.game_state = "Ready"
+        state.filtered_game_state = {
+            Ready = {
+                kicking_team = "Hulks"
+            }
+        }
+    end
+
+    if state.cycle_count == 1100 then
+        state.game_controller_state.game_state = "Set"
+        state.filtered_game_state = "Set"
+    end
+
+    if state.cycle_count == 1600 then
+        state.game_controller_state.game_state = "Playing"
+        state.filtered_game_state = {
+            Playing = {
+                ball_is_free = true
+            }
+        }
+    end
+
+    if state.cycle_count >= game_end_time then
+        state.finished = true
+    end
+end
+
+expect_eventually("goal scored despite noisy perception", function()
+    return goals > 0
+end)
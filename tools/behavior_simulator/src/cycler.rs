@@ -5,6 +5,8 @@ use control::{
     active_vision::{self, ActiveVision},
     ball_state_composer::{self, BallStateComposer},
     behavior::node::{self, Behavior},
+    game_controller_filter::{self, GameControllerFilter},
+    game_state_filter::{self, GameStateFilter},
     kick_selector::{self, KickSelector},
     role_assignment::{self, RoleAssignment},
     rule_obstacle_composer::RuleObstacleComposer,
@@ -12,6 +14,7 @@ use control::{
 };
 use cyclers::control::Database;
 use framework::{AdditionalOutput, PerceptionInput};
+use nalgebra::Isometry2;
 use structs::Configuration;
 use tokio::sync::Notify;
 use types::{hardware, messages::IncomingMessage};
@@ -19,6 +22,8 @@ use types::{hardware, messages::IncomingMessage};
 pub struct BehaviorCycler<Interface> {
     hardware_interface: Arc<Interface>,
     own_changed: Arc<Notify>,
+    game_controller_filter: GameControllerFilter,
+    game_state_filter: GameStateFilter,
     role_assignment: RoleAssignment,
     ball_state_composer: BallStateComposer,
     active_vision: ActiveVision,
@@ -37,6 +42,16 @@ where
         own_changed: Arc<Notify>,
         configuration: &Configuration,
     ) -> Result<Self> {
+        let game_controller_filter =
+            GameControllerFilter::new(game_controller_filter::CreationContext {})
+                .wrap_err("failed to create node `GameControllerFilter`")?;
+        let game_state_filter = GameStateFilter::new(game_state_filter::CreationContext {
+            config: &configuration.game_state_filter,
+            field_dimensions: &configuration.field_dimensions,
+            player_number: &configuration.player_number,
+            robot_to_field: &mut Isometry2::identity(),
+        })
+        .wrap_err("failed to create node `GameStateFilter`")?;
         let rule_obstacle_composer = control::rule_obstacle_composer::RuleObstacleComposer::new(
             control::rule_obstacle_composer::CreationContext {},
         )
@@ -70,6 +85,8 @@ where
             hardware_interface,
             own_changed,
 
+            game_controller_filter,
+            game_state_filter,
             role_assignment,
             ball_state_composer,
             rule_obstacle_composer,
@@ -85,7 +102,58 @@ where
         own_database: &mut Database,
         configuration: &Configuration,
         incoming_messages: BTreeMap<SystemTime, Vec<&IncomingMessage>>,
+        filter_game_state: bool,
     ) -> Result<()> {
+        if filter_game_state {
+            {
+                let main_outputs = self
+                    .game_controller_filter
+                    .cycle(game_controller_filter::CycleContext {
+                        sensor_data: &own_database.main_outputs.sensor_data,
+                        cycle_time: &own_database.main_outputs.cycle_time,
+                        network_message: PerceptionInput {
+                            persistent: incoming_messages.clone(),
+                            temporary: Default::default(),
+                        },
+                    })
+                    .wrap_err("failed to execute cycle of node `GameControllerFilter`")?;
+                own_database.main_outputs.game_controller_state =
+                    main_outputs.game_controller_state.value;
+            }
+            if own_database
+                .main_outputs
+                .game_controller_state
+                .as_ref()
+                .is_some()
+            {
+                let mut robot_to_field = own_database
+                    .main_outputs
+                    .robot_to_field
+                    .unwrap_or_else(Isometry2::identity);
+                let main_outputs = self
+                    .game_state_filter
+                    .cycle(game_state_filter::CycleContext {
+                        ball_position: own_database.main_outputs.ball_position.as_ref(),
+                        buttons: &own_database.main_outputs.buttons,
+                        cycle_time: &own_database.main_outputs.cycle_time,
+                        filtered_whistle: &own_database.main_outputs.filtered_whistle,
+                        game_controller_state: own_database
+                            .main_outputs
+                            .game_controller_state
+                            .as_ref()
+                            .unwrap(),
+                        config: &configuration.game_state_filter,
+                        field_dimensions: &configuration.field_dimensions,
+                        player_number: &configuration.player_number,
+                        robot_to_field: &mut robot_to_field,
+                    })
+                    .wrap_err("failed to execute cycle of node `GameStateFilter`")?;
+                own_database.main_outputs.filtered_game_state =
+                    main_outputs.filtered_game_state.value;
+            } else {
+                own_database.main_outputs.filtered_game_state = Default::default();
+            }
+        }
         if own_database
             .main_outputs
             .game_controller_state
@@ -274,6 +342,7 @@ where
                     field_dimensions: &configuration.field_dimensions,
                     lost_ball_parameters: &configuration.behavior.lost_ball,
                     has_ground_contact: &true,
+                    health: &own_database.main_outputs.health,
                 })
                 .wrap_err("failed to execute cycle of node `Behavior`")?;
             own_database.main_outputs.motion_command = main_outputs.motion_command.value;
@@ -0,0 +1,270 @@
+use std::time::Duration;
+
+use constants::DNT_TEAM_NUMBER;
+use serde::{Deserialize, Serialize};
+use spl_network_messages::{
+    CompetitionPhase, CompetitionType, GameControllerStateMessage, GamePhase, GameState, Half,
+    Penalty, Player, PlayerNumber, SubState, Team, TeamColor, TeamState,
+};
+use types::Players;
+
+use crate::robot::to_player_number;
+
+const OPPONENT_TEAM_NUMBER: u8 = 0;
+const HALF_DURATION: Duration = Duration::from_secs(600);
+const READY_DURATION: Duration = Duration::from_secs(45);
+// the referee whistles and the operator switches to playing a few seconds after set
+const SET_DURATION: Duration = Duration::from_secs(5);
+const KICK_OFF_DURATION: Duration = Duration::from_secs(10);
+const SET_PLAY_DURATION: Duration = Duration::from_secs(30);
+pub const PENALTY_DURATION: Duration = Duration::from_secs(45);
+const MESSAGE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Commands of the game controller operator
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum GameControllerAction {
+    /// Kick-off of the first half from initial or of the second half after the first half finished
+    Ready,
+    Set,
+    Playing,
+    Finished,
+    Goal {
+        team: Team,
+    },
+    SetPlay {
+        sub_state: SubState,
+        kicking_team: Team,
+    },
+    /// Penalties expire after their remaining time, except `Manual` penalties
+    Penalize {
+        player_number: usize,
+        penalty: Penalty,
+    },
+    Unpenalize {
+        player_number: usize,
+    },
+}
+
+/// Game controller following the SPL rules, its state messages are sent to all robots
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GameController {
+    pub game_state: GameState,
+    pub game_phase: GamePhase,
+    pub sub_state: SubState,
+    pub half: Half,
+    pub kicking_team: Team,
+    pub first_half_kicking_team: Team,
+    pub remaining_time_in_half: Duration,
+    pub secondary_time: Duration,
+    pub hulks_score: u8,
+    pub opponent_score: u8,
+    pub penalties: Players<Penalty>,
+    pub next_message_in: Duration,
+}
+
+impl Default for GameController {
+    fn default() -> Self {
+        Self {
+            game_state: GameState::Initial,
+            game_phase: GamePhase::Normal,
+            sub_state: SubState::None,
+            half: Half::First,
+            kicking_team: Team::Hulks,
+            first_half_kicking_team: Team::Hulks,
+            remaining_time_in_half: HALF_DURATION,
+            secondary_time: Duration::ZERO,
+            hulks_score: 0,
+            opponent_score: 0,
+            penalties: Players {
+                one: Penalty::None,
+                two: Penalty::None,
+                three: Penalty::None,
+                four: Penalty::None,
+                five: Penalty::None,
+            },
+            next_message_in: Duration::ZERO,
+        }
+    }
+}
+
+impl GameController {
+    pub fn apply(&mut self, action: GameControllerAction) -> Result<(), String> {
+        match action {
+            GameControllerAction::Ready => match (self.game_state, self.half) {
+                (GameState::Initial, Half::First) => {
+                    self.first_half_kicking_team = self.kicking_team;
+                    self.enter_ready(self.kicking_team);
+                }
+                (GameState::Initial, Half::Second) => self.enter_ready(self.kicking_team),
+                (GameState::Finished, Half::First) => {
+                    self.half = Half::Second;
+                    self.remaining_time_in_half = HALF_DURATION;
+                    self.enter_ready(opposite(self.first_half_kicking_team));
+                }
+                _ => {}
+            },
+            GameControllerAction::Set => self.enter_set(),
+            GameControllerAction::Playing => self.enter_playing(),
+            GameControllerAction::Finished => self.enter_finished(),
+            GameControllerAction::Goal { team } => self.goal(team),
+            GameControllerAction::SetPlay {
+                sub_state,
+                kicking_team,
+            } => {
+                if self.game_state == GameState::Playing {
+                    self.sub_state = sub_state;
+                    self.kicking_team = kicking_team;
+                    self.secondary_time = SET_PLAY_DURATION;
+                }
+            }
+            GameControllerAction::Penalize {
+                player_number,
+                penalty,
+            } => {
+                self.penalties[to_player_number(player_number)?] = penalty;
+            }
+            GameControllerAction::Unpenalize { player_number } => {
+                self.penalties[to_player_number(player_number)?] = Penalty::None;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn goal(&mut self, scoring_team: Team) {
+        if self.game_state != GameState::Playing {
+            return;
+        }
+        match scoring_team {
+            Team::Hulks => self.hulks_score += 1,
+            Team::Opponent => self.opponent_score += 1,
+            Team::Uncertain => return,
+        }
+        self.enter_ready(opposite(scoring_team));
+    }
+
+    pub fn penalty(&self, player_number: PlayerNumber) -> Penalty {
+        self.penalties[player_number]
+    }
+
+    /// Advances the timers and returns a state message every 500 ms
+    pub fn step(
+        &mut self,
+        time_step: Duration,
+        remaining_amount_of_messages: u16,
+    ) -> Option<GameControllerStateMessage> {
+        self.secondary_time = self.secondary_time.saturating_sub(time_step);
+        match self.game_state {
+            GameState::Ready if self.secondary_time.is_zero() => self.enter_set(),
+            GameState::Set if self.secondary_time.is_zero() => self.enter_playing(),
+            GameState::Playing => {
+                self.remaining_time_in_half = self.remaining_time_in_half.saturating_sub(time_step);
+                if self.secondary_time.is_zero() {
+                    self.sub_state = SubState::None;
+                }
+                if self.remaining_time_in_half.is_zero() {
+                    self.enter_finished();
+                }
+            }
+            _ => {}
+        }
+
+        for (player_number, penalty) in self.penalties.clone().iter() {
+            let mut penalty = *penalty;
+            if matches!(penalty, Penalty::Manual { .. }) {
+                continue;
+            }
+            if let Some(remaining) = penalty.remaining_mut() {
+                *remaining = remaining.saturating_sub(time_step);
+                if remaining.is_zero() {
+                    penalty = Penalty::None;
+                }
+            }
+            self.penalties[player_number] = penalty;
+        }
+
+        self.next_message_in = self.next_message_in.saturating_sub(time_step);
+        if !self.next_message_in.is_zero() {
+            return None;
+        }
+        self.next_message_in = MESSAGE_INTERVAL;
+        Some(self.state_message(remaining_amount_of_messages))
+    }
+
+    fn state_message(&self, remaining_amount_of_messages: u16) -> GameControllerStateMessage {
+        let players = [
+            PlayerNumber::One,
+            PlayerNumber::Two,
+            PlayerNumber::Three,
+            PlayerNumber::Four,
+            PlayerNumber::Five,
+        ]
+        .into_iter()
+        .map(|player_number| Player {
+            penalty: self.penalty(player_number),
+        })
+        .collect();
+        GameControllerStateMessage {
+            competition_phase: CompetitionPhase::RoundRobin,
+            competition_type: CompetitionType::Normal,
+            game_phase: self.game_phase,
+            game_state: self.game_state,
+            sub_state: self.sub_state,
+            half: self.half,
+            remaining_time_in_half: self.remaining_time_in_half,
+            secondary_time: self.secondary_time,
+            hulks_team: TeamState {
+                team_number: DNT_TEAM_NUMBER,
+                field_player_colour: TeamColor::Blue,
+                goalkeeper_colour: TeamColor::Yellow,
+                score: self.hulks_score,
+                penalty_shoot_index: 0,
+                penalty_shoots: Vec::new(),
+                remaining_amount_of_messages,
+                players,
+            },
+            opponent_team: TeamState {
+                team_number: OPPONENT_TEAM_NUMBER,
+                field_player_colour: TeamColor::Red,
+                goalkeeper_colour: TeamColor::Black,
+                score: self.opponent_score,
+                penalty_shoot_index: 0,
+                penalty_shoots: Vec::new(),
+                remaining_amount_of_messages: 0,
+                players: Vec::new(),
+            },
+            kicking_team: self.kicking_team,
+        }
+    }
+
+    fn enter_ready(&mut self, kicking_team: Team) {
+        self.game_state = GameState::Ready;
+        self.sub_state = SubState::None;
+        self.kicking_team = kicking_team;
+        self.secondary_time = READY_DURATION;
+    }
+
+    fn enter_set(&mut self) {
+        self.game_state = GameState::Set;
+        self.secondary_time = SET_DURATION;
+    }
+
+    fn enter_playing(&mut self) {
+        self.game_state = GameState::Playing;
+        self.secondary_time = KICK_OFF_DURATION;
+    }
+
+    fn enter_finished(&mut self) {
+        self.game_state = GameState::Finished;
+        self.sub_state = SubState::None;
+        self.secondary_time = Duration::ZERO;
+    }
+}
+
+fn opposite(team: Team) -> Team {
+    match team {
+        Team::Hulks => Team::Opponent,
+        Team::Opponent => Team::Hulks,
+        Team::Uncertain => Team::Uncertain,
+    }
+}
@@ -0,0 +1,50 @@
+use std::{path::Path, time::Instant};
+
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
+use log::{error, info};
+
+use crate::simulator::Simulator;
+
+pub fn run(scenario_file: impl AsRef<Path>) -> Result<()> {
+    let mut simulator = Simulator::try_new()?;
+    simulator.execute_script(&scenario_file)?;
+
+    let start = Instant::now();
+    let mut cycle_count = 0;
+    simulator
+        .run_until_finished(|_| cycle_count += 1)
+        .wrap_err("failed to run simulation")?;
+    info!(
+        "Simulated {cycle_count} cycles of {:?} in {:.2} seconds",
+        scenario_file.as_ref(),
+        start.elapsed().as_secs_f32()
+    );
+    {
+        let state = simulator.state.lock();
+        let statistics = &state.network.statistics;
+        for (player_number, sent) in statistics.sent.iter() {
+            let received = statistics.received.get(player_number).unwrap_or(&0);
+            info!("Player {player_number}: sent {sent}, received {received} message(s)");
+        }
+        info!(
+            "{} message(s) lost, {} message(s) exceeding the budget",
+            statistics.lost, statistics.exceeding_budget
+        );
+    }
+
+    let failed_expectations = simulator.failed_expectations();
+    for failed_expectation in failed_expectations {
+        error!("{failed_expectation}");
+    }
+    if !failed_expectations.is_empty() {
+        bail!(
+            "{} expectation(s) of {:?} failed",
+            failed_expectations.len(),
+            scenario_file.as_ref()
+        );
+    }
+    Ok(())
+}
@@ -1 +1,11 @@
 pub mod cycler;
+mod game_controller;
+pub mod headless;
+mod interfake;
+mod network;
+mod opponent;
+mod perception;
+mod robot;
+pub mod server;
+mod simulator;
+mod state;
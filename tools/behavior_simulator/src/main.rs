@@ -1,5 +1,6 @@
 use std::{io::stdout, path::PathBuf};
 
+use behavior_simulator::{headless, server};
 use chrono::Local;
 use clap::Parser;
 use color_eyre::{install, Result};
@@ -7,17 +8,13 @@ use fern::{Dispatch, InitError};
 use log::LevelFilter;
 use tokio_util::sync::CancellationToken;
 
-mod cycler;
-mod interfake;
-mod robot;
-mod server;
-mod simulator;
-mod state;
-
 #[derive(Parser)]
 struct Arguments {
     #[arg(short, long, default_value = "[::]:1337")]
     listen_address: String,
+    /// Run the scenario to completion without starting the server and fail on unmet expectations
+    #[arg(long)]
+    headless: bool,
     scenario_file: PathBuf,
 }
 
@@ -55,6 +52,9 @@ fn main() -> Result<()> {
     }
 
     let arguments = Arguments::parse();
+    if arguments.headless {
+        return headless::run(arguments.scenario_file);
+    }
 
     server::run(
         Some(arguments.listen_address),
@@ -0,0 +1,134 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use spl_network_messages::{HulkMessage, PlayerNumber};
+
+use crate::robot::from_player_number;
+
+/// Transmission characteristics of the simulated WiFi. The default is a perfect network.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NetworkModel {
+    /// Probability of a message getting lost on its way to a single receiver
+    pub loss_probability: f32,
+    /// Delay of every message in seconds
+    pub latency: f32,
+    /// Maximum additional, uniformly distributed delay in seconds
+    pub jitter: f32,
+    pub outages: Vec<Outage>,
+}
+
+/// A robot neither sends nor receives messages between `start_time` and `end_time` (elapsed seconds)
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Outage {
+    pub player_number: usize,
+    pub start_time: f32,
+    pub end_time: f32,
+}
+
+impl NetworkModel {
+    fn is_offline(&self, player_number: PlayerNumber, time_elapsed: Duration) -> bool {
+        let player_number = from_player_number(player_number);
+        let time_elapsed = time_elapsed.as_secs_f32();
+        self.outages.iter().any(|outage| {
+            outage.player_number == player_number
+                && (outage.start_time..outage.end_time).contains(&time_elapsed)
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct NetworkStatistics {
+    pub sent: BTreeMap<usize, usize>,
+    pub received: BTreeMap<usize, usize>,
+    pub lost: usize,
+    pub exceeding_budget: usize,
+}
+
+struct InFlightMessage {
+    receiver: PlayerNumber,
+    arrival: Duration,
+    message: HulkMessage,
+}
+
+pub struct Network {
+    rng: StdRng,
+    in_flight: Vec<InFlightMessage>,
+    pub statistics: NetworkStatistics,
+}
+
+impl Network {
+    pub fn new() -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(0),
+            in_flight: Vec::new(),
+            statistics: NetworkStatistics::default(),
+        }
+    }
+
+    /// Sends a message to all other robots, returns false if the robot is currently offline
+    pub fn send(
+        &mut self,
+        model: &NetworkModel,
+        time_elapsed: Duration,
+        sender: PlayerNumber,
+        receivers: impl IntoIterator<Item = PlayerNumber>,
+        message: HulkMessage,
+    ) -> bool {
+        if model.is_offline(sender, time_elapsed) {
+            return false;
+        }
+        *self
+            .statistics
+            .sent
+            .entry(from_player_number(sender))
+            .or_default() += 1;
+
+        for receiver in receivers {
+            if receiver == sender {
+                continue;
+            }
+            if self
+                .rng
+                .gen_bool(model.loss_probability.clamp(0.0, 1.0) as f64)
+            {
+                self.statistics.lost += 1;
+                continue;
+            }
+            let delay = model.latency.max(0.0) + self.rng.gen::<f32>() * model.jitter.max(0.0);
+            self.in_flight.push(InFlightMessage {
+                receiver,
+                arrival: time_elapsed + Duration::from_secs_f32(delay),
+                message,
+            });
+        }
+        true
+    }
+
+    /// Takes all messages which arrived at the receiver until now
+    pub fn receive(
+        &mut self,
+        model: &NetworkModel,
+        time_elapsed: Duration,
+        receiver: PlayerNumber,
+    ) -> Vec<HulkMessage> {
+        let is_offline = model.is_offline(receiver, time_elapsed);
+        let mut arrived = Vec::new();
+        self.in_flight.retain(|in_flight| {
+            if in_flight.receiver != receiver || in_flight.arrival > time_elapsed {
+                return true;
+            }
+            if !is_offline {
+                arrived.push(in_flight.message);
+            }
+            false
+        });
+        *self
+            .statistics
+            .received
+            .entry(from_player_number(receiver))
+            .or_default() += arrived.len();
+        arrived
+    }
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use nalgebra::{Point2, Vector2};
+use serde::{Deserialize, Serialize};
+
+const WALKING_SPEED: f32 = 0.3;
+const BLOCKING_DISTANCE: f32 = 0.5;
+const MARKING_DISTANCE: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub enum OpponentPolicy {
+    #[default]
+    Stand,
+    ChaseBall,
+    Block,
+    Mark {
+        player_number: usize,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Opponent {
+    pub position: Point2<f32>,
+    #[serde(default)]
+    pub orientation: f32,
+    #[serde(default)]
+    pub policy: OpponentPolicy,
+}
+
+impl Opponent {
+    pub fn step(
+        &mut self,
+        time_step: Duration,
+        ball_position: Option<Point2<f32>>,
+        marked_robot_position: Option<Point2<f32>>,
+        own_goal_center: Point2<f32>,
+    ) {
+        let target = match (self.policy, ball_position) {
+            (OpponentPolicy::Stand, _) | (_, None) => None,
+            (OpponentPolicy::ChaseBall, Some(ball)) => Some(ball),
+            (OpponentPolicy::Block, Some(ball)) => {
+                Some(ball + direction(ball, own_goal_center) * BLOCKING_DISTANCE)
+            }
+            (OpponentPolicy::Mark { .. }, Some(ball)) => {
+                marked_robot_position.map(|robot| robot + direction(robot, ball) * MARKING_DISTANCE)
+            }
+        };
+
+        if let Some(target) = target {
+            self.position +=
+                (target - self.position).cap_magnitude(WALKING_SPEED * time_step.as_secs_f32());
+        }
+        if let Some(ball) = ball_position {
+            let to_ball = ball - self.position;
+            if to_ball.norm_squared() > f32::EPSILON {
+                self.orientation = to_ball.y.atan2(to_ball.x);
+            }
+        }
+    }
+}
+
+fn direction(from: Point2<f32>, to: Point2<f32>) -> Vector2<f32> {
+    (to - from).try_normalize(f32::EPSILON).unwrap_or_default()
+}
@@ -0,0 +1,168 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime},
+};
+
+use nalgebra::{point, vector, Isometry2, Isometry3, Point2};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+use serde::{Deserialize, Serialize};
+use types::{
+    configuration::CameraMatrixParameters, BallPosition, CameraMatrix, HeadMotion, MotionCommand,
+};
+
+const MINIMUM_FALSE_POSITIVE_DISTANCE: f32 = 0.3;
+const MAXIMUM_FALSE_POSITIVE_DISTANCE: f32 = 4.0;
+
+/// Perception failures applied to the ground truth before it is fed into the simulated robots.
+/// The default is perfect perception.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PerceptionModel {
+    /// Standard deviation of the detected ball position in meters
+    pub ball_noise: f32,
+    /// Standard deviation of the pose translation in meters
+    pub pose_translation_noise: f32,
+    /// Standard deviation of the pose orientation in radians
+    pub pose_rotation_noise: f32,
+    /// Only detect balls inside the horizontal field of view of the camera
+    pub limited_field_of_view: bool,
+    /// Probability of not detecting a visible ball in a cycle
+    pub miss_probability: f32,
+    /// Probability of detecting a ball at a random position in the field of view in a cycle
+    pub false_positive_probability: f32,
+    /// Delay between a detection and its arrival at the robot in seconds
+    pub latency: f32,
+    /// Time in seconds a ball is remembered after its last detection
+    pub ball_timeout: f32,
+}
+
+pub struct Perception {
+    rng: StdRng,
+    pending_detections: VecDeque<(SystemTime, Option<Point2<f32>>)>,
+    ball_position: Option<BallPosition>,
+}
+
+impl Perception {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            pending_detections: VecDeque::new(),
+            ball_position: None,
+        }
+    }
+
+    pub fn perceive_pose(
+        &mut self,
+        model: &PerceptionModel,
+        ground_truth: Isometry2<f32>,
+    ) -> Isometry2<f32> {
+        let rng = &mut self.rng;
+        Isometry2::new(
+            ground_truth.translation.vector
+                + vector![
+                    sample_noise(rng, model.pose_translation_noise),
+                    sample_noise(rng, model.pose_translation_noise)
+                ],
+            ground_truth.rotation.angle() + sample_noise(rng, model.pose_rotation_noise),
+        )
+    }
+
+    /// Returns the ball position a robot perceives given the ground truth ball in robot coordinates.
+    pub fn perceive_ball(
+        &mut self,
+        model: &PerceptionModel,
+        now: SystemTime,
+        ball: Option<Point2<f32>>,
+        field_of_view: FieldOfView,
+    ) -> Option<BallPosition> {
+        let rng = &mut self.rng;
+        let visible_ball = ball
+            .filter(|ball| !model.limited_field_of_view || field_of_view.contains(*ball))
+            .filter(|_| !rng.gen_bool(model.miss_probability.clamp(0.0, 1.0) as f64));
+        let detection = if rng.gen_bool(model.false_positive_probability.clamp(0.0, 1.0) as f64) {
+            Some(field_of_view.sample(rng))
+        } else {
+            visible_ball.map(|ball| {
+                ball + vector![
+                    sample_noise(rng, model.ball_noise),
+                    sample_noise(rng, model.ball_noise)
+                ]
+            })
+        };
+        self.pending_detections.push_back((now, detection));
+
+        let latency = Duration::from_secs_f32(model.latency.max(0.0));
+        while let Some((detection_time, detection)) = self.pending_detections.front().copied() {
+            if detection_time + latency > now {
+                break;
+            }
+            self.pending_detections.pop_front();
+            if let Some(position) = detection {
+                self.ball_position = Some(BallPosition {
+                    position,
+                    last_seen: detection_time,
+                });
+            }
+        }
+
+        let ball_timeout = Duration::from_secs_f32(model.ball_timeout.max(0.0));
+        self.ball_position = self.ball_position.filter(|ball_position| {
+            now.duration_since(ball_position.last_seen)
+                .unwrap_or_default()
+                <= ball_timeout
+        });
+        self.ball_position
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FieldOfView {
+    pub head_yaw: f32,
+    pub opening_angle: f32,
+}
+
+impl FieldOfView {
+    pub fn new(
+        camera_matrix_parameters: &CameraMatrixParameters,
+        motion_command: &MotionCommand,
+    ) -> Self {
+        let camera_matrix = CameraMatrix::from_normalized_focal_and_center(
+            camera_matrix_parameters.focal_lengths,
+            camera_matrix_parameters.cc_optical_center,
+            vector![640.0, 480.0],
+            Isometry3::identity(),
+            Isometry3::identity(),
+            Isometry3::identity(),
+        );
+        let head_yaw = match motion_command.head_motion() {
+            Some(HeadMotion::LookAt { target })
+            | Some(HeadMotion::LookLeftAndRightOf { target }) => target.y.atan2(target.x),
+            _ => 0.0,
+        };
+        Self {
+            head_yaw,
+            opening_angle: camera_matrix.field_of_view.x,
+        }
+    }
+
+    fn contains(&self, position: Point2<f32>) -> bool {
+        let angle = position.y.atan2(position.x) - self.head_yaw;
+        let angle = angle.sin().atan2(angle.cos());
+        angle.abs() <= self.opening_angle / 2.0
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> Point2<f32> {
+        let half_opening_angle = self.opening_angle / 2.0;
+        let angle = self.head_yaw + rng.gen_range(-half_opening_angle..=half_opening_angle);
+        let distance =
+            rng.gen_range(MINIMUM_FALSE_POSITIVE_DISTANCE..MAXIMUM_FALSE_POSITIVE_DISTANCE);
+        point![angle.cos(), angle.sin()] * distance
+    }
+}
+
+fn sample_noise(rng: &mut StdRng, standard_deviation: f32) -> f32 {
+    Normal::new(0.0, standard_deviation.max(0.0))
+        .map(|normal| normal.sample(rng))
+        .unwrap_or_default()
+}
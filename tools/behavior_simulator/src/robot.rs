@@ -4,11 +4,12 @@ use color_eyre::{eyre::WrapErr, Result};
 use communication::server::parameters::directory::deserialize;
 use control::localization::generate_initial_pose;
 use cyclers::control::Database;
+use nalgebra::Isometry2;
 use spl_network_messages::PlayerNumber;
 use structs::Configuration;
 use types::messages::IncomingMessage;
 
-use crate::{cycler::BehaviorCycler, interfake::Interfake};
+use crate::{cycler::BehaviorCycler, interfake::Interfake, perception::Perception};
 
 pub struct Robot {
     pub interface: Arc<Interfake>,
@@ -16,6 +17,8 @@ pub struct Robot {
     pub database: Database,
     pub configuration: Configuration,
     pub is_penalized: bool,
+    pub ground_truth_pose: Isometry2<f32>,
+    pub perception: Perception,
 }
 
 impl Robot {
@@ -41,10 +44,11 @@ impl Robot {
 
         let mut database = Database::default();
 
-        database.main_outputs.robot_to_field = Some(generate_initial_pose(
+        let ground_truth_pose = generate_initial_pose(
             &configuration.localization.initial_poses[player_number],
             &configuration.field_dimensions,
-        ));
+        );
+        database.main_outputs.robot_to_field = Some(ground_truth_pose);
 
         Ok(Self {
             interface,
@@ -52,12 +56,22 @@ impl Robot {
             database,
             configuration,
             is_penalized: false,
+            ground_truth_pose,
+            perception: Perception::new(from_player_number(player_number) as u64),
         })
     }
 
-    pub fn cycle(&mut self, messages: BTreeMap<SystemTime, Vec<&IncomingMessage>>) -> Result<()> {
-        self.cycler
-            .cycle(&mut self.database, &self.configuration, messages)
+    pub fn cycle(
+        &mut self,
+        messages: BTreeMap<SystemTime, Vec<&IncomingMessage>>,
+        filter_game_state: bool,
+    ) -> Result<()> {
+        self.cycler.cycle(
+            &mut self.database,
+            &self.configuration,
+            messages,
+            filter_game_state,
+        )
     }
 }
 
@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
 use tokio::{net::ToSocketAddrs, select, sync::Notify, time::interval};
 use tokio_util::sync::CancellationToken;
-use types::{FieldDimensions, Players};
+use types::{FieldDimensions, Players, TimelineMarker, TimelineMarkerDetector};
 
 #[derive(Clone, Serialize, Deserialize, SerializeHierarchy)]
 struct Configuration {
@@ -30,6 +30,7 @@ struct Configuration {
 #[derive(Clone, Default, Serialize, Deserialize, SerializeHierarchy)]
 struct MainOutputs {
     frame_count: usize,
+    markers: Vec<TimelineMarker<usize>>,
     databases: Players<Option<Database>>,
 }
 
@@ -48,6 +49,7 @@ async fn timeline_server(
     control_writer: Writer<Database>,
     control_changed: Arc<Notify>,
     frames: Vec<Frame>,
+    markers: Vec<TimelineMarker<usize>>,
 ) {
     // Hack to provide frame count to clients initially.
     // Can be removed if communication sends data for
@@ -68,6 +70,7 @@ async fn timeline_server(
         {
             let mut outputs = outputs_writer.next();
             outputs.main_outputs.frame_count = frames.len();
+            outputs.main_outputs.markers = markers.clone();
             outputs.main_outputs.databases = frames[parameters.selected_frame].robots.clone();
         }
         outputs_changed.notify_waiters();
@@ -85,6 +88,41 @@ async fn timeline_server(
     }
 }
 
+fn detect_markers(frames: &[Frame]) -> Vec<TimelineMarker<usize>> {
+    let mut game_controller_detector = TimelineMarkerDetector::default();
+    let mut fall_detectors = Players::<TimelineMarkerDetector>::default();
+    let mut markers = Vec::new();
+    for (frame_index, frame) in frames.iter().enumerate() {
+        let game_controller_state = frame.robots.iter().find_map(|(_, database)| {
+            database
+                .as_ref()?
+                .main_outputs
+                .game_controller_state
+                .as_ref()
+        });
+        game_controller_detector.detect_game_controller_changes(
+            frame_index,
+            game_controller_state,
+            &mut markers,
+        );
+        for (player_number, database) in frame.robots.iter() {
+            if let Some(database) = database {
+                let mut fall_markers = Vec::new();
+                fall_detectors[player_number].detect_falls(
+                    frame_index,
+                    database.main_outputs.fall_state,
+                    &mut fall_markers,
+                );
+                markers.extend(fall_markers.into_iter().map(|marker| TimelineMarker {
+                    description: format!("Player {player_number}: {}", marker.description),
+                    ..marker
+                }));
+            }
+        }
+    }
+    markers
+}
+
 pub fn run(
     addresses: Option<impl ToSocketAddrs + Send + Sync + 'static>,
     keep_running: CancellationToken,
@@ -134,6 +172,10 @@ pub fn run(
     let frames = simulator.run().wrap_err("failed to run simulation")?;
     let duration = Instant::now() - start;
     println!("Took {:.2} seconds", duration.as_secs_f32());
+    for failed_expectation in simulator.failed_expectations() {
+        println!("Failed expectation: {failed_expectation}");
+    }
+    let markers = detect_markers(&frames);
 
     let runtime = tokio::runtime::Runtime::new()?;
     {
@@ -149,6 +191,7 @@ pub fn run(
                 control_writer,
                 control_changed,
                 frames,
+                markers,
             )
             .await
         });
@@ -6,16 +6,31 @@ use color_eyre::{
     Result,
 };
 use cyclers::control::Database;
-use mlua::{Error as LuaError, Function, Lua, LuaSerdeExt, SerializeOptions, Value};
+use mlua::{
+    Error as LuaError, Function, Lua, LuaSerdeExt, RegistryKey, SerializeOptions, Table, Value,
+};
 use nalgebra::{Isometry2, Vector2};
 use parking_lot::Mutex;
+use spl_network_messages::Penalty;
 use types::Players;
 
 use crate::{
+    game_controller::{GameController, GameControllerAction, PENALTY_DURATION},
     robot::Robot,
     state::{Event, LuaRobot, State},
 };
 
+enum ExpectationKind {
+    Always,
+    Eventually { before_cycle: Option<usize> },
+}
+
+struct Expectation {
+    description: String,
+    kind: ExpectationKind,
+    predicate: RegistryKey,
+}
+
 const SERIALIZE_OPTIONS: SerializeOptions = SerializeOptions::new().serialize_none_to_null(false);
 
 pub struct Frame {
@@ -25,6 +40,8 @@ pub struct Frame {
 pub struct Simulator {
     pub state: Arc<Mutex<State>>,
     lua: Lua,
+    expectations: Arc<Mutex<Vec<Expectation>>>,
+    failed_expectations: Vec<String>,
 }
 
 impl Simulator {
@@ -43,7 +60,61 @@ impl Simulator {
             .set("create_robot", create_robot)
             .wrap_err("failed to insert create_robot")?;
 
-        Ok(Self { state, lua })
+        let game_controller = lua
+            .create_function(|lua, action: Value| {
+                let action: GameControllerAction = lua.from_value(action)?;
+                apply_game_controller_action(lua, action)
+            })
+            .wrap_err("failed to create function game_controller")?;
+        lua.globals()
+            .set("game_controller", game_controller)
+            .wrap_err("failed to insert game_controller")?;
+
+        let expectations = Arc::new(Mutex::new(Vec::new()));
+        let expect_always = lua
+            .create_function({
+                let expectations = expectations.clone();
+                move |lua, (description, predicate): (String, Function)| {
+                    expectations.lock().push(Expectation {
+                        description,
+                        kind: ExpectationKind::Always,
+                        predicate: lua.create_registry_value(predicate)?,
+                    });
+                    Ok(())
+                }
+            })
+            .wrap_err("failed to create function expect_always")?;
+        lua.globals()
+            .set("expect_always", expect_always)
+            .wrap_err("failed to insert expect_always")?;
+        let expect_eventually = lua
+            .create_function({
+                let expectations = expectations.clone();
+                move |lua,
+                      (description, predicate, before_cycle): (
+                    String,
+                    Function,
+                    Option<usize>,
+                )| {
+                    expectations.lock().push(Expectation {
+                        description,
+                        kind: ExpectationKind::Eventually { before_cycle },
+                        predicate: lua.create_registry_value(predicate)?,
+                    });
+                    Ok(())
+                }
+            })
+            .wrap_err("failed to create function expect_eventually")?;
+        lua.globals()
+            .set("expect_eventually", expect_eventually)
+            .wrap_err("failed to insert expect_eventually")?;
+
+        Ok(Self {
+            state,
+            lua,
+            expectations,
+            failed_expectations: Vec::new(),
+        })
     }
 
     pub fn execute_script(&mut self, file_name: impl AsRef<Path>) -> Result<()> {
@@ -67,22 +138,42 @@ impl Simulator {
 
     pub fn run(&mut self) -> Result<Vec<Frame>> {
         let mut frames = Vec::new();
-        loop {
-            self.cycle()?;
-
-            let state = self.state.lock();
+        self.run_until_finished(|state| {
             let mut robots = Players::<Option<Database>>::default();
             for (player_number, robot) in &state.robots {
                 robots[*player_number] = Some(robot.database.clone())
             }
             frames.push(Frame { robots });
+        })?;
+
+        Ok(frames)
+    }
+
+    pub fn run_until_finished(&mut self, mut on_cycle: impl FnMut(&State)) -> Result<()> {
+        loop {
+            self.cycle()?;
+
+            let state = self.state.lock();
+            on_cycle(&state);
 
             if state.finished {
                 break;
             }
         }
 
-        Ok(frames)
+        for expectation in self.expectations.lock().drain(..) {
+            if let ExpectationKind::Eventually { .. } = expectation.kind {
+                self.failed_expectations.push(format!(
+                    "expected `{}` before the end of the scenario",
+                    expectation.description
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn failed_expectations(&self) -> &[String] {
+        &self.failed_expectations
     }
 
     pub fn cycle(&mut self) -> Result<()> {
@@ -96,7 +187,18 @@ impl Simulator {
         self.lua.scope(|scope| {
             self.lua.globals().set(
                 "penalize",
-                scope.create_function(|_, player_number: usize| {
+                scope.create_function(|lua, player_number: usize| {
+                    if is_game_controller_enabled(lua)? {
+                        return apply_game_controller_action(
+                            lua,
+                            GameControllerAction::Penalize {
+                                player_number,
+                                penalty: Penalty::Manual {
+                                    remaining: PENALTY_DURATION,
+                                },
+                            },
+                        );
+                    }
                     let player_number =
                         to_player_number(player_number).map_err(LuaError::external)?;
                     self.state
@@ -111,7 +213,13 @@ impl Simulator {
             )?;
             self.lua.globals().set(
                 "unpenalize",
-                scope.create_function(|_, player_number: usize| {
+                scope.create_function(|lua, player_number: usize| {
+                    if is_game_controller_enabled(lua)? {
+                        return apply_game_controller_action(
+                            lua,
+                            GameControllerAction::Unpenalize { player_number },
+                        );
+                    }
                     let player_number =
                         to_player_number(player_number).map_err(LuaError::external)?;
                     self.state
@@ -138,9 +246,7 @@ impl Simulator {
                             .robots
                             .get_mut(&player_number)
                             .unwrap()
-                            .database
-                            .main_outputs
-                            .robot_to_field = Some(Isometry2::new(position, angle));
+                            .ground_truth_pose = Isometry2::new(position, angle);
 
                         Ok(())
                     },
@@ -156,7 +262,46 @@ impl Simulator {
             Ok(())
         })?;
 
-        self.deserialize_state()
+        self.deserialize_state()?;
+        self.check_expectations()
+    }
+
+    fn check_expectations(&mut self) -> Result<()> {
+        let cycle_count = self.state.lock().cycle_count;
+        let mut expectations = self.expectations.lock();
+        let mut remaining_expectations = Vec::new();
+        for expectation in expectations.drain(..) {
+            let predicate: Function = self
+                .lua
+                .registry_value(&expectation.predicate)
+                .wrap_err("failed to retrieve expectation predicate")?;
+            let is_fulfilled: bool = predicate.call(()).wrap_err_with(|| {
+                format!(
+                    "failed to evaluate expectation `{}`",
+                    expectation.description
+                )
+            })?;
+            match expectation.kind {
+                ExpectationKind::Always if !is_fulfilled => {
+                    self.failed_expectations.push(format!(
+                        "expected always `{}`, violated in cycle {cycle_count}",
+                        expectation.description
+                    ));
+                }
+                ExpectationKind::Eventually { .. } if is_fulfilled => {}
+                ExpectationKind::Eventually {
+                    before_cycle: Some(before_cycle),
+                } if cycle_count >= before_cycle => {
+                    self.failed_expectations.push(format!(
+                        "expected `{}` before cycle {before_cycle}",
+                        expectation.description
+                    ));
+                }
+                _ => remaining_expectations.push(expectation),
+            }
+        }
+        *expectations = remaining_expectations;
+        Ok(())
     }
 
     fn execute_event_callback(&self, name: &str) -> Result<(), LuaError> {
@@ -195,3 +340,24 @@ impl Simulator {
             .wrap_err("failed to load lua state")
     }
 }
+
+fn is_game_controller_enabled(lua: &Lua) -> Result<bool, LuaError> {
+    let state: Table = lua.globals().get("state")?;
+    let game_controller: Value = state.get("game_controller")?;
+    Ok(!matches!(game_controller, Value::Nil))
+}
+
+/// Applies the action to `state.game_controller` which is loaded into the simulation after the callbacks
+fn apply_game_controller_action(lua: &Lua, action: GameControllerAction) -> Result<(), LuaError> {
+    let state: Table = lua.globals().get("state")?;
+    let mut game_controller: GameController = lua
+        .from_value::<Option<GameController>>(state.get("game_controller")?)?
+        .ok_or_else(|| {
+            LuaError::external("the game controller is disabled, set `state.game_controller` first")
+        })?;
+    game_controller.apply(action).map_err(LuaError::external)?;
+    state.set(
+        "game_controller",
+        lua.to_value_with(&game_controller, SERIALIZE_OPTIONS)?,
+    )
+}
@@ -1,22 +1,34 @@
 use std::{
     collections::{BTreeMap, HashMap},
-    mem::take,
     time::{Duration, UNIX_EPOCH},
 };
 
 use color_eyre::Result;
 use cyclers::control::Database;
-use nalgebra::{vector, Isometry2, Point2, UnitComplex, Vector2};
+use nalgebra::{point, vector, Isometry2, Point2, UnitComplex, Vector2};
 use serde::{Deserialize, Serialize};
-use spl_network_messages::{GamePhase, GameState, HulkMessage, Penalty, PlayerNumber, Team};
+use spl_network_messages::{
+    GameControllerStateMessage, GamePhase, GameState, HulkMessage, Penalty, PlayerNumber, SubState,
+    Team,
+};
 use structs::{control::AdditionalOutputs, Configuration};
 use types::{
     messages::{IncomingMessage, OutgoingMessage},
-    BallPosition, FilteredGameState, GameControllerState, KickVariant, LineSegment, MotionCommand,
-    OrientationMode, PathSegment, Players, PrimaryState, Side,
+    FieldDimensions, FilteredGameState, GameControllerState, KickVariant, LineSegment,
+    MotionCommand, Obstacle, OrientationMode, PathSegment, Players, PrimaryState, Role, Side,
+};
+
+use crate::{
+    game_controller::GameController,
+    network::{Network, NetworkModel, NetworkStatistics},
+    opponent::{Opponent, OpponentPolicy},
+    perception::{FieldOfView, PerceptionModel},
+    robot::{from_player_number, to_player_number, Robot},
 };
 
-use crate::robot::Robot;
+const ROBOT_RADIUS: f32 = 0.15;
+const BALL_DECELERATION: f32 = 0.6;
+const BALL_RESTITUTION: f32 = 0.5;
 
 pub enum Event {
     Cycle,
@@ -34,10 +46,16 @@ pub struct State {
     pub cycle_count: usize,
     pub robots: HashMap<PlayerNumber, Robot>,
     pub ball: Option<Ball>,
+    pub opponents: Vec<Opponent>,
+    pub perception_model: PerceptionModel,
+    pub network_model: NetworkModel,
+    pub network: Network,
+    pub game_controller: Option<GameController>,
     pub messages: Vec<(PlayerNumber, HulkMessage)>,
     pub finished: bool,
     pub game_controller_state: GameControllerState,
     pub filtered_game_state: FilteredGameState,
+    pub field_dimensions: FieldDimensions,
 }
 
 impl State {
@@ -65,10 +83,16 @@ impl State {
             cycle_count: 0,
             robots,
             ball: None,
+            opponents: Vec::new(),
+            perception_model: PerceptionModel::default(),
+            network_model: NetworkModel::default(),
+            network: Network::new(),
+            game_controller: None,
             messages: Vec::new(),
             finished: false,
             game_controller_state,
             filtered_game_state: FilteredGameState::Initial,
+            field_dimensions: FieldDimensions::default(),
         }
     }
 
@@ -78,7 +102,9 @@ impl State {
         let mut events = vec![Event::Cycle];
 
         self.move_robots(time_step);
-        self.cycle_robots(now)?;
+        self.move_opponents(time_step);
+        let game_controller_message = self.step_game_controller(time_step);
+        self.cycle_robots(now, game_controller_message)?;
         events.extend(self.move_ball(time_step));
 
         self.time_elapsed += time_step;
@@ -89,12 +115,7 @@ impl State {
 
     fn move_robots(&mut self, time_step: Duration) {
         for robot in self.robots.values_mut() {
-            let robot_to_field = robot
-                .database
-                .main_outputs
-                .robot_to_field
-                .as_mut()
-                .expect("simulated robots should always have a known pose");
+            let robot_to_field = &mut robot.ground_truth_pose;
 
             robot.database.additional_outputs = AdditionalOutputs::default();
             match &robot.database.main_outputs.motion_command {
@@ -157,35 +178,107 @@ impl State {
         }
     }
 
-    fn cycle_robots(&mut self, now: std::time::SystemTime) -> Result<()> {
-        let incoming_messages = take(&mut self.messages);
+    fn move_opponents(&mut self, time_step: Duration) {
+        let ball_position = self.ball.as_ref().map(|ball| ball.position);
+        for opponent in self.opponents.iter_mut() {
+            let marked_robot_position = match opponent.policy {
+                OpponentPolicy::Mark { player_number } => to_player_number(player_number)
+                    .ok()
+                    .and_then(|player_number| self.robots.get(&player_number))
+                    .map(|robot| robot.ground_truth_pose.translation.vector.into()),
+                _ => None,
+            };
+            opponent.step(
+                time_step,
+                ball_position,
+                marked_robot_position,
+                point![self.field_dimensions.length / 2.0, 0.0],
+            );
+        }
+    }
 
+    fn step_game_controller(&mut self, time_step: Duration) -> Option<GameControllerStateMessage> {
+        let game_controller = self.game_controller.as_mut()?;
+        let message = game_controller.step(
+            time_step,
+            self.game_controller_state.remaining_amount_of_messages,
+        );
+
+        self.game_controller_state.game_state = game_controller.game_state;
+        self.game_controller_state.game_phase = game_controller.game_phase;
+        self.game_controller_state.kicking_team = game_controller.kicking_team;
+        self.game_controller_state.sub_state = match game_controller.sub_state {
+            SubState::None => None,
+            set_play => Some(set_play),
+        };
+        self.game_controller_state.penalties = Players {
+            one: game_controller.penalty(PlayerNumber::One),
+            two: game_controller.penalty(PlayerNumber::Two),
+            three: game_controller.penalty(PlayerNumber::Three),
+            four: game_controller.penalty(PlayerNumber::Four),
+            five: game_controller.penalty(PlayerNumber::Five),
+        };
         for (player_number, robot) in self.robots.iter_mut() {
-            let robot_to_field = robot
-                .database
-                .main_outputs
-                .robot_to_field
-                .as_mut()
-                .expect("simulated robots should always have a known pose");
-
-            let incoming_messages: Vec<_> = incoming_messages
-                .iter()
-                .filter_map(|(sender, message)| {
-                    (sender != player_number).then_some(IncomingMessage::Spl(*message))
-                })
+            robot.is_penalized = game_controller.penalty(*player_number).is_some();
+        }
+
+        message
+    }
+
+    fn cycle_robots(
+        &mut self,
+        now: std::time::SystemTime,
+        game_controller_message: Option<GameControllerStateMessage>,
+    ) -> Result<()> {
+        self.messages.clear();
+
+        for (player_number, robot) in self.robots.iter_mut() {
+            let robot_to_field = robot.ground_truth_pose;
+
+            let incoming_messages: Vec<_> = self
+                .network
+                .receive(&self.network_model, self.time_elapsed, *player_number)
+                .into_iter()
+                .map(IncomingMessage::Spl)
+                .chain(
+                    game_controller_message
+                        .clone()
+                        .map(IncomingMessage::GameController),
+                )
                 .collect();
             let messages = BTreeMap::from_iter([(now, incoming_messages.iter().collect())]);
 
             robot.database.main_outputs.cycle_time.start_time = now;
 
-            robot.database.main_outputs.ball_position =
-                self.ball.as_ref().map(|ball| BallPosition {
-                    position: robot_to_field.inverse() * ball.position,
-                    last_seen: now,
-                });
-
+            let field_of_view = FieldOfView::new(
+                &robot.configuration.camera_matrix_parameters.vision_top,
+                &robot.database.main_outputs.motion_command,
+            );
+            robot.database.main_outputs.ball_position = robot.perception.perceive_ball(
+                &self.perception_model,
+                now,
+                self.ball
+                    .as_ref()
+                    .map(|ball| robot_to_field.inverse() * ball.position),
+                field_of_view,
+            );
+            robot.database.main_outputs.robot_to_field = Some(
+                robot
+                    .perception
+                    .perceive_pose(&self.perception_model, robot_to_field),
+            );
+
+            // with a simulated game controller, robots filter the game state themselves
+            let filtered_game_state = match self.game_controller {
+                Some(_) => robot
+                    .database
+                    .main_outputs
+                    .filtered_game_state
+                    .unwrap_or(FilteredGameState::Initial),
+                None => self.filtered_game_state,
+            };
             robot.database.main_outputs.primary_state =
-                match (robot.is_penalized, self.filtered_game_state) {
+                match (robot.is_penalized, filtered_game_state) {
                     (true, _) => PrimaryState::Penalized,
                     (false, FilteredGameState::Initial) => PrimaryState::Initial,
                     (false, FilteredGameState::Ready { .. }) => PrimaryState::Ready,
@@ -193,30 +286,104 @@ impl State {
                     (false, FilteredGameState::Playing { .. }) => PrimaryState::Playing,
                     (false, FilteredGameState::Finished) => PrimaryState::Finished,
                 };
-            robot.database.main_outputs.filtered_game_state = Some(self.filtered_game_state);
-            robot.database.main_outputs.game_controller_state = Some(self.game_controller_state);
+            robot.database.main_outputs.obstacles = self
+                .opponents
+                .iter()
+                .map(|opponent| {
+                    Obstacle::robot(
+                        robot_to_field.inverse() * opponent.position,
+                        robot
+                            .configuration
+                            .obstacle_filter
+                            .robot_obstacle_radius_at_foot_height,
+                        robot
+                            .configuration
+                            .obstacle_filter
+                            .robot_obstacle_radius_at_hip_height,
+                    )
+                })
+                .collect();
+            if self.game_controller.is_none() {
+                robot.database.main_outputs.filtered_game_state = Some(self.filtered_game_state);
+                robot.database.main_outputs.game_controller_state =
+                    Some(self.game_controller_state);
+            }
 
-            robot.cycle(messages)?;
+            robot.cycle(messages, self.game_controller.is_some())?;
 
             for message in robot.interface.take_outgoing_messages() {
                 if let OutgoingMessage::Spl(message) = message {
                     self.messages.push((*player_number, message));
-                    self.game_controller_state.remaining_amount_of_messages -= 1
                 }
             }
         }
 
+        // robots are stored in a hash map, sort to draw random numbers in a reproducible order
+        let mut receivers: Vec<_> = self.robots.keys().copied().collect();
+        receivers.sort_by_key(|player_number| from_player_number(*player_number));
+        self.messages
+            .sort_by_key(|(sender, _)| from_player_number(*sender));
+        for (sender, message) in self.messages.iter() {
+            if self.game_controller_state.remaining_amount_of_messages == 0 {
+                self.network.statistics.exceeding_budget += 1;
+                continue;
+            }
+            if self.network.send(
+                &self.network_model,
+                self.time_elapsed,
+                *sender,
+                receivers.iter().copied(),
+                *message,
+            ) {
+                self.game_controller_state.remaining_amount_of_messages -= 1;
+            }
+        }
+
         Ok(())
     }
 
     fn move_ball(&mut self, time_step: Duration) -> Vec<Event> {
         let mut events = Vec::new();
         if let Some(ball) = self.ball.as_mut() {
+            let was_in_goal = is_in_goal(&self.field_dimensions, ball.position);
             ball.position += ball.velocity * time_step.as_secs_f32();
-            ball.velocity *= 0.98;
+            let speed = ball.velocity.norm();
+            if speed > f32::EPSILON {
+                ball.velocity *=
+                    (speed - BALL_DECELERATION * time_step.as_secs_f32()).max(0.0) / speed;
+            }
 
-            if ball.position.x.abs() > 4.5 && ball.position.y < 0.75 {
+            let robot_positions = self
+                .robots
+                .values()
+                .map(|robot| Point2::from(robot.ground_truth_pose.translation.vector))
+                .chain(self.opponents.iter().map(|opponent| opponent.position));
+            for robot_position in robot_positions {
+                collide(
+                    ball,
+                    self.field_dimensions.ball_radius,
+                    robot_position,
+                    ROBOT_RADIUS,
+                );
+            }
+            for goal_post_position in goal_post_positions(&self.field_dimensions) {
+                collide(
+                    ball,
+                    self.field_dimensions.ball_radius,
+                    goal_post_position,
+                    self.field_dimensions.goal_post_diameter / 2.0,
+                );
+            }
+
+            if !was_in_goal && is_in_goal(&self.field_dimensions, ball.position) {
                 events.push(Event::Goal);
+                if let Some(game_controller) = self.game_controller.as_mut() {
+                    game_controller.goal(if ball.position.x > 0.0 {
+                        Team::Hulks
+                    } else {
+                        Team::Opponent
+                    });
+                }
             }
         }
         events
@@ -229,7 +396,22 @@ impl State {
             // TODO: Expose robot data to lua again
             // robots: self.robots.iter().map(LuaRobot::new).collect(),
             robots: Default::default(),
+            roles: self
+                .robots
+                .iter()
+                .map(|(player_number, robot)| {
+                    (
+                        from_player_number(*player_number),
+                        robot.database.main_outputs.role,
+                    )
+                })
+                .collect(),
             ball: self.ball.clone(),
+            opponents: self.opponents.clone(),
+            perception_model: self.perception_model.clone(),
+            network_model: self.network_model.clone(),
+            network_statistics: self.network.statistics.clone(),
+            game_controller: self.game_controller.clone(),
             messages: self.messages.clone(),
 
             finished: self.finished,
@@ -241,12 +423,20 @@ impl State {
 
     pub fn load_lua_state(&mut self, lua_state: LuaState) -> Result<()> {
         self.ball = lua_state.ball;
+        self.opponents = lua_state.opponents;
+        self.perception_model = lua_state.perception_model;
+        self.network_model = lua_state.network_model;
+        self.game_controller = lua_state.game_controller;
         self.cycle_count = lua_state.cycle_count;
         for lua_robot in lua_state.robots {
             let mut robot = Robot::try_new(lua_robot.configuration.player_number)
                 .expect("Creating dummy robot should never fail");
             robot.database = lua_robot.database;
             robot.configuration = lua_robot.configuration;
+            if let Some(robot_to_field) = robot.database.main_outputs.robot_to_field {
+                robot.ground_truth_pose = robot_to_field;
+            }
+            self.field_dimensions = robot.configuration.field_dimensions.clone();
             self.robots.insert(robot.configuration.player_number, robot);
         }
 
@@ -259,12 +449,55 @@ impl State {
     }
 }
 
+fn goal_post_positions(field_dimensions: &FieldDimensions) -> [Point2<f32>; 4] {
+    let x = field_dimensions.length / 2.0;
+    let y = (field_dimensions.goal_inner_width + field_dimensions.goal_post_diameter) / 2.0;
+    [point![x, y], point![x, -y], point![-x, y], point![-x, -y]]
+}
+
+fn is_in_goal(field_dimensions: &FieldDimensions, position: Point2<f32>) -> bool {
+    position.x.abs() > field_dimensions.length / 2.0
+        && position.y.abs() < field_dimensions.goal_inner_width / 2.0
+}
+
+fn collide(
+    ball: &mut Ball,
+    ball_radius: f32,
+    obstacle_position: Point2<f32>,
+    obstacle_radius: f32,
+) {
+    let minimum_distance = obstacle_radius + ball_radius;
+    let offset = ball.position - obstacle_position;
+    let distance = offset.norm();
+    if distance >= minimum_distance || distance < f32::EPSILON {
+        return;
+    }
+    let normal = offset / distance;
+    ball.position = obstacle_position + normal * minimum_distance;
+    let normal_speed = ball.velocity.dot(&normal);
+    if normal_speed < 0.0 {
+        ball.velocity -= (1.0 + BALL_RESTITUTION) * normal_speed * normal;
+    }
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct LuaState {
     pub time_elapsed: f32,
     pub cycle_count: usize,
     pub robots: Vec<LuaRobot>,
+    #[serde(default)]
+    pub roles: BTreeMap<usize, Role>,
     pub ball: Option<Ball>,
+    #[serde(default)]
+    pub opponents: Vec<Opponent>,
+    #[serde(default)]
+    pub perception_model: PerceptionModel,
+    #[serde(default)]
+    pub network_model: NetworkModel,
+    #[serde(default)]
+    pub network_statistics: NetworkStatistics,
+    #[serde(default)]
+    pub game_controller: Option<GameController>,
     pub messages: Vec<(PlayerNumber, HulkMessage)>,
     pub finished: bool,
     pub game_controller_state: GameControllerState,
@@ -0,0 +1,34 @@
+use std::{env::set_current_dir, path::Path};
+
+use behavior_simulator::headless;
+
+fn run_scenario(scenario_name: &str) {
+    // scenarios and parameters are referenced relative to the repository root
+    set_current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")).unwrap();
+    headless::run(format!("tests/behavior/{scenario_name}.lua")).unwrap();
+}
+
+#[test]
+fn golden_goal_is_scored_by_a_single_striker() {
+    run_scenario("golden_goal_expectations");
+}
+
+#[test]
+fn goal_is_scored_despite_noisy_perception() {
+    run_scenario("noisy_perception");
+}
+
+#[test]
+fn goal_is_scored_despite_lossy_network() {
+    run_scenario("lossy_network");
+}
+
+#[test]
+fn game_controller_follows_goals_and_penalties() {
+    run_scenario("game_controller");
+}
+
+#[test]
+fn goal_is_scored_against_opponents() {
+    run_scenario("hulks_vs_opponents");
+}
@@ -4,7 +4,7 @@ use clap::Parser;
 use color_eyre::{eyre::bail, Result};
 use communication::{
     client::{Communication, CyclerOutput, SubscriberMessage},
-    messages::Format,
+    messages::{Format, Rate},
 };
 use log::{error, info};
 
@@ -17,6 +17,9 @@ mod logging;
 struct CommandlineArguments {
     #[clap(short, long, default_value = "localhost")]
     address: String,
+    /// Token to authenticate with if the robot requires authentication
+    #[clap(short, long)]
+    token: Option<String>,
     path: String,
 }
 
@@ -27,8 +30,9 @@ async fn main() -> Result<()> {
     let arguments = CommandlineArguments::parse();
     let output_to_subscribe = CyclerOutput::from_str(&arguments.path)?;
     let communication = Communication::new(Some(format!("ws://{}:1337", arguments.address)), true);
+    communication.set_token(arguments.token).await;
     let (_uuid, mut receiver) = communication
-        .subscribe_output(output_to_subscribe, Format::Textual)
+        .subscribe_output(output_to_subscribe, Format::Textual, Rate::EveryCycle)
         .await;
     while let Some(message) = receiver.recv().await {
         match message {
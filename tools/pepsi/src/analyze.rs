@@ -1,11 +1,18 @@
 use std::path::PathBuf;
 
 use bat::{PagingMode, PrettyPrinter};
-use clap::Subcommand;
-use color_eyre::{eyre::WrapErr, Result};
+use clap::{Subcommand, ValueEnum};
+use color_eyre::{
+    eyre::{bail, WrapErr},
+    Result,
+};
 
 use repository::Repository;
-use source_analyzer::{parse_rust_file, Contexts, CyclerInstances, CyclerTypes, Nodes, Structs};
+use source_analyzer::{
+    parse_rust_file, Allowlist, Contexts, CyclerInstances, CyclerTypes, Lints, Nodes, Structs,
+};
+
+use crate::dataflow::Dataflow;
 
 #[derive(Subcommand)]
 #[allow(clippy::enum_variant_names)]
@@ -22,6 +29,11 @@ pub enum Arguments {
     },
     DumpCyclerInstances,
     DumpCyclerTypes,
+    /// Dump cyclers, nodes in execution order and their inputs, outputs and parameters
+    DumpDataflow {
+        #[arg(long, value_enum, default_value_t = DataflowFormat::Dot)]
+        format: DataflowFormat,
+    },
     DumpLatest {
         /// File name to dump (may contain wildcard characters usable by glob())
         file_name: String,
@@ -29,6 +41,15 @@ pub enum Arguments {
     DumpNodes,
     DumpSortedNodes,
     DumpStructs,
+    /// Report unused configuration keys, unknown override paths and unconsumed main outputs
+    Lint,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum DataflowFormat {
+    /// Graphviz DOT, e.g. for `dot -Tsvg`
+    Dot,
+    Json,
 }
 
 pub async fn analyze(arguments: Arguments, repository: &Repository) -> Result<()> {
@@ -69,6 +90,18 @@ pub async fn analyze(arguments: Arguments, repository: &Repository) -> Result<()
                     .wrap_err("failed to get cycler types")?;
             println!("{cycler_types:#?}");
         }
+        Arguments::DumpDataflow { format } => {
+            let dataflow = Dataflow::try_from_crates_directory(repository.crates_directory())
+                .wrap_err("failed to get dataflow")?;
+            match format {
+                DataflowFormat::Dot => print!("{}", dataflow.to_dot()),
+                DataflowFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&dataflow)
+                        .wrap_err("failed to serialize dataflow")?
+                ),
+            }
+        }
         Arguments::DumpLatest { file_name } => {
             let file_path = repository
                 .find_latest_file(&format!("target/**/out/**/{file_name}"))
@@ -100,6 +133,22 @@ pub async fn analyze(arguments: Arguments, repository: &Repository) -> Result<()
                 .wrap_err("failed to get structs")?;
             println!("{structs:#?}");
         }
+        Arguments::Lint => {
+            let allowlist = Allowlist::try_from_file(repository.lint_allowlist_file_path())
+                .wrap_err("failed to read lint allowlist")?;
+            let lints = Lints::try_from_directories(
+                repository.crates_directory(),
+                repository.configuration_root(),
+                &allowlist,
+            )
+            .wrap_err("failed to lint")?;
+            for finding in lints.findings.iter() {
+                println!("{finding}");
+            }
+            if !lints.findings.is_empty() {
+                bail!("found {} lint findings", lints.findings.len());
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,301 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    path::Path,
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use quote::ToTokens;
+use serde::Serialize;
+use source_analyzer::{CyclerInstances, CyclerType, CyclerTypes, Field, Nodes, PathSegment};
+
+#[derive(Debug, Serialize)]
+pub struct Dataflow {
+    pub cyclers: Vec<Cycler>,
+    pub edges: Vec<Edge>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Cycler {
+    pub module: String,
+    pub cycler_type: String,
+    pub instances: Vec<String>,
+    /// Nodes in execution order
+    pub nodes: Vec<Node>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Node {
+    pub name: String,
+    pub main_outputs: Vec<MainOutput>,
+    pub parameters: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MainOutput {
+    pub name: String,
+    pub data_type: String,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub enum InputKind {
+    Input,
+    RequiredInput,
+    HistoricInput,
+    PerceptionInput,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Edge {
+    /// `None` if no node of the cycler produces the consumed main output
+    pub producer: Option<String>,
+    pub consumer: String,
+    pub kind: InputKind,
+    pub path: String,
+    pub cycler_instance: Option<String>,
+}
+
+impl Dataflow {
+    pub fn try_from_crates_directory(crates_directory: impl AsRef<Path>) -> Result<Self> {
+        let crates_directory = crates_directory.as_ref();
+        let cycler_instances = CyclerInstances::try_from_crates_directory(crates_directory)
+            .wrap_err("failed to get cycler instances")?;
+        let cycler_types = CyclerTypes::try_from_crates_directory(crates_directory)
+            .wrap_err("failed to get cycler types")?;
+        let mut nodes =
+            Nodes::try_from_crates_directory(crates_directory).wrap_err("failed to get nodes")?;
+        nodes.sort().wrap_err("failed to sort nodes")?;
+
+        let main_outputs_to_nodes: BTreeMap<(&str, String), &str> = nodes
+            .nodes
+            .iter()
+            .flat_map(|(node_name, node)| {
+                node.contexts
+                    .main_outputs
+                    .iter()
+                    .filter_map(move |field| match field {
+                        Field::MainOutput { name, .. } => Some((
+                            (node.cycler_module.as_str(), name.to_string()),
+                            node_name.as_str(),
+                        )),
+                        _ => None,
+                    })
+            })
+            .collect();
+
+        let cyclers = nodes
+            .cycler_modules_to_nodes
+            .iter()
+            .map(|(cycler_module, node_names)| Cycler {
+                module: cycler_module.clone(),
+                cycler_type: match cycler_types
+                    .cycler_modules_to_cycler_types
+                    .get(cycler_module)
+                {
+                    Some(CyclerType::Perception) => "Perception",
+                    Some(CyclerType::RealTime) => "RealTime",
+                    Some(CyclerType::Periodic) => "Periodic",
+                    None => "Unknown",
+                }
+                .to_string(),
+                instances: cycler_instances
+                    .modules_to_instances
+                    .get(cycler_module)
+                    .cloned()
+                    .unwrap_or_default(),
+                nodes: node_names
+                    .iter()
+                    .map(|node_name| {
+                        let contexts = &nodes.nodes[node_name].contexts;
+                        Node {
+                            name: node_name.clone(),
+                            main_outputs: contexts
+                                .main_outputs
+                                .iter()
+                                .filter_map(|field| match field {
+                                    Field::MainOutput { name, data_type } => Some(MainOutput {
+                                        name: name.to_string(),
+                                        data_type: data_type
+                                            .to_token_stream()
+                                            .to_string()
+                                            .replace(' ', ""),
+                                    }),
+                                    _ => None,
+                                })
+                                .collect(),
+                            parameters: contexts
+                                .creation_context
+                                .iter()
+                                .chain(contexts.cycle_context.iter())
+                                .filter_map(|field| match field {
+                                    Field::Parameter { path, .. } => Some(path_to_string(path)),
+                                    _ => None,
+                                })
+                                .collect::<BTreeSet<_>>()
+                                .into_iter()
+                                .collect(),
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut edges = Vec::new();
+        for consumer in nodes.cycler_modules_to_nodes.values().flatten() {
+            let node = &nodes.nodes[consumer];
+            for field in node
+                .contexts
+                .creation_context
+                .iter()
+                .chain(node.contexts.cycle_context.iter())
+            {
+                let (kind, path, cycler_instance) = match field {
+                    Field::Input {
+                        cycler_instance,
+                        path,
+                        ..
+                    } => (InputKind::Input, path, cycler_instance.clone()),
+                    Field::RequiredInput {
+                        cycler_instance,
+                        path,
+                        ..
+                    } => (InputKind::RequiredInput, path, cycler_instance.clone()),
+                    Field::HistoricInput { path, .. } => (InputKind::HistoricInput, path, None),
+                    Field::PerceptionInput {
+                        cycler_instance,
+                        path,
+                        ..
+                    } => (
+                        InputKind::PerceptionInput,
+                        path,
+                        Some(cycler_instance.clone()),
+                    ),
+                    _ => continue,
+                };
+                let producing_cycler_module = match &cycler_instance {
+                    Some(cycler_instance) => cycler_instances
+                        .instances_to_modules
+                        .get(cycler_instance)
+                        .map(String::as_str),
+                    None => Some(node.cycler_module.as_str()),
+                };
+                let producer = producing_cycler_module
+                    .zip(path.first())
+                    .and_then(|(cycler_module, segment)| {
+                        main_outputs_to_nodes.get(&(cycler_module, segment.name.clone()))
+                    })
+                    .map(|producer| producer.to_string());
+                edges.push(Edge {
+                    producer,
+                    consumer: consumer.clone(),
+                    kind,
+                    path: path_to_string(path),
+                    cycler_instance,
+                });
+            }
+        }
+
+        Ok(Self { cyclers, edges })
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph dataflow {{").unwrap();
+        writeln!(dot, "    rankdir=LR;").unwrap();
+        writeln!(dot, "    node [shape=record, fontname=monospace];").unwrap();
+        for cycler in self.cyclers.iter() {
+            writeln!(dot, "    subgraph \"cluster_{}\" {{", cycler.module).unwrap();
+            writeln!(
+                dot,
+                "        label=\"{} ({}: {})\";",
+                cycler.module,
+                cycler.cycler_type,
+                cycler.instances.join(", ")
+            )
+            .unwrap();
+            for (index, node) in cycler.nodes.iter().enumerate() {
+                let main_outputs: String = node
+                    .main_outputs
+                    .iter()
+                    .map(|main_output| {
+                        format!(
+                            "{}: {}\\l",
+                            escape_record(&main_output.name),
+                            escape_record(&main_output.data_type)
+                        )
+                    })
+                    .collect();
+                let parameters: String = node
+                    .parameters
+                    .iter()
+                    .map(|parameter| format!("{}\\l", escape_record(parameter)))
+                    .collect();
+                writeln!(
+                    dot,
+                    "        \"{}\" [label=\"{{{}. {}|{}|{}}}\"];",
+                    node.name,
+                    index + 1,
+                    node.name,
+                    main_outputs,
+                    parameters
+                )
+                .unwrap();
+            }
+            for (first, second) in cycler.nodes.iter().zip(cycler.nodes.iter().skip(1)) {
+                writeln!(
+                    dot,
+                    "        \"{}\" -> \"{}\" [style=invis];",
+                    first.name, second.name
+                )
+                .unwrap();
+            }
+            writeln!(dot, "    }}").unwrap();
+        }
+        for edge in self.edges.iter() {
+            let Some(producer) = &edge.producer else {
+                continue;
+            };
+            let style = match edge.kind {
+                InputKind::Input => "solid",
+                InputKind::RequiredInput => "bold",
+                InputKind::HistoricInput => "dashed",
+                InputKind::PerceptionInput => "dotted",
+            };
+            let label = match &edge.cycler_instance {
+                Some(cycler_instance) => format!("{cycler_instance}: {}", edge.path),
+                None => edge.path.clone(),
+            };
+            writeln!(
+                dot,
+                "    \"{producer}\" -> \"{}\" [label=\"{label}\", style={style}];",
+                edge.consumer
+            )
+            .unwrap();
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+}
+
+fn path_to_string(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| {
+            format!(
+                "{}{}{}",
+                if segment.is_variable { "$" } else { "" },
+                segment.name,
+                if segment.is_optional { "?" } else { "" }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn escape_record(text: &str) -> String {
+    text.chars()
+        .flat_map(|character| match character {
+            '<' | '>' | '{' | '}' | '|' | '"' => vec!['\\', character],
+            character => vec![character],
+        })
+        .collect()
+}
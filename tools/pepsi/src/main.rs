@@ -30,6 +30,7 @@ mod cargo;
 mod change_ip;
 mod communication;
 mod completions;
+mod dataflow;
 mod gammaray;
 mod hulk;
 mod location;
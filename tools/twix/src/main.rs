@@ -12,7 +12,8 @@ use communication::client::ConnectionStatus;
 use completion_edit::CompletionEdit;
 use eframe::{
     egui::{
-        CentralPanel, Context, Key, Modifiers, TopBottomPanel, Ui, Visuals, Widget, WidgetText,
+        CentralPanel, Context, Key, Modifiers, TextEdit, TopBottomPanel, Ui, Visuals, Widget,
+        WidgetText,
     },
     epaint::Color32,
     run_native, App, CreationContext, Frame, NativeOptions, Storage,
@@ -24,7 +25,7 @@ use nao::Nao;
 use panel::Panel;
 use panels::{
     BehaviorSimulatorPanel, ImagePanel, ImageSegmentsPanel, ManualCalibrationPanel, MapPanel,
-    ParameterPanel, PlotPanel, TextPanel,
+    ParameterHistoryPanel, ParameterPanel, PlotPanel, TextPanel, TimelinePanel,
 };
 use serde_json::{from_str, to_string, Value};
 use tokio::sync::mpsc;
@@ -74,7 +75,9 @@ enum SelectablePanel {
     ImageSegments(ImageSegmentsPanel),
     Map(MapPanel),
     Parameter(ParameterPanel),
+    ParameterHistory(ParameterHistoryPanel),
     ManualCalibration(ManualCalibrationPanel),
+    Timeline(TimelinePanel),
 }
 
 impl SelectablePanel {
@@ -99,9 +102,13 @@ impl SelectablePanel {
             "image segments" => SelectablePanel::ImageSegments(ImageSegmentsPanel::new(nao, value)),
             "map" => SelectablePanel::Map(MapPanel::new(nao, value)),
             "parameter" => SelectablePanel::Parameter(ParameterPanel::new(nao, value)),
+            "parameter history" => {
+                SelectablePanel::ParameterHistory(ParameterHistoryPanel::new(nao, value))
+            }
             "manual calibration" => {
                 SelectablePanel::ManualCalibration(ManualCalibrationPanel::new(nao, value))
             }
+            "timeline" => SelectablePanel::Timeline(TimelinePanel::new(nao, value)),
             name => bail!("unexpected panel name: {name}"),
         })
     }
@@ -115,7 +122,9 @@ impl SelectablePanel {
             SelectablePanel::ImageSegments(panel) => panel.save(),
             SelectablePanel::Map(panel) => panel.save(),
             SelectablePanel::Parameter(panel) => panel.save(),
+            SelectablePanel::ParameterHistory(panel) => panel.save(),
             SelectablePanel::ManualCalibration(panel) => panel.save(),
+            SelectablePanel::Timeline(panel) => panel.save(),
         };
         value["_panel_type"] = Value::String(self.to_string());
 
@@ -133,7 +142,9 @@ impl Widget for &mut SelectablePanel {
             SelectablePanel::ImageSegments(panel) => panel.ui(ui),
             SelectablePanel::Map(panel) => panel.ui(ui),
             SelectablePanel::Parameter(panel) => panel.ui(ui),
+            SelectablePanel::ParameterHistory(panel) => panel.ui(ui),
             SelectablePanel::ManualCalibration(panel) => panel.ui(ui),
+            SelectablePanel::Timeline(panel) => panel.ui(ui),
         }
     }
 }
@@ -148,7 +159,9 @@ impl Display for SelectablePanel {
             SelectablePanel::ImageSegments(_) => ImageSegmentsPanel::NAME,
             SelectablePanel::Map(_) => MapPanel::NAME,
             SelectablePanel::Parameter(_) => ParameterPanel::NAME,
+            SelectablePanel::ParameterHistory(_) => ParameterHistoryPanel::NAME,
             SelectablePanel::ManualCalibration(_) => ManualCalibrationPanel::NAME,
+            SelectablePanel::Timeline(_) => TimelinePanel::NAME,
         };
         f.write_str(panel_name)
     }
@@ -158,6 +171,7 @@ struct TwixApp {
     nao: Arc<Nao>,
     connection_intent: bool,
     ip_address: String,
+    token: String,
     panel_selection: String,
     last_focused_tab: (NodeIndex, TabIndex),
     tree: Tree<SelectablePanel>,
@@ -181,7 +195,13 @@ impl TwixApp {
             })
             .unwrap_or(false);
 
+        let token = creation_context
+            .storage
+            .and_then(|storage| storage.get_string("token"))
+            .unwrap_or_default();
+
         let nao = Arc::new(Nao::new(ip_address.clone(), connection_intent));
+        nao.set_token(&token);
 
         let tree: Option<Tree<Value>> = creation_context
             .storage
@@ -212,6 +232,7 @@ impl TwixApp {
             nao,
             connection_intent,
             ip_address: ip_address.unwrap_or_default(),
+            token,
             panel_selection,
             tree,
             last_focused_tab: (0.into(), 0.into()),
@@ -238,6 +259,15 @@ impl App for TwixApp {
                 if address_input.changed() || address_input.lost_focus() {
                     self.nao.set_address(&self.ip_address);
                 }
+                let token_input = ui.add(
+                    TextEdit::singleline(&mut self.token)
+                        .password(true)
+                        .hint_text("Token")
+                        .desired_width(80.0),
+                );
+                if token_input.lost_focus() {
+                    self.nao.set_token(&self.token);
+                }
                 let (connect_text, color) = match &self.connection_status {
                     ConnectionStatus::Disconnected { connect, .. } => (
                         "Connect",
@@ -274,7 +304,9 @@ impl App for TwixApp {
                         "Image Segments".to_string(),
                         "Map".to_string(),
                         "Parameter".to_string(),
+                        "Parameter History".to_string(),
                         "Manual Calibration".to_string(),
+                        "Timeline".to_string(),
                     ],
                 )
                 .ui(ui);
@@ -321,6 +353,7 @@ impl App for TwixApp {
 
         storage.set_string("tree", to_string(&tree).unwrap());
         storage.set_string("ip_address", self.ip_address.clone());
+        storage.set_string("token", self.token.clone());
         storage.set_string(
             "connection_intent",
             if self.connection_intent {
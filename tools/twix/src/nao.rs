@@ -1,12 +1,19 @@
-use std::{collections::BTreeSet, sync::Mutex};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Mutex,
+};
 
 use communication::{
     client::{Communication, ConnectionStatus, CyclerOutput},
-    messages::{Fields, Path},
+    messages::{Fields, Format, ParameterChange, Path, Rate},
 };
 
 use serde_json::Value;
-use tokio::runtime::{Builder, Runtime};
+use serialize_hierarchy::{Constraints, TypeDescription};
+use tokio::{
+    runtime::{Builder, Runtime},
+    sync::oneshot,
+};
 
 use crate::{image_buffer::ImageBuffer, value_buffer::ValueBuffer};
 
@@ -49,14 +56,24 @@ impl Nao {
         );
     }
 
+    pub fn set_token(&self, token: &str) {
+        let token = (!token.is_empty()).then(|| token.to_string());
+        self.runtime.block_on(self.communication.set_token(token));
+    }
+
     pub fn subscribe_output(&self, output: CyclerOutput) -> ValueBuffer {
         let _guard = self.runtime.enter();
-        ValueBuffer::output(self.communication.clone(), output)
+        ValueBuffer::output(self.communication.clone(), output, Format::Textual)
     }
 
-    pub fn subscribe_image(&self, output: CyclerOutput) -> ImageBuffer {
+    pub fn subscribe_compressed_output(&self, output: CyclerOutput) -> ValueBuffer {
         let _guard = self.runtime.enter();
-        ImageBuffer::new(self.communication.clone(), output)
+        ValueBuffer::output(self.communication.clone(), output, Format::Compressed)
+    }
+
+    pub fn subscribe_image(&self, output: CyclerOutput, rate: Rate) -> ImageBuffer {
+        let _guard = self.runtime.enter();
+        ImageBuffer::new(self.communication.clone(), output, rate)
     }
 
     pub fn subscribe_parameter(&self, path: &str) -> ValueBuffer {
@@ -84,10 +101,36 @@ impl Nao {
             .block_on(self.communication.get_parameter_fields())
     }
 
+    pub fn get_parameter_constraints(&self) -> Option<BTreeMap<Path, Constraints>> {
+        self.runtime
+            .block_on(self.communication.get_parameter_constraints())
+    }
+
+    pub fn get_parameter_types(&self) -> Option<BTreeMap<Path, TypeDescription>> {
+        self.runtime
+            .block_on(self.communication.get_parameter_types())
+    }
+
     pub fn update_parameter_value(&self, path: &str, value: Value) {
         self.runtime
             .block_on(self.communication.update_parameter_value(path, value));
     }
+
+    /// Requests the history without blocking, the receiver is meant to be polled every frame
+    pub fn request_parameter_history(&self) -> oneshot::Receiver<Option<Vec<ParameterChange>>> {
+        let (sender, receiver) = oneshot::channel();
+        let communication = self.communication.clone();
+        self.runtime.spawn(async move {
+            // the panel may have been closed in the meantime
+            let _ = sender.send(communication.get_parameter_history().await);
+        });
+        receiver
+    }
+
+    pub fn revert_parameters_to(&self, change_id: usize) {
+        self.runtime
+            .block_on(self.communication.revert_parameters_to(change_id));
+    }
 }
 
 fn ip_address_to_communication_url(ip_address: &str) -> String {
@@ -1,7 +1,10 @@
-use std::{str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc, time::Duration};
 
 use color_eyre::{eyre::eyre, Result};
-use communication::client::{Cycler, CyclerOutput, Output};
+use communication::{
+    client::{Cycler, CyclerOutput, Output},
+    messages::Rate,
+};
 use eframe::{
     egui::{ComboBox, Response, TextureOptions, Ui, Widget},
     emath::Rect,
@@ -44,12 +47,33 @@ impl ImageKind {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+enum ImageRate {
+    Full,
+    TenHertz,
+    TwoHertz,
+    OneHertz,
+}
+
+impl ImageRate {
+    fn as_rate(&self) -> Rate {
+        let interval = match self {
+            ImageRate::Full => return Rate::EveryCycle,
+            ImageRate::TenHertz => Duration::from_millis(100),
+            ImageRate::TwoHertz => Duration::from_millis(500),
+            ImageRate::OneHertz => Duration::from_secs(1),
+        };
+        Rate::MinimumInterval { interval }
+    }
+}
+
 pub struct ImagePanel {
     nao: Arc<Nao>,
     image_buffer: ImageBuffer,
     cycler_selector: VisionCyclerSelector,
     overlays: Overlays,
     image_kind: ImageKind,
+    image_rate: ImageRate,
 }
 
 impl Panel for ImagePanel {
@@ -76,11 +100,15 @@ impl Panel for ImagePanel {
             .and_then(|value| value.get("image_kind"))
             .and_then(|value| from_value(value.clone()).ok())
             .unwrap_or(ImageKind::YCbCr422);
+        let image_rate = value
+            .and_then(|value| value.get("image_rate"))
+            .and_then(|value| from_value(value.clone()).ok())
+            .unwrap_or(ImageRate::Full);
         let output = CyclerOutput {
             cycler,
             output: image_kind.as_output(),
         };
-        let image_buffer = nao.subscribe_image(output);
+        let image_buffer = nao.subscribe_image(output, image_rate.as_rate());
         let cycler_selector = VisionCyclerSelector::new(cycler);
         let overlays = Overlays::new(
             nao.clone(),
@@ -93,6 +121,7 @@ impl Panel for ImagePanel {
             cycler_selector,
             overlays,
             image_kind,
+            image_rate,
         }
     }
 
@@ -100,11 +129,13 @@ impl Panel for ImagePanel {
         let cycler = self.cycler_selector.selected_cycler();
         let overlays = self.overlays.save();
         let image_kind = format!("{:?}", self.image_kind);
+        let image_rate = format!("{:?}", self.image_rate);
 
         json!({
             "cycler": cycler.to_string(),
             "overlays": overlays,
             "image_kind": image_kind,
+            "image_rate": image_rate,
         })
     }
 }
@@ -117,7 +148,7 @@ impl Widget for &mut ImagePanel {
                     cycler: self.cycler_selector.selected_cycler(),
                     output: self.image_kind.as_output(),
                 };
-                self.image_buffer = self.nao.subscribe_image(output);
+                self.image_buffer = self.nao.subscribe_image(output, self.image_rate.as_rate());
                 self.overlays
                     .update_cycler(self.cycler_selector.selected_cycler());
             }
@@ -138,12 +169,29 @@ impl Widget for &mut ImagePanel {
                         image_selection_changed = true;
                     }
                 });
+            ComboBox::from_label("Rate")
+                .selected_text(format!("{:?}", self.image_rate))
+                .show_ui(ui, |ui| {
+                    for (image_rate, label) in [
+                        (ImageRate::Full, "Full"),
+                        (ImageRate::TenHertz, "10 Hz"),
+                        (ImageRate::TwoHertz, "2 Hz"),
+                        (ImageRate::OneHertz, "1 Hz"),
+                    ] {
+                        if ui
+                            .selectable_value(&mut self.image_rate, image_rate, label)
+                            .changed()
+                        {
+                            image_selection_changed = true;
+                        }
+                    }
+                });
             if image_selection_changed {
                 let output = CyclerOutput {
                     cycler: self.cycler_selector.selected_cycler(),
                     output: self.image_kind.as_output(),
                 };
-                self.image_buffer = self.nao.subscribe_image(output);
+                self.image_buffer = self.nao.subscribe_image(output, self.image_rate.as_rate());
                 self.overlays
                     .update_cycler(self.cycler_selector.selected_cycler());
             }
@@ -33,7 +33,7 @@ impl Overlay for BallDetection {
                 ))
                 .unwrap(),
             ),
-            ball_candidates: nao.subscribe_output(
+            ball_candidates: nao.subscribe_compressed_output(
                 CyclerOutput::from_str(&format!("{}.additional.ball_candidates", selected_cycler))
                     .unwrap(),
             ),
@@ -4,8 +4,10 @@ mod image_segments;
 mod manual_camera_calibration;
 mod map;
 mod parameter;
+mod parameter_history;
 mod plot;
 mod text;
+mod timeline;
 
 pub use self::behavior_simulator::BehaviorSimulatorPanel;
 pub use self::image::ImagePanel;
@@ -13,5 +15,7 @@ pub use image_segments::ImageSegmentsPanel;
 pub use manual_camera_calibration::ManualCalibrationPanel;
 pub use map::MapPanel;
 pub use parameter::ParameterPanel;
+pub use parameter_history::ParameterHistoryPanel;
 pub use plot::PlotPanel;
 pub use text::TextPanel;
+pub use timeline::TimelinePanel;
@@ -5,9 +5,10 @@ use crate::{
     repository_parameters::RepositoryParameters, value_buffer::ValueBuffer,
 };
 use color_eyre::{eyre::Context, Result};
-use eframe::egui::{Response, ScrollArea, TextEdit, Ui, Widget};
+use eframe::egui::{ComboBox, Response, ScrollArea, Slider, TextEdit, Ui, Widget};
 use log::error;
 use serde_json::{json, Value};
+use serialize_hierarchy::{Constraints, Primitive, TypeDescription};
 use tokio::sync::mpsc;
 
 pub struct ParameterPanel {
@@ -101,6 +102,22 @@ impl Widget for &mut ParameterPanel {
                 );
             });
 
+            let constraints = self
+                .nao
+                .get_parameter_constraints()
+                .and_then(|constraints| constraints.get(&self.path).cloned());
+            if let Some(constraints) = constraints {
+                self.add_constraints(ui, &constraints);
+            }
+
+            let type_description = self
+                .nao
+                .get_parameter_types()
+                .and_then(|types| types.get(&self.path).cloned());
+            if let Some(type_description) = type_description {
+                self.add_type_editor(ui, &type_description);
+            }
+
             if let Some(buffer) = &self.value_buffer {
                 match buffer.get_latest() {
                     Ok(value) => {
@@ -125,6 +142,73 @@ impl Widget for &mut ParameterPanel {
     }
 }
 
+impl ParameterPanel {
+    fn add_constraints(&mut self, ui: &mut Ui, constraints: &Constraints) {
+        let unit = constraints.unit.as_deref().unwrap_or_default();
+        match (
+            constraints.minimum,
+            constraints.maximum,
+            self.parameter_value.trim().parse::<f64>(),
+        ) {
+            (Some(minimum), Some(maximum), Ok(mut value)) => {
+                let slider = ui.add(Slider::new(&mut value, minimum..=maximum).text(unit));
+                if slider.changed() {
+                    self.parameter_value = value.to_string();
+                }
+                // sending every intermediate value while dragging would flood the history
+                if slider.drag_released() || slider.lost_focus() {
+                    self.nao.update_parameter_value(&self.path, json!(value));
+                }
+            }
+            (minimum, maximum, _) => {
+                let format_bound =
+                    |bound: Option<f64>| bound.map_or("..".to_string(), |bound| bound.to_string());
+                let mut description = match (minimum, maximum) {
+                    (None, None) => String::new(),
+                    _ => format!("[{}, {}] ", format_bound(minimum), format_bound(maximum)),
+                };
+                description.push_str(unit);
+                if constraints.non_empty {
+                    description.push_str(" (must not be empty)");
+                }
+                ui.label(description.trim());
+            }
+        }
+    }
+
+    fn add_type_editor(&mut self, ui: &mut Ui, type_description: &TypeDescription) {
+        match type_description {
+            TypeDescription::Primitive(Primitive::Boolean) => {
+                if let Ok(mut value) = self.parameter_value.trim().parse::<bool>() {
+                    if ui.checkbox(&mut value, "").changed() {
+                        self.parameter_value = value.to_string();
+                        self.nao.update_parameter_value(&self.path, json!(value));
+                    }
+                }
+            }
+            TypeDescription::Enum { variants } => {
+                // only unit variants are serialized as plain strings
+                let Ok(current) = serde_json::from_str::<String>(&self.parameter_value) else {
+                    return;
+                };
+                let mut selected = current.clone();
+                ComboBox::from_id_source(&self.path)
+                    .selected_text(current.as_str())
+                    .show_ui(ui, |ui| {
+                        for variant in variants {
+                            ui.selectable_value(&mut selected, variant.clone(), variant.as_str());
+                        }
+                    });
+                if selected != current {
+                    self.parameter_value = json!(selected).to_string();
+                    self.nao.update_parameter_value(&self.path, json!(selected));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 pub fn add_save_button<SerdesJsonValueProvider>(
     ui: &mut Ui,
     parameter_path: &str,
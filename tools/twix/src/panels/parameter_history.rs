@@ -0,0 +1,105 @@
+use std::{sync::Arc, time::SystemTime};
+
+use communication::messages::ParameterChange;
+use eframe::egui::{Grid, Response, ScrollArea, Ui, Widget};
+use serde_json::Value;
+use tokio::sync::oneshot::{self, error::TryRecvError};
+
+use crate::{nao::Nao, panel::Panel};
+
+pub struct ParameterHistoryPanel {
+    nao: Arc<Nao>,
+    history: Option<Vec<ParameterChange>>,
+    pending_history: Option<oneshot::Receiver<Option<Vec<ParameterChange>>>>,
+}
+
+impl Panel for ParameterHistoryPanel {
+    const NAME: &'static str = "Parameter History";
+
+    fn new(nao: Arc<Nao>, _value: Option<&Value>) -> Self {
+        let pending_history = Some(nao.request_parameter_history());
+        Self {
+            nao,
+            history: None,
+            pending_history,
+        }
+    }
+}
+
+impl ParameterHistoryPanel {
+    fn poll_history(&mut self) {
+        let Some(pending_history) = &mut self.pending_history else {
+            return;
+        };
+        match pending_history.try_recv() {
+            Ok(history) => {
+                self.history = history;
+                self.pending_history = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Closed) => {
+                self.history = None;
+                self.pending_history = None;
+            }
+        }
+    }
+}
+
+impl Widget for &mut ParameterHistoryPanel {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.vertical(|ui| {
+            self.poll_history();
+            if ui.button("Refresh").clicked() {
+                self.pending_history = Some(self.nao.request_parameter_history());
+            }
+            if self.pending_history.is_some() {
+                ui.ctx().request_repaint();
+            }
+            let Some(history) = &self.history else {
+                match self.pending_history {
+                    Some(_) => ui.label("Loading..."),
+                    None => ui.label("Not connected"),
+                };
+                return;
+            };
+            let mut revert_to = None;
+            ScrollArea::both().show(ui, |ui| {
+                Grid::new("parameter_history")
+                    .num_columns(6)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Age");
+                        ui.strong("Client");
+                        ui.strong("Path");
+                        ui.strong("Old");
+                        ui.strong("New");
+                        ui.label("");
+                        ui.end_row();
+                        for change in history.iter().rev() {
+                            let age = SystemTime::now()
+                                .duration_since(change.timestamp)
+                                .unwrap_or_default();
+                            ui.label(format!("{:.0}s", age.as_secs_f32()));
+                            ui.label(change.client_id.to_string());
+                            ui.label(&change.path);
+                            ui.monospace(change.old_value.to_string());
+                            ui.monospace(change.new_value.to_string());
+                            if ui
+                                .button("Undo")
+                                .on_hover_text("Undo this and all later changes")
+                                .clicked()
+                            {
+                                revert_to = Some(change.id);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+            if let Some(change_id) = revert_to {
+                self.nao.revert_parameters_to(change_id);
+                self.pending_history = Some(self.nao.request_parameter_history());
+            }
+        })
+        .response
+    }
+}
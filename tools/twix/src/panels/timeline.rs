@@ -0,0 +1,409 @@
+use std::{
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use communication::client::CyclerOutput;
+use eframe::{
+    egui::{pos2, vec2, ComboBox, Response, Sense, Slider, Ui, Widget},
+    epaint::{Color32, Stroke},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use types::{TimelineMarker, TimelineMarkerKind};
+
+use crate::{nao::Nao, panel::Panel, value_buffer::ValueBuffer};
+
+const TIMELINE_HEIGHT: f32 = 30.0;
+const MARKER_HOVER_DISTANCE: f32 = 3.0;
+// the behavior simulator cycles every 12 ms, one frame per cycle
+const BEHAVIOR_SIMULATOR_FRAME_DURATION: Duration = Duration::from_millis(12);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Source {
+    Recording,
+    BehaviorSimulator,
+}
+
+impl Source {
+    fn from_value(value: Option<&Value>) -> Self {
+        match value
+            .and_then(|value| value.get("source"))
+            .and_then(|source| source.as_str())
+        {
+            Some("BehaviorSimulator") => Source::BehaviorSimulator,
+            _ => Source::Recording,
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+struct FrameTimes {
+    previous: Option<Duration>,
+    next: Option<Duration>,
+}
+
+enum Buffers {
+    Recording {
+        duration: ValueBuffer,
+        current_time: ValueBuffer,
+        playing: ValueBuffer,
+        markers: ValueBuffer,
+        control_frame_times: ValueBuffer,
+    },
+    BehaviorSimulator {
+        frame_count: ValueBuffer,
+        selected_frame: ValueBuffer,
+        markers: ValueBuffer,
+    },
+}
+
+impl Buffers {
+    fn subscribe(nao: &Nao, source: Source) -> Self {
+        match source {
+            Source::Recording => Buffers::Recording {
+                duration: nao.subscribe_output(
+                    CyclerOutput::from_str("Timeline.main_outputs.duration").unwrap(),
+                ),
+                current_time: nao.subscribe_output(
+                    CyclerOutput::from_str("Timeline.main_outputs.current_time").unwrap(),
+                ),
+                playing: nao.subscribe_output(
+                    CyclerOutput::from_str("Timeline.main_outputs.playing").unwrap(),
+                ),
+                markers: nao.subscribe_output(
+                    CyclerOutput::from_str("Timeline.main_outputs.markers").unwrap(),
+                ),
+                control_frame_times: nao.subscribe_output(
+                    CyclerOutput::from_str("Timeline.main_outputs.control").unwrap(),
+                ),
+            },
+            Source::BehaviorSimulator => Buffers::BehaviorSimulator {
+                frame_count: nao.subscribe_output(
+                    CyclerOutput::from_str("BehaviorSimulator.main_outputs.frame_count").unwrap(),
+                ),
+                selected_frame: nao.subscribe_parameter("selected_frame"),
+                markers: nao.subscribe_output(
+                    CyclerOutput::from_str("BehaviorSimulator.main_outputs.markers").unwrap(),
+                ),
+            },
+        }
+    }
+}
+
+/// The frame shown at a time is computed from the start of playing, not from the last received
+/// frame, which lags behind by the round trip to the behavior simulator
+struct BehaviorSimulatorPlayback {
+    started_at: Instant,
+    start_frame: f64,
+    shown_frame: f64,
+}
+
+impl BehaviorSimulatorPlayback {
+    fn starting_at(frame: f64) -> Self {
+        Self {
+            started_at: Instant::now(),
+            start_frame: frame,
+            shown_frame: frame,
+        }
+    }
+
+    fn current_frame(&self, end: f64) -> f64 {
+        let elapsed_frames = self.started_at.elapsed().as_secs_f64()
+            / BEHAVIOR_SIMULATOR_FRAME_DURATION.as_secs_f64();
+        (self.start_frame + elapsed_frames.floor()).min(end)
+    }
+}
+
+struct TimelineState {
+    end: f64,
+    cursor: f64,
+    markers: Vec<TimelineMarker<f64>>,
+}
+
+pub struct TimelinePanel {
+    nao: Arc<Nao>,
+    source: Source,
+    buffers: Buffers,
+    behavior_simulator_playback: Option<BehaviorSimulatorPlayback>,
+    playback_speed: f32,
+}
+
+impl Panel for TimelinePanel {
+    const NAME: &'static str = "Timeline";
+
+    fn new(nao: Arc<Nao>, value: Option<&Value>) -> Self {
+        let source = Source::from_value(value);
+        let buffers = Buffers::subscribe(&nao, source);
+        Self {
+            nao,
+            source,
+            buffers,
+            behavior_simulator_playback: None,
+            playback_speed: 1.0,
+        }
+    }
+
+    fn save(&self) -> Value {
+        json!({
+            "source": format!("{:?}", self.source),
+        })
+    }
+}
+
+impl TimelinePanel {
+    fn state(&self) -> TimelineState {
+        match &self.buffers {
+            Buffers::Recording {
+                duration,
+                current_time,
+                markers,
+                ..
+            } => TimelineState {
+                end: duration
+                    .require_latest::<Duration>()
+                    .unwrap_or_default()
+                    .as_secs_f64(),
+                cursor: current_time
+                    .require_latest::<Duration>()
+                    .unwrap_or_default()
+                    .as_secs_f64(),
+                markers: markers
+                    .parse_latest::<Vec<TimelineMarker<Duration>>>()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|marker| TimelineMarker {
+                        position: marker.position.as_secs_f64(),
+                        kind: marker.kind,
+                        description: marker.description,
+                    })
+                    .collect(),
+            },
+            Buffers::BehaviorSimulator {
+                frame_count,
+                selected_frame,
+                markers,
+            } => TimelineState {
+                end: frame_count
+                    .require_latest::<usize>()
+                    .unwrap_or(1)
+                    .saturating_sub(1) as f64,
+                cursor: selected_frame.require_latest::<usize>().unwrap_or_default() as f64,
+                markers: markers
+                    .parse_latest::<Vec<TimelineMarker<usize>>>()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|marker| TimelineMarker {
+                        position: marker.position as f64,
+                        kind: marker.kind,
+                        description: marker.description,
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    fn seek(&mut self, position: f64) {
+        if let Some(playback) = &mut self.behavior_simulator_playback {
+            *playback = BehaviorSimulatorPlayback::starting_at(position);
+        }
+        self.send_seek(position);
+    }
+
+    fn send_seek(&self, position: f64) {
+        match self.source {
+            Source::Recording => self.nao.update_parameter_value(
+                "seek",
+                json!({
+                    "id": request_id(),
+                    "time": Duration::from_secs_f64(position.max(0.0)),
+                }),
+            ),
+            Source::BehaviorSimulator => self
+                .nao
+                .update_parameter_value("selected_frame", (position.round() as usize).into()),
+        }
+    }
+
+    fn step(&mut self, state: &TimelineState, forward: bool) {
+        match &self.buffers {
+            Buffers::Recording {
+                control_frame_times,
+                ..
+            } => {
+                let frame_times = control_frame_times
+                    .require_latest::<FrameTimes>()
+                    .unwrap_or_default();
+                let target = match forward {
+                    true => frame_times.next,
+                    false => frame_times.previous,
+                };
+                if let Some(target) = target {
+                    self.send_seek(target.as_secs_f64());
+                }
+            }
+            Buffers::BehaviorSimulator { .. } => {
+                let cursor = match &self.behavior_simulator_playback {
+                    Some(playback) => playback.shown_frame,
+                    None => state.cursor,
+                };
+                let step = if forward { 1.0 } else { -1.0 };
+                self.seek((cursor + step).clamp(0.0, state.end));
+            }
+        }
+    }
+
+    fn controls(&mut self, ui: &mut Ui, state: &TimelineState) {
+        ui.horizontal(|ui| {
+            let previous_source = self.source;
+            ComboBox::from_label("Source")
+                .selected_text(format!("{:?}", self.source))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.source, Source::Recording, "Recording");
+                    ui.selectable_value(
+                        &mut self.source,
+                        Source::BehaviorSimulator,
+                        "BehaviorSimulator",
+                    );
+                });
+            if self.source != previous_source {
+                self.buffers = Buffers::subscribe(&self.nao, self.source);
+                self.behavior_simulator_playback = None;
+            }
+
+            if ui.button("⏴").clicked() {
+                self.step(state, false);
+            }
+            match &self.buffers {
+                Buffers::Recording { playing, .. } => {
+                    let mut playing = playing.require_latest::<bool>().unwrap_or_default();
+                    if ui.checkbox(&mut playing, "Play").changed() {
+                        self.nao.update_parameter_value(
+                            "play",
+                            json!({
+                                "id": request_id(),
+                                "playing": playing,
+                            }),
+                        );
+                    }
+                }
+                Buffers::BehaviorSimulator { .. } => {
+                    let mut playing = self.behavior_simulator_playback.is_some();
+                    if ui.checkbox(&mut playing, "Play").changed() {
+                        self.behavior_simulator_playback =
+                            playing.then(|| BehaviorSimulatorPlayback::starting_at(state.cursor));
+                    }
+                }
+            }
+            if ui.button("⏵").clicked() {
+                self.step(state, true);
+            }
+            if self.source == Source::Recording
+                && ui
+                    .add(Slider::new(&mut self.playback_speed, 0.1..=4.0).text("Speed"))
+                    .changed()
+            {
+                self.nao
+                    .update_parameter_value("playback_speed", self.playback_speed.into());
+            }
+
+            match self.source {
+                Source::Recording => {
+                    ui.label(format!("{:.2} s / {:.2} s", state.cursor, state.end))
+                }
+                Source::BehaviorSimulator => {
+                    ui.label(format!("Frame {} / {}", state.cursor, state.end))
+                }
+            };
+        });
+    }
+}
+
+impl Widget for &mut TimelinePanel {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let state = self.state();
+        if let Some(playback) = &mut self.behavior_simulator_playback {
+            let frame = playback.current_frame(state.end);
+            ui.ctx().request_repaint();
+            if frame != playback.shown_frame {
+                playback.shown_frame = frame;
+                self.send_seek(frame);
+            }
+        }
+
+        ui.vertical(|ui| {
+            self.controls(ui, &state);
+            let response = timeline(ui, &state);
+            if let Some(pointer) = response.interact_pointer_pos() {
+                let fraction = (pointer.x - response.rect.left()) / response.rect.width();
+                self.seek(fraction.clamp(0.0, 1.0) as f64 * state.end);
+            }
+            response
+        })
+        .inner
+    }
+}
+
+/// Requests of the timeline server are only handled if their id differs from the previous one
+fn request_id() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+}
+
+fn timeline(ui: &mut Ui, state: &TimelineState) -> Response {
+    let (rect, response) = ui.allocate_exact_size(
+        vec2(ui.available_width(), TIMELINE_HEIGHT),
+        Sense::click_and_drag(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let to_x = |position: f64| {
+        let fraction = match state.end > 0.0 {
+            true => (position / state.end) as f32,
+            false => 0.0,
+        };
+        rect.left() + fraction.clamp(0.0, 1.0) * rect.width()
+    };
+
+    for marker in state.markers.iter() {
+        let color = match marker.kind {
+            TimelineMarkerKind::GameStateChange => Color32::LIGHT_BLUE,
+            TimelineMarkerKind::Fall => Color32::RED,
+            TimelineMarkerKind::Penalty => Color32::YELLOW,
+        };
+        let x = to_x(marker.position);
+        painter.line_segment(
+            [pos2(x, rect.top()), pos2(x, rect.bottom())],
+            Stroke::new(2.0, color),
+        );
+    }
+
+    let x = to_x(state.cursor);
+    painter.line_segment(
+        [pos2(x, rect.top()), pos2(x, rect.bottom())],
+        Stroke::new(3.0, ui.visuals().strong_text_color()),
+    );
+
+    let hovered_markers: Vec<_> = response
+        .hover_pos()
+        .map(|pointer| {
+            state
+                .markers
+                .iter()
+                .filter(|marker| (to_x(marker.position) - pointer.x).abs() < MARKER_HOVER_DISTANCE)
+                .collect()
+        })
+        .unwrap_or_default();
+    if hovered_markers.is_empty() {
+        return response;
+    }
+    response.on_hover_ui_at_pointer(|ui| {
+        for marker in hovered_markers {
+            ui.label(format!("{:?}: {}", marker.kind, marker.description));
+        }
+    })
+}
@@ -6,9 +6,9 @@ use color_eyre::{
 };
 use communication::{
     client::{Communication, CyclerOutput, SubscriberMessage},
-    messages::Format,
+    compression::decompress_value,
+    messages::{Format, Rate},
 };
-use log::error;
 use serde::Deserialize;
 use serde_json::{from_value, Value, Value::Array};
 use tokio::{
@@ -43,11 +43,11 @@ pub struct ValueBuffer {
 }
 
 impl ValueBuffer {
-    pub fn output(communication: Communication, output: CyclerOutput) -> Self {
+    pub fn output(communication: Communication, output: CyclerOutput, format: Format) -> Self {
         let (command_sender, command_receiver) = mpsc::channel(10);
         spawn(async move {
             let (uuid, receiver) = communication
-                .subscribe_output(output.clone(), Format::Textual)
+                .subscribe_output(output.clone(), format, Rate::EveryCycle)
                 .await;
             value_buffer(receiver, command_receiver).await;
             communication.unsubscribe_output(uuid).await;
@@ -150,32 +150,39 @@ async fn value_buffer(
             maybe_message = subscriber_receiver.recv() => {
                 match maybe_message {
                     Some(message) => {
-                        match message {
-                            SubscriberMessage::Update{value:new_value} => {
-                                match &mut values {
-                                    Some(Ok(values)) => {
-                                        values.push_front(new_value);
-                                        values.truncate(buffer_capacity);
-                                    },
-                                    _ => {
-                                        let mut new_buffer = VecDeque::with_capacity(buffer_capacity);
-                                        new_buffer.push_back(new_value);
-                                        values = Some(Ok(new_buffer));
-                                    },
-                                }
-                                update_listeners.retain(|listener| {
-                                    if let Err(TrySendError::Closed(_)) = listener.try_send(()) {
-                                            return false;
-                                    }
-                                    true
-                                });
+                        let new_value = match message {
+                            SubscriberMessage::Update{value} => Some(value),
+                            SubscriberMessage::SubscriptionSuccess => None,
+                            SubscriberMessage::SubscriptionFailure{info} => {
+                                values = Some(Err(info));
+                                None
+                            },
+                            SubscriberMessage::UpdateBinary{data} => match decompress_value(&data) {
+                                Ok(value) => Some(value),
+                                Err(error) => {
+                                    values = Some(Err(format!("{error:?}")));
+                                    None
+                                },
                             },
-                            SubscriberMessage::SubscriptionSuccess => (),
-                            SubscriberMessage::SubscriptionFailure{info} => values = Some(Err(info)),
-                            SubscriberMessage::UpdateBinary{..} => {
-                                error!("Got UpdateBinary message in value buffer");
-                                break;
+                        };
+                        if let Some(new_value) = new_value {
+                            match &mut values {
+                                Some(Ok(values)) => {
+                                    values.push_front(new_value);
+                                    values.truncate(buffer_capacity);
+                                },
+                                _ => {
+                                    let mut new_buffer = VecDeque::with_capacity(buffer_capacity);
+                                    new_buffer.push_back(new_value);
+                                    values = Some(Ok(new_buffer));
+                                },
                             }
+                            update_listeners.retain(|listener| {
+                                if let Err(TrySendError::Closed(_)) = listener.try_send(()) {
+                                        return false;
+                                }
+                                true
+                            });
                         }
                     },
                     None => continue,